@@ -0,0 +1,27 @@
+//! Measures the wall-clock time of rendering a report with ten table views.
+//! Compare `cargo bench` with `cargo bench --no-default-features` to see the
+//! speedup of rendering views in parallel.
+use std::process::Command;
+use std::time::Instant;
+
+const RUNS: u32 = 5;
+
+fn main() {
+    let output = std::env::temp_dir().join("datavzrd-bench-render");
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        let status = Command::new(env!("CARGO_BIN_EXE_datavzrd"))
+            .arg("benches/ten-tables.yaml")
+            .arg("--overwrite-output")
+            .arg("--output")
+            .arg(&output)
+            .status()
+            .expect("failed to run datavzrd");
+        assert!(status.success());
+    }
+    println!(
+        "rendering ten tables took {:?} on average",
+        start.elapsed() / RUNS
+    );
+    std::fs::remove_dir_all(&output).expect("failed to remove benchmark output");
+}
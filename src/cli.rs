@@ -1,24 +1,27 @@
+use crate::spec::parse_separator;
 use std::path::PathBuf;
+use structopt::clap::AppSettings;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
 #[structopt(
     about = "A tool to create visual HTML reports from collections of CSV/TSV tables.",
-    name = "datavzrd"
+    name = "datavzrd",
+    setting = AppSettings::SubcommandsNegateReqs
 )]
 pub struct Datavzrd {
     // The number of occurrences of the `v/verbose` flag
     /// Verbose mode (-v, -vv, -vvv, etc.)
     #[structopt(short, long, parse(from_occurrences))]
-    pub(crate) _verbose: u8,
+    pub(crate) verbose: u8,
 
     /// Activates debug mode. Javascript files are not minified.
     #[structopt(long)]
     pub(crate) debug: bool,
 
-    /// Config file containing file paths and settings
-    #[structopt(name = "CONFIG", parse(from_os_str))]
-    pub(crate) config: PathBuf,
+    /// Config file containing file paths and settings. Datasets with path "-" are read from stdin.
+    #[structopt(name = "CONFIG", parse(from_os_str), required_unless = "dump-schema")]
+    pub(crate) config: Option<PathBuf>,
 
     /// Sets the URL of the webview host. Note that when using the link the row data can temporarily occur (in base64-encoded form) in the server logs of the given webview host.
     #[structopt(
@@ -33,7 +36,75 @@ pub struct Datavzrd {
     #[structopt(long)]
     pub(crate) overwrite_output: bool,
 
+    /// Renders all views again when overwriting a report. By default, views whose config and
+    /// data files are unchanged since the previous render are kept.
+    #[structopt(long)]
+    pub(crate) no_cache: bool,
+
+    /// Directory for caching datasets downloaded from HTTP(S) URLs across runs.
+    #[structopt(long, parse(from_os_str))]
+    pub(crate) cache_dir: Option<PathBuf>,
+
+    /// Refuses to download datasets from HTTP(S) URLs. Only cached copies are used.
+    #[structopt(long)]
+    pub(crate) offline: bool,
+
+    /// Disables the progress bars of rendered tables, e.g. for CI logs.
+    #[structopt(long)]
+    pub(crate) no_progress: bool,
+
+    /// Skips computing summary statistics of table columns, e.g. for very large tables.
+    #[structopt(long)]
+    pub(crate) no_stats: bool,
+
+    /// Writes the report as a single self-contained HTML file to the given output path instead
+    /// of a directory. Only the first page of the default view is included.
+    #[structopt(long)]
+    pub(crate) single_file: bool,
+
+    /// Only validates the config and the referenced datasets and files without writing any output.
+    #[structopt(long)]
+    pub(crate) validate: bool,
+
+    /// Keeps running and re-renders the report whenever the config or a local file referenced
+    /// by it changes. Errors are reported without stopping.
+    #[structopt(long)]
+    pub(crate) watch: bool,
+
+    /// Writes the JSON Schema of config files to the given path or to stdout instead of
+    /// rendering a report, e.g. for autocompletion in editors.
+    #[structopt(long)]
+    pub(crate) dump_schema: Option<Option<PathBuf>>,
+
     /// Output file
-    #[structopt(short, long, parse(from_os_str))]
-    pub(crate) output: PathBuf,
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        required_unless_one = &["validate", "dump-schema"]
+    )]
+    pub(crate) output: Option<PathBuf>,
+
+    #[structopt(subcommand)]
+    pub(crate) command: Option<Command>,
+}
+
+#[derive(StructOpt, Debug)]
+pub(crate) enum Command {
+    /// Prints the column types inferred for the given table along with statistics of its
+    /// values, e.g. to find columns whose type should be configured.
+    Classify {
+        /// Table to classify.
+        #[structopt(parse(from_os_str))]
+        path: PathBuf,
+
+        /// Separator of the table, given as a single ASCII character, an escape like \t or a
+        /// name like tab. Detected from the file if not given.
+        #[structopt(long, parse(try_from_str = parse_separator))]
+        separator: Option<char>,
+
+        /// Output format.
+        #[structopt(long, default_value = "table", possible_values = &["table", "json"])]
+        format: String,
+    },
 }
@@ -1,12 +1,18 @@
-use crate::render::portable::utils::{render_index_file, render_static_files};
-use crate::render::portable::ItemRenderer;
+use crate::render::portable::utils::{render_index_file, render_single_file, render_static_files};
+use crate::render::portable::{view_sizes, ItemRenderer};
 use crate::render::Renderer;
-use crate::spec::ItemsSpec;
+use crate::spec::{config_schema, DatasetSpecs, ItemsSpec};
+use crate::utils::column_type::{format_column_summaries, summarize_columns};
+use crate::utils::reader::remove_temporary_files;
+use crate::utils::remote::DownloadSettings;
+use crate::utils::render_cache::{cache_entries, read_cache, unchanged_views, write_cache};
+use crate::utils::watch::watch;
 use anyhow::{bail, Result};
-use log::LevelFilter;
+use log::{error, warn, LevelFilter};
 use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 use thiserror::Error;
 
@@ -19,33 +25,226 @@ pub(crate) mod utils;
 fn main() -> Result<()> {
     let opt = cli::Datavzrd::from_args();
     let _ = TermLogger::init(
-        LevelFilter::Warn,
+        if opt.verbose > 0 {
+            LevelFilter::Info
+        } else {
+            LevelFilter::Warn
+        },
         Config::default(),
         TerminalMode::Stderr,
         ColorChoice::Auto,
     );
-    let config = ItemsSpec::from_file(&opt.config)?;
+    if let Some(path) = &opt.dump_schema {
+        return dump_schema(path.as_deref());
+    }
+    if let Some(cli::Command::Classify {
+        path,
+        separator,
+        format,
+    }) = &opt.command
+    {
+        let classified = classify(&opt, path, *separator, format);
+        remove_temporary_files()?;
+        return classified;
+    }
+    let rendered = render(&opt, opt.overwrite_output);
+    // Remove buffered and downloaded datasets regardless of whether rendering succeeded.
+    remove_temporary_files()?;
+    if opt.watch {
+        if let Err(err) = &rendered {
+            error!("Failed to render report: {err:#}");
+        }
+        return watch(opt.config.as_ref().unwrap(), || {
+            // Re-rendered reports replace the previous ones.
+            let rendered = render(&opt, true);
+            remove_temporary_files()?;
+            rendered
+        });
+    }
+    rendered
+}
+
+fn render(opt: &cli::Datavzrd, overwrite_output: bool) -> Result<()> {
+    let download_settings = DownloadSettings {
+        cache_dir: opt.cache_dir.clone(),
+        offline: opt.offline,
+    };
+    // The config is required by the CLI unless only dumping the schema.
+    let config_path = opt.config.as_ref().unwrap();
+    let config = ItemsSpec::from_file(config_path, &download_settings)?;
     config.validate()?;
+    if opt.validate {
+        for (name, summary) in config.validation_summary()? {
+            println!("OK: {name} — {summary}");
+        }
+        return Ok(());
+    }
+    // The output is required by the CLI unless only validating the config.
+    let output_path = opt.output.clone().unwrap();
 
-    if !opt.output.exists() {
-        std::fs::create_dir(&opt.output)?;
-    } else if opt.output.read_dir()?.next().is_some() {
-        if opt.overwrite_output {
-            fs::remove_dir_all(&opt.output)?;
-            std::fs::create_dir(&opt.output)?;
+    let single_file = opt.single_file || config.single_file;
+    // Single file reports are rendered to a temporary directory first and then bundled.
+    let temp_dir = if single_file {
+        Some(tempfile::tempdir()?)
+    } else {
+        None
+    };
+    let output = temp_dir
+        .as_ref()
+        .map_or(output_path.clone(), |dir| dir.path().to_path_buf());
+    // Single file reports are always rendered completely.
+    let render_cache = if single_file || opt.no_cache {
+        None
+    } else {
+        let view_sizes: BTreeMap<_, _> = view_sizes(&config)?.into_iter().collect();
+        let context = (view_sizes, &opt.webview_url, opt.debug, opt.no_stats);
+        Some(cache_entries(config_path, &config, &context)?)
+    };
+    let mut skipped_views = HashSet::new();
+    if single_file {
+        if output_path.exists() && !overwrite_output {
+            bail!(OutputError::OutputFileExists { output_path })
+        }
+        if config.views.len() > 1 {
+            warn!("The single file report only contains the default view.");
+        }
+    } else if !output_path.exists() {
+        std::fs::create_dir(&output_path)?;
+    } else if output_path.read_dir()?.next().is_some() {
+        if overwrite_output {
+            if let Some(render_cache) = &render_cache {
+                skipped_views =
+                    unchanged_views(&read_cache(&output_path), render_cache, &output_path);
+            }
+            clear_directory(&output_path, &skipped_views)?;
         } else {
-            bail!(OutputError::OutputDirectoryNotEmpty {
-                output_path: opt.output
-            })
+            bail!(OutputError::OutputDirectoryNotEmpty { output_path })
+        }
+    }
+
+    render_index_file(&output, &config)?;
+    render_static_files(&output, &config.custom_css)?;
+
+    let padded_datasets: Vec<_> = config
+        .datasets
+        .iter()
+        .filter(|(_, dataset)| dataset.padded_rows > 0)
+        .map(|(name, dataset)| (name.to_owned(), dataset.padded_rows))
+        .collect();
+    let normalized_headers = config.normalized_headers()?;
+    let invalid_computed_values = config.invalid_computed_values()?;
+    let column_diagnostics = config.column_diagnostics()?;
+    let non_numeric_stripped_columns = config.non_numeric_stripped_columns()?;
+    let tolerated_non_numeric_values = config.tolerated_non_numeric_values()?;
+    let missing_images = config.missing_images()?;
+    let empty_columns = config.empty_columns(&column_diagnostics)?;
+
+    let renderer = ItemRenderer::builder()
+        .specs(config)
+        .show_progress(!opt.no_progress)
+        .column_stats(!opt.no_stats)
+        .skipped_views(skipped_views)
+        .build();
+    renderer.render_tables(&output, &opt.webview_url, opt.debug)?;
+    if single_file {
+        render_single_file(&output, &output_path)?;
+    }
+    if let Some(render_cache) = render_cache {
+        write_cache(&output_path, &render_cache)?;
+    }
+
+    for (name, rows) in padded_datasets {
+        warn!("Padded {rows} rows of dataset {name:?} with missing trailing fields.");
+    }
+    for (name, count) in invalid_computed_values {
+        warn!("Left {count} undefined computed values of view {name:?} empty, e.g. due to a division by zero or non-numeric operands.");
+    }
+    for (name, diagnostics) in &column_diagnostics {
+        for column in &diagnostics.mixed_boolean_columns {
+            warn!("Column {column:?} of dataset {name:?} mostly contains boolean values but is rendered as text because of other values. Consider configuring true-values and false-values for it.");
         }
     }
+    for (name, diagnostics) in &column_diagnostics {
+        for (column, row) in &diagnostics.mixed_percentage_columns {
+            warn!("Column {column:?} of dataset {name:?} is rendered as text because only some of its values are percentages, e.g. not the value in row {row}.");
+        }
+    }
+    for (name, diagnostics) in &column_diagnostics {
+        for column in &diagnostics.overflowing_integer_columns {
+            warn!("Column {column:?} of dataset {name:?} is treated as float because some of its integers exceed the range of 64 bit integers, so that they are plotted with limited precision.");
+        }
+    }
+    for (name, header, normalized) in normalized_headers {
+        warn!("Column {header:?} of dataset {name:?} is referred to as {normalized:?} since surrounding whitespace and line breaks are removed from column names.");
+    }
+    for (name, column, fraction) in non_numeric_stripped_columns {
+        warn!("Column {column:?} of view {name:?} is rendered as text because only {:.0}% of its values are numbers after stripping. Consider lowering min-numeric-fraction for it.", fraction * 100.0);
+    }
+    for (name, column, count, examples) in tolerated_non_numeric_values {
+        let examples = examples
+            .iter()
+            .map(|(row, value)| format!("{value:?} in row {row}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        warn!("Column {column:?} of view {name:?} is treated as numeric although {count} of its values are no numbers, which are plotted as missing: {examples}.");
+    }
+    for (name, column) in empty_columns {
+        warn!("Column {column:?} of view {name:?} has no values, so no plot is shown for it.");
+    }
+    for (name, column, images) in missing_images {
+        let images = images
+            .iter()
+            .map(|image| format!("{:?}", image.display().to_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        warn!("Image column {column:?} of view {name:?} references missing local images, which are shown as placeholders: {images}.");
+    }
 
-    render_index_file(&opt.output, &config)?;
-    render_static_files(&opt.output)?;
+    Ok(())
+}
 
-    let renderer = ItemRenderer::builder().specs(config).build();
-    renderer.render_tables(&opt.output, &opt.webview_url, opt.debug)?;
+/// Removes all contents of the given directory except the directories of the given views.
+fn clear_directory(path: &Path, kept_views: &HashSet<String>) -> Result<()> {
+    for entry in path.read_dir()? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            if entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| kept_views.contains(name))
+            {
+                continue;
+            }
+            fs::remove_dir_all(entry.path())?;
+        } else {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
 
+/// Prints the inferred type and statistics of the values of each column of the given table.
+fn classify(opt: &cli::Datavzrd, path: &Path, separator: Option<char>, format: &str) -> Result<()> {
+    let download_settings = DownloadSettings {
+        cache_dir: opt.cache_dir.clone(),
+        offline: opt.offline,
+    };
+    let dataset = DatasetSpecs::from_path(path, separator, &download_settings)?;
+    let summaries = summarize_columns(&dataset)?;
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&summaries)?),
+        _ => print!("{}", format_column_summaries(&summaries)),
+    }
+    Ok(())
+}
+
+/// Writes the JSON Schema of config files to the given path or to stdout.
+fn dump_schema(path: Option<&Path>) -> Result<()> {
+    let schema = config_schema()?;
+    match path {
+        Some(path) => fs::write(path, schema)?,
+        None => println!("{schema}"),
+    }
     Ok(())
 }
 
@@ -53,4 +252,6 @@ fn main() -> Result<()> {
 pub enum OutputError {
     #[error("Given output directory {output_path:?} was not empty. If you wish to overwrite it please use the --overwrite-output option.")]
     OutputDirectoryNotEmpty { output_path: PathBuf },
+    #[error("Given output file {output_path:?} already exists. If you wish to overwrite it please use the --overwrite-output option.")]
+    OutputFileExists { output_path: PathBuf },
 }
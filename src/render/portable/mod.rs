@@ -1,34 +1,45 @@
 mod plot;
 pub(crate) mod utils;
 use crate::render::portable::plot::get_min_max;
-use crate::render::portable::plot::render_plots;
+use crate::render::portable::plot::{render_plots, MAX_NOMINAL_BINS};
 use crate::render::portable::utils::minify_js;
 use crate::render::Renderer;
-use crate::spec::{AdditionalColumnSpec, LinkToUrlSpecEntry};
+use crate::spec::{cell_color, AdditionalColumnSpec, LinkToUrlSpecEntry};
 use crate::spec::{
-    BarPlot, DatasetSpecs, DisplayMode, HeaderSpecs, Heatmap, ItemSpecs, ItemsSpec, LinkSpec,
-    RenderColumnSpec, TickPlot,
+    BarPlot, Color, DatasetSpecs, DisplayMode, HeaderSpecs, Heatmap, ItemSpecs, ItemsSpec,
+    LinkSpec, MaxCategories, NominalSort, NumberFormat, RenderColumnSpec, RowColors, Strip, Theme,
+    TickPlot,
 };
 use crate::utils::column_index::ColumnIndex;
 use crate::utils::column_position;
+use crate::utils::column_stats::{compute_column_stats, compute_missing_fractions, ColumnStats};
 use crate::utils::column_type::IsNa;
-use crate::utils::column_type::{classify_table, ColumnType};
+use crate::utils::column_type::{classify_table, column_type_report, ColumnType};
 use crate::utils::compress::compress;
+use crate::utils::natural_sort::natural_compare;
+use crate::utils::number_format::format_number;
+use crate::utils::reader::skipped_lines;
 use crate::utils::row_address::RowAddressFactory;
 use anyhow::Result;
 use anyhow::{bail, Context as AnyhowContext};
+use base64::prelude::*;
 use chrono::{DateTime, Local};
+use indexmap::IndexMap;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use itertools::Itertools;
+use log::info;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use minify_html::{minify, Cfg};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::option::Option::Some;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
 use tera::{escape_html, Context, Tera};
 use thiserror::Error;
@@ -37,6 +48,15 @@ use typed_builder::TypedBuilder;
 #[derive(TypedBuilder, Debug)]
 pub(crate) struct ItemRenderer {
     specs: ItemsSpec,
+    /// Whether to report the rendering progress of tables on stderr.
+    #[builder(default)]
+    show_progress: bool,
+    /// Whether to compute summary statistics of the columns of tables.
+    #[builder(default = true)]
+    column_stats: bool,
+    /// Views that are unchanged since the previous render and thus not rendered again.
+    #[builder(default)]
+    skipped_views: HashSet<String>,
 }
 
 type LinkedTable = HashMap<(String, String), ColumnIndex>;
@@ -47,260 +67,416 @@ impl Renderer for ItemRenderer {
     where
         P: AsRef<Path>,
     {
-        let view_sizes: HashMap<_, _> = self
-            .specs
-            .views
-            .iter()
-            .filter(|(_, v)| v.dataset.is_some())
-            .map(|(n, v)| {
-                (
-                    n.to_string(),
-                    self.specs
-                        .datasets
-                        .get(v.dataset.as_ref().unwrap())
-                        .unwrap()
-                        .size()
-                        .unwrap(),
-                )
-            })
-            .filter(|(view_name, size)| {
-                size == &0
-                    || (self
-                        .specs
-                        .views
-                        .get(view_name)
-                        .unwrap()
-                        .render_plot
-                        .is_none()
-                        && self
-                            .specs
-                            .views
-                            .get(view_name)
-                            .unwrap()
-                            .render_html
-                            .is_none())
-            })
-            .map(|(view_name, size)| {
-                if size == 0 {
-                    (view_name, "empty".to_string())
-                } else {
-                    (view_name, format!("{size} rows"))
-                }
-            })
-            .collect();
-        for (name, table) in &self.specs.views {
-            let out_path = Path::new(path.as_ref()).join(name);
-            fs::create_dir(&out_path)?;
-            if table.render_plot.is_some() {
-                if let Some(datasets) = &table.datasets {
-                    // Render plot with multiple datasets
-                    render_plot_page_with_multiple_datasets(
-                        &out_path,
-                        &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
-                        name,
-                        table,
-                        datasets
-                            .iter()
-                            .map(|(n, name)| {
-                                (n.to_string(), self.specs.datasets.get(name).unwrap())
-                            })
-                            .collect(),
-                        &self.specs.views,
-                        &self.specs.default_view,
-                    )?;
-                    continue;
-                }
+        let view_sizes = view_sizes(&self.specs)?;
+        let output_path = path.as_ref();
+        let progress = progress_bars(self.show_progress);
+        let render_view = |(name, table): (&String, &ItemSpecs)| {
+            if self.skipped_views.contains(name) {
+                info!("Skipping unchanged table: {name}");
+                return Ok(());
             }
+            self.render_view(
+                output_path,
+                name,
+                table,
+                &view_sizes,
+                &progress,
+                webview_host,
+                debug,
+            )
+        };
+        // Each view is rendered into its own directory, so views can be rendered concurrently.
+        #[cfg(feature = "parallel")]
+        self.specs.views.par_iter().try_for_each(render_view)?;
+        #[cfg(not(feature = "parallel"))]
+        self.specs.views.iter().try_for_each(render_view)?;
+        if self.specs.needs_excel_sheet() {
+            render_excel_sheet(&self.specs, path)?;
+        }
+        Ok(())
+    }
+}
 
-            let dataset = if let Some(d) = table.dataset.as_ref() {
-                match self.specs.datasets.get(d) {
-                    Some(dataset) => dataset,
-                    None => {
-                        bail!(DatasetError::NotFound {
-                            dataset_name: table.dataset.as_ref().unwrap().clone()
-                        })
-                    }
-                }
+/// Returns the sizes of the views rendering tables, which are shown in the menu of all pages.
+pub(crate) fn view_sizes(specs: &ItemsSpec) -> Result<HashMap<String, String>> {
+    let view_sizes = specs
+        .views
+        .iter()
+        .filter(|(_, v)| v.dataset.is_some())
+        .map(|(n, v)| {
+            (
+                n.to_string(),
+                specs
+                    .datasets
+                    .get(v.dataset.as_ref().unwrap())
+                    .unwrap()
+                    .size()
+                    .unwrap(),
+            )
+        })
+        .filter(|(view_name, size)| {
+            size == &0
+                || (specs.views.get(view_name).unwrap().render_plot.is_none()
+                    && specs.views.get(view_name).unwrap().render_html.is_none())
+        })
+        .map(|(view_name, size)| {
+            if size == 0 {
+                (view_name, "empty".to_string())
             } else {
-                &DatasetSpecs::default()
-            };
+                (view_name, format!("{size} rows"))
+            }
+        })
+        .collect();
+    Ok(view_sizes)
+}
+
+impl ItemRenderer {
+    /// Render a single view of user config into its own directory
+    #[allow(clippy::too_many_arguments)]
+    fn render_view(
+        &self,
+        path: &Path,
+        name: &String,
+        table: &ItemSpecs,
+        view_sizes: &HashMap<String, String>,
+        progress: &MultiProgress,
+        webview_host: &str,
+        debug: bool,
+    ) -> Result<()> {
+        let out_path = path.join(name);
+        fs::create_dir(&out_path)?;
+        let custom_css = self.specs.custom_stylesheets();
+        if table.render_plot.is_some() {
+            if let Some(datasets) = &table.datasets {
+                // Render plot with multiple datasets
+                render_plot_page_with_multiple_datasets(
+                    &out_path,
+                    &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
+                    name,
+                    table,
+                    datasets
+                        .iter()
+                        .map(|(n, name)| (n.to_string(), self.specs.datasets.get(name).unwrap()))
+                        .collect(),
+                    &self.specs.views,
+                    &self.specs.default_view,
+                    self.specs.theme,
+                    &custom_css,
+                )?;
+                return Ok(());
+            }
+        }
 
-            let (records_length, is_empty) = if table.render_img.is_none() {
-                (dataset.size()?, dataset.is_empty()?)
+        let dataset = if let Some(d) = table.dataset.as_ref() {
+            match self.specs.datasets.get(d) {
+                Some(dataset) => dataset,
+                None => {
+                    bail!(DatasetError::NotFound {
+                        dataset_name: table.dataset.as_ref().unwrap().clone()
+                    })
+                }
+            }
+        } else {
+            &DatasetSpecs::default()
+        };
+        // Restrict the dataset to the rows and columns selected by the table, if any.
+        let selected = match &table.render_table {
+            Some(render_table) => render_table.view_dataset(dataset)?,
+            None => None,
+        };
+        let dataset = selected.as_ref().unwrap_or(dataset);
+
+        let (records_length, is_empty) = if table.render_img.is_none() {
+            (dataset.size()?, dataset.is_empty()?)
+        } else {
+            (0, false)
+        };
+        if !is_empty {
+            let linked_tables = if table.render_img.is_none() {
+                get_linked_tables(name, &self.specs)?
             } else {
-                (0, false)
+                HashMap::new()
             };
-            if !is_empty {
-                let linked_tables = if table.render_img.is_none() {
-                    get_linked_tables(name, &self.specs)?
+            // Render plot
+            if table.render_plot.is_some() {
+                render_plot_page(
+                    &out_path,
+                    &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
+                    name,
+                    table,
+                    dataset,
+                    &linked_tables,
+                    dataset.links.as_ref().unwrap(),
+                    &self.specs.views,
+                    &self.specs.default_view,
+                    &self.specs.report_name,
+                    self.specs.needs_excel_sheet(),
+                    view_sizes,
+                    self.specs.theme,
+                    &custom_css,
+                )?;
+            // Render HTML
+            } else if let Some(table_specs) = &table.render_html {
+                render_html_page(
+                    &out_path,
+                    &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
+                    name,
+                    table,
+                    dataset,
+                    &self.specs.views,
+                    &self.specs.default_view,
+                    table_specs.script_path.to_string(),
+                    &self.specs.aux_libraries,
+                    &self.specs.report_name,
+                    self.specs.needs_excel_sheet(),
+                    view_sizes,
+                    self.specs.theme,
+                    &custom_css,
+                )?;
+            } else if let Some(table_specs) = &table.render_img {
+                render_img_page(
+                    &out_path,
+                    &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
+                    name,
+                    table,
+                    &self.specs.views,
+                    &self.specs.default_view,
+                    table_specs.path.to_string(),
+                    &self.specs.report_name,
+                    view_sizes,
+                    self.specs.theme,
+                    &custom_css,
+                )?;
+            }
+            // Render table
+            else if let Some(table_specs) = &table.render_table {
+                // Only the pages are restricted to the sampled rows, plots are based on all rows.
+                let sampled = table_specs.sampled_dataset(dataset, records_length);
+                let sampled = sampled.as_ref().unwrap_or(dataset);
+                // Rows are sorted before they are split into pages, so that the order holds
+                // across all pages.
+                let sorted = table_specs.sorted_dataset(sampled)?;
+                let paged = sorted.as_ref().unwrap_or(sampled);
+                let sample = sampled.sample.map(|sample| SampleInfo {
+                    rows: sample.size,
+                    total: records_length,
+                });
+                let records_length = sample.map_or(records_length, |sample| sample.rows);
+                let data_path = out_path.join("data");
+                fs::create_dir(&data_path)?;
+                render_type_report(&out_path, dataset)?;
+                let row_address_factory = RowAddressFactory::new(table.page_size);
+                let pages = row_address_factory.get(records_length - 1).page + 1;
+
+                let is_single_page = if let Some(max_rows) = table.max_in_memory_rows {
+                    records_length <= max_rows
+                } else {
+                    records_length <= self.specs.max_in_memory_rows
+                };
+
+                let headers = dataset
+                    .reader()?
+                    .headers()?
+                    .iter()
+                    .map(|s| s.to_owned())
+                    .collect_vec();
+
+                let row_colors = RowColors::new(&table_specs.row_color_rules, &headers)?;
+                let full_csv = if table_specs.embed_full_csv && !is_single_page {
+                    let records = paged
+                        .reader()?
+                        .records()?
+                        .skip(dataset.header_rows - 1)
+                        .collect::<Result<Vec<_>>>()?;
+                    Some(csv_data_uri(&headers, &records.iter().collect_vec())?)
+                } else {
+                    None
+                };
+
+                // Filter out optional columns that are not in the headers and excluded columns
+                let table_specs: &HashMap<String, RenderColumnSpec> = &table_specs
+                    .columns
+                    .clone()
+                    .into_iter()
+                    .filter(|(k, s)| !s.optional.unwrap() || headers.contains(k))
+                    .filter(|(k, _)| dataset.is_selected(k))
+                    .collect();
+                // Assert that remaining columns are present in dataset.
+                // This should be guaranteed by the validation that happens before.
+                for column in table_specs.keys() {
+                    assert!(headers.contains(column));
+                }
+
+                let additional_headers = if dataset.header_rows > 1 {
+                    Some(
+                        dataset
+                            .reader()?
+                            .records()?
+                            .take(dataset.header_rows - 1)
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                } else {
+                    None
+                };
+
+                // Statistics are shown in the column headers and bound the range filters.
+                let column_stats = if self.column_stats || is_single_page {
+                    compute_column_stats(dataset, &classify_table(dataset)?)?
                 } else {
                     HashMap::new()
                 };
-                // Render plot
-                if table.render_plot.is_some() {
-                    render_plot_page(
+                // Filters only exist in single page tables, whose rows are all known to the page.
+                let column_filters = if is_single_page {
+                    column_filters(
+                        dataset,
+                        table_specs,
+                        &headers,
+                        &table.render_table.as_ref().unwrap().additional_columns,
+                        &column_stats,
+                        table.render_table.as_ref().unwrap().max_categories,
+                    )?
+                } else {
+                    Vec::new()
+                };
+
+                let progress_bar = progress.add(ProgressBar::new(records_length as u64));
+                progress_bar.set_style(progress_style());
+                progress_bar.set_prefix(name.to_owned());
+                for (page, grouped_records) in &paged
+                    .reader()?
+                    .records()?
+                    .skip(dataset.header_rows - 1)
+                    .enumerate()
+                    .chunk_by(|(i, _)| row_address_factory.get(*i).page)
+                {
+                    let records = grouped_records
+                        .map(|(_, record)| record)
+                        .collect::<Result<Vec<_>>>()?;
+                    render_page(
                         &out_path,
+                        page + 1,
+                        records.iter().collect_vec(),
+                        &headers,
+                        table_specs,
                         &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
                         name,
-                        table,
-                        dataset,
                         &linked_tables,
                         dataset.links.as_ref().unwrap(),
-                        &self.specs.views,
-                        &self.specs.default_view,
                         &self.specs.report_name,
-                        self.specs.needs_excel_sheet(),
-                        &view_sizes,
-                    )?;
-                // Render HTML
-                } else if let Some(table_specs) = &table.render_html {
-                    render_html_page(
-                        &out_path,
-                        &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
-                        name,
-                        table,
-                        dataset,
                         &self.specs.views,
                         &self.specs.default_view,
-                        table_specs.script_path.to_string(),
-                        &self.specs.aux_libraries,
-                        &self.specs.report_name,
-                        self.specs.needs_excel_sheet(),
-                        &view_sizes,
-                    )?;
-                } else if let Some(table_specs) = &table.render_img {
-                    render_img_page(
-                        &out_path,
-                        &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
-                        name,
-                        table,
-                        &self.specs.views,
-                        &self.specs.default_view,
-                        table_specs.path.to_string(),
-                        &self.specs.report_name,
-                        &view_sizes,
-                    )?;
-                }
-                // Render table
-                else if let Some(table_specs) = &table.render_table {
-                    let data_path = out_path.join("data");
-                    fs::create_dir(&data_path)?;
-                    let row_address_factory = RowAddressFactory::new(table.page_size);
-                    let pages = row_address_factory.get(records_length - 1).page + 1;
-
-                    let is_single_page = if let Some(max_rows) = table.max_in_memory_rows {
-                        records_length <= max_rows
-                    } else {
-                        records_length <= self.specs.max_in_memory_rows
-                    };
-
-                    let headers = dataset
-                        .reader()?
-                        .headers()?
-                        .iter()
-                        .map(|s| s.to_owned())
-                        .collect_vec();
-
-                    // Filter out optional columns that are not in the headers
-                    let table_specs: &HashMap<String, RenderColumnSpec> = &table_specs
-                        .columns
-                        .clone()
-                        .into_iter()
-                        .filter(|(k, s)| !s.optional.unwrap() || headers.contains(k))
-                        .collect();
-                    // Assert that remaining columns are present in dataset.
-                    // This should be guaranteed by the validation that happens before.
-                    for column in table_specs.keys() {
-                        assert!(headers.contains(column));
-                    }
-
-                    let additional_headers = if dataset.header_rows > 1 {
-                        Some(
-                            dataset
-                                .reader()?
-                                .records()?
-                                .take(dataset.header_rows - 1)
-                                .collect_vec(),
-                        )
-                    } else {
-                        None
-                    };
-
-                    for (page, grouped_records) in &dataset
-                        .reader()?
-                        .records()?
-                        .skip(dataset.header_rows - 1)
-                        .enumerate()
-                        .chunk_by(|(i, _)| row_address_factory.get(*i).page)
-                    {
-                        let records = grouped_records.collect_vec();
-                        render_page(
-                            &out_path,
-                            page + 1,
-                            records.iter().map(|(_, records)| records).collect_vec(),
-                            &headers,
-                            &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
-                            name,
-                            &linked_tables,
-                            dataset.links.as_ref().unwrap(),
-                            &self.specs.report_name,
-                            &self.specs.views,
-                            &self.specs.default_view,
-                            is_single_page,
-                            debug,
-                        )?;
-                    }
-                    if !is_single_page {
-                        render_search_dialogs(&out_path, &headers, dataset, table.page_size)?;
-                    }
-                    render_table_javascript(
-                        &out_path,
-                        &headers,
-                        table_specs,
-                        &table.render_table.as_ref().unwrap().additional_columns,
-                        additional_headers,
-                        &table.render_table.as_ref().unwrap().headers,
+                        &row_colors,
+                        full_csv.as_deref(),
                         is_single_page,
-                        table.single_page_page_size,
-                        pages,
-                        webview_host,
-                        self.specs.webview_controls,
                         debug,
-                        name,
-                        dataset,
-                        &view_sizes,
-                        &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
-                        &self.specs.default_view,
-                        self.specs.needs_excel_sheet(),
-                        table.description.as_deref(),
-                        &self.specs.report_name,
-                        name,
+                        self.specs.theme,
+                        &custom_css,
+                        &column_filters,
                     )?;
-                    render_custom_javascript_functions(
+                    progress_bar.inc(records.len() as u64);
+                    progress_bar.set_message(format!("{}/{pages} pages", page + 1));
+                }
+                progress_bar.finish();
+                if !is_single_page {
+                    render_search_dialogs(
                         &out_path,
+                        &headers,
                         table_specs,
-                        &table.render_table.as_ref().unwrap().additional_columns,
+                        paged,
+                        table.page_size,
+                        self.specs.theme,
+                        &custom_css,
                     )?;
-                    render_plots(&out_path, dataset, debug)?;
                 }
-            } else {
-                render_empty_dataset(
+                let column_stats = if self.column_stats {
+                    column_stats
+                } else {
+                    HashMap::new()
+                };
+                let missing_values = missing_value_labels(
+                    dataset,
+                    table.render_table.as_ref().unwrap().missing_threshold,
+                )?;
+                render_table_javascript(
                     &out_path,
+                    &headers,
+                    table_specs,
+                    &table.render_table.as_ref().unwrap().additional_columns,
+                    additional_headers,
+                    &table.render_table.as_ref().unwrap().headers,
+                    is_single_page,
+                    table.single_page_page_size,
+                    pages,
+                    webview_host,
+                    self.specs.webview_controls,
+                    debug,
                     name,
-                    &self.specs.report_name,
+                    dataset,
+                    view_sizes,
                     &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
+                    &self.specs.default_view,
                     self.specs.needs_excel_sheet(),
-                    &view_sizes,
+                    table.description.as_deref(),
+                    &self.specs.report_name,
+                    name,
+                    &column_stats,
+                    &missing_values,
+                    sample,
+                )?;
+                render_custom_javascript_functions(
+                    &out_path,
+                    table_specs,
+                    &table.render_table.as_ref().unwrap().additional_columns,
+                )?;
+                render_plots(
+                    &out_path,
+                    dataset,
+                    table_specs,
+                    self.specs.default_bins,
+                    table.render_table.as_ref().unwrap().max_categories,
+                    table.render_table.as_ref().unwrap().correlation_matrix,
+                    debug,
+                )?;
+                // Images are copied last since their paths may lie within the directories
+                // created above.
+                copy_local_images(
+                    &out_path,
+                    &local_images(dataset, &image_columns(dataset, table_specs)?)?,
                 )?;
             }
-        }
-        if self.specs.needs_excel_sheet() {
-            render_excel_sheet(&self.specs, path)?;
+        } else {
+            render_empty_dataset(
+                &out_path,
+                name,
+                &self.specs.report_name,
+                &self.specs.views.keys().map(|s| s.to_owned()).collect_vec(),
+                self.specs.needs_excel_sheet(),
+                view_sizes,
+                self.specs.theme,
+                &custom_css,
+            )?;
         }
         Ok(())
     }
 }
 
+/// Returns the progress bars of the rendered tables, drawn to stderr unless hidden.
+fn progress_bars(show_progress: bool) -> MultiProgress {
+    if show_progress {
+        MultiProgress::with_draw_target(ProgressDrawTarget::stderr())
+    } else {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    }
+}
+
+/// Style of the progress bar of a single table, estimating the remaining time from the
+/// throughput of rendered rows.
+fn progress_style() -> ProgressStyle {
+    ProgressStyle::with_template("{prefix:.bold} [{bar:40}] {pos}/{len} rows, {msg} (ETA {eta})")
+        .unwrap()
+        .progress_chars("=> ")
+}
+
 #[allow(clippy::too_many_arguments)]
 /// Render single page of a table
 fn render_page<P: AsRef<Path>>(
@@ -308,6 +484,7 @@ fn render_page<P: AsRef<Path>>(
     page_index: usize,
     data: Vec<&Vec<String>>,
     titles: &[String],
+    render_columns: &HashMap<String, RenderColumnSpec>,
     tables: &[String],
     name: &str,
     linked_tables: &LinkedTable,
@@ -315,8 +492,13 @@ fn render_page<P: AsRef<Path>>(
     report_name: &str,
     views: &HashMap<String, ItemSpecs>,
     default_view: &Option<String>,
+    row_colors: &RowColors,
+    full_csv: Option<&str>,
     is_single_page: bool,
     debug: bool,
+    theme: Theme,
+    custom_css: &[String],
+    column_filters: &[ColumnFilter],
 ) -> Result<()> {
     let mut templates = Tera::default();
     templates.add_raw_template(
@@ -328,6 +510,9 @@ fn render_page<P: AsRef<Path>>(
         include_str!("../../../templates/data.js.tera"),
     )?;
     let mut context = Context::new();
+    context.insert("theme", &theme);
+    context.insert("custom_css", custom_css);
+    context.insert("column_filters", column_filters);
 
     let data = data
         .iter()
@@ -353,10 +538,71 @@ fn render_page<P: AsRef<Path>>(
         None
     };
 
+    let compressed_cell_colors = if render_columns
+        .values()
+        .any(|spec| !spec.color_rules.is_empty())
+    {
+        let cell_colors = data
+            .iter()
+            .map(|r| render_cell_colors(r, titles, render_columns))
+            .collect_vec();
+        Some(compress(json!(cell_colors))?)
+    } else {
+        None
+    };
+
+    let compressed_cell_links = if render_columns
+        .values()
+        .any(|spec| spec.link_to_table.is_some())
+    {
+        let cell_links = data
+            .iter()
+            .map(|r| render_cell_links(r, titles, render_columns, linked_tables))
+            .collect_vec();
+        Some(compress(json!(cell_links))?)
+    } else {
+        None
+    };
+
+    let compressed_formatted_numbers = if render_columns.values().any(|spec| spec.format.is_some())
+    {
+        let formatted_numbers = data
+            .iter()
+            .map(|r| render_formatted_numbers(r, titles, render_columns))
+            .collect_vec();
+        Some(compress(json!(formatted_numbers))?)
+    } else {
+        None
+    };
+
+    let compressed_row_colors = if !row_colors.is_empty() {
+        let colors = data.iter().map(|r| row_colors.color(r)).collect_vec();
+        Some(compress(json!(colors))?)
+    } else {
+        None
+    };
+
+    // Single page tables offer a download of the filtered rows on the client side instead.
+    let csv_download = if is_single_page {
+        None
+    } else if let Some(full_csv) = full_csv {
+        Some(full_csv.to_owned())
+    } else {
+        Some(csv_data_uri(titles, &data.iter().collect_vec())?)
+    };
+
     let compressed_data = compress(json!(data))?;
 
     context.insert("data", &json!(compressed_data).to_string());
     context.insert("linkouts", &json!(compressed_linkouts).to_string());
+    context.insert("cell_colors", &json!(compressed_cell_colors).to_string());
+    context.insert("cell_links", &json!(compressed_cell_links).to_string());
+    context.insert(
+        "formatted_numbers",
+        &json!(compressed_formatted_numbers).to_string(),
+    );
+    context.insert("row_colors", &json!(compressed_row_colors).to_string());
+    context.insert("csv_download", &json!(csv_download).to_string());
     context.insert("current_page", &page_index);
     context.insert("is_single_page", &is_single_page);
     context.insert(
@@ -424,6 +670,9 @@ fn render_table_javascript<P: AsRef<Path>>(
     description: Option<&str>,
     report_name: &String,
     title: &String,
+    column_stats: &HashMap<String, ColumnStats>,
+    missing_values: &HashMap<String, String>,
+    sample: Option<SampleInfo>,
 ) -> Result<()> {
     let mut templates = Tera::default();
     templates.add_raw_template(
@@ -450,6 +699,9 @@ fn render_table_javascript<P: AsRef<Path>>(
         description,
         report_name,
         title,
+        column_stats,
+        missing_values,
+        sample,
     );
 
     let custom_plot_config =
@@ -628,10 +880,19 @@ struct JavascriptConfig {
     ticks: Vec<JavascriptTickAndBarConfig>,
     bars: Vec<JavascriptTickAndBarConfig>,
     heatmaps: Vec<JavascriptHeatmapConfig>,
-    brush_domains: HashMap<String, Vec<f32>>,
-    aux_domains: HashMap<String, Vec<String>>,
     link_urls: Vec<JavascriptLinkConfig>,
     ellipsis: Vec<JavascriptEllipsisConfig>,
+    url_columns: Vec<JavascriptUrlConfig>,
+    image_columns: Vec<JavascriptImageConfig>,
+    boolean_columns: Vec<JavascriptBooleanConfig>,
+    value_colors: HashMap<String, IndexMap<String, Color>>,
+    percentage_columns: Vec<String>,
+    duration_columns: Vec<String>,
+    sort_orders: HashMap<String, Vec<String>>,
+    natural_sort_columns: Vec<String>,
+    unfilterable_columns: Vec<String>,
+    number_formats: HashMap<String, JavascriptNumberConfig>,
+    na_values: HashMap<String, Vec<String>>,
     format: HashMap<String, String>,
     additional_colums: HashMap<String, String>,
     unique_column_values: HashMap<String, usize>,
@@ -641,12 +902,198 @@ struct JavascriptConfig {
     default_view: Option<String>,
     has_excel_sheet: bool,
     description: Option<String>,
+    skipped_lines: Option<String>,
+    column_stats: HashMap<String, ColumnStats>,
+    missing_values: HashMap<String, String>,
+    sample: Option<SampleInfo>,
+    classified_rows: Option<usize>,
     report_name: String,
     time: String,
     version: String,
     title: String,
 }
 
+/// Returns the sorted distinct values of the displayed columns that are not numeric and have at
+/// most as many distinct values as their maximal number of categories. These columns are
+/// filtered by selecting values instead of typing a text.
+fn category_filters(
+    dataset: &DatasetSpecs,
+    config: &HashMap<String, RenderColumnSpec>,
+    columns: &[String],
+    column_classification: &HashMap<String, ColumnType>,
+    max_categories: Option<MaxCategories>,
+) -> Result<HashMap<String, Vec<String>>> {
+    let max_values = columns
+        .iter()
+        .filter(|column| {
+            column_classification
+                .get(*column)
+                .is_some_and(|column_type| !column_type.is_numeric())
+        })
+        .filter_map(|column| {
+            let spec = config.get(column);
+            if spec.is_some_and(|spec| !spec.filterable) {
+                return None;
+            }
+            let max_values = match spec.and_then(|spec| spec.max_categories).or(max_categories) {
+                Some(MaxCategories::Count(count)) => count,
+                Some(MaxCategories::All) => usize::MAX,
+                None => MAX_NOMINAL_BINS,
+            };
+            Some((column.to_owned(), max_values))
+        })
+        .collect();
+    let mut values = dataset.distinct_values(&max_values)?;
+    for (column, values) in values.iter_mut() {
+        if config.get(column).is_some_and(|spec| spec.natural_sort) {
+            values.sort_by(|a, b| natural_compare(a, b));
+        } else {
+            values.sort();
+        }
+    }
+    Ok(values)
+}
+
+/// Filter offered in the header of a column of a single page table.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct ColumnFilter {
+    column: String,
+    #[serde(flatten)]
+    control: FilterControl,
+}
+
+/// Control filtering the values of a column, which is rendered into the page.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "control", rename_all = "kebab-case")]
+enum FilterControl {
+    /// Text input keeping the rows whose value contains the typed text.
+    Input,
+    /// Slider selecting the range of numbers between the minimum and maximum of the column.
+    Range { min: f64, max: f64 },
+    /// Multi-select of the distinct values of the column, keeping the rows with a selected value.
+    Select { values: Vec<String> },
+}
+
+/// Returns the filters of the displayed columns of a single page table in the order of the
+/// columns. Columns configured with filterable: false get no filter, those with few distinct
+/// values are filtered by selecting them and numeric columns by a range between the minimum and
+/// maximum of their configured domain or statistics unless configured with range-filter: false.
+fn column_filters(
+    dataset: &DatasetSpecs,
+    config: &HashMap<String, RenderColumnSpec>,
+    columns: &[String],
+    additional_columns: &Option<HashMap<String, AdditionalColumnSpec>>,
+    column_stats: &HashMap<String, ColumnStats>,
+    max_categories: Option<MaxCategories>,
+) -> Result<Vec<ColumnFilter>> {
+    let column_classification = classify_table(dataset)?;
+    let mut categories = category_filters(
+        dataset,
+        config,
+        columns,
+        &column_classification,
+        max_categories,
+    )?;
+    let mut filters = Vec::new();
+    for column in columns {
+        let Some(spec) = config.get(column) else {
+            continue;
+        };
+        if !spec.filterable || spec.display_mode != Some(DisplayMode::Normal) {
+            continue;
+        }
+        if let Some(values) = categories.remove(column) {
+            filters.push(ColumnFilter {
+                column: column.to_owned(),
+                control: FilterControl::Select { values },
+            });
+            continue;
+        }
+        let numeric = column_classification
+            .get(column)
+            .is_some_and(ColumnType::is_numeric);
+        let control = if numeric && spec.range_filter {
+            let stats = column_stats
+                .get(column)
+                .and_then(|stats| stats.min.zip(stats.max));
+            // Columns without values have nothing to filter, those with a single value are
+            // filtered by text.
+            let Some((min, max)) = configured_range(dataset, column, spec)?.or(stats) else {
+                continue;
+            };
+            if min < max {
+                FilterControl::Range { min, max }
+            } else {
+                FilterControl::Input
+            }
+        } else {
+            FilterControl::Input
+        };
+        filters.push(ColumnFilter {
+            column: column.to_owned(),
+            control,
+        });
+    }
+    let additional_filters = additional_columns
+        .iter()
+        .flatten()
+        .filter(|(_, spec)| spec.display_mode == DisplayMode::Normal)
+        .map(|(column, _)| column)
+        .sorted()
+        .map(|column| ColumnFilter {
+            column: column.to_owned(),
+            control: FilterControl::Input,
+        });
+    Ok(filters.into_iter().chain(additional_filters).collect())
+}
+
+/// Returns the range configured for the values of the given column by its tick plot or heatmap,
+/// i.e. their domain or the range of the column together with their auxiliary domain columns,
+/// or `None` if neither is configured.
+fn configured_range(
+    dataset: &DatasetSpecs,
+    column: &str,
+    spec: &RenderColumnSpec,
+) -> Result<Option<(f64, f64)>> {
+    let plot = spec.plot.as_ref();
+    let tick_plot = plot.and_then(|plot| plot.tick_plot.as_ref());
+    let heatmap = plot
+        .and_then(|plot| plot.heatmap.as_ref())
+        .filter(|heatmap| heatmap.custom_content.is_none());
+    if let Some(domain) = tick_plot.and_then(|tick_plot| tick_plot.domain.as_ref()) {
+        return Ok(domain
+            .iter()
+            .map(|value| f64::from(*value))
+            .minmax()
+            .into_option());
+    }
+    if let Some(domain) = heatmap.and_then(|heatmap| heatmap.domain.as_ref()) {
+        let domain = domain
+            .iter()
+            .map(|value| {
+                f64::from_str(value).context(format!(
+                    "Could not parse given domain value {value} for column {column}."
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(domain.into_iter().minmax().into_option());
+    }
+    let aux_domain_columns = tick_plot
+        .and_then(|tick_plot| tick_plot.aux_domain_columns.0.as_ref())
+        .or_else(|| heatmap.and_then(|heatmap| heatmap.aux_domain_columns.0.as_ref()))
+        .filter(|aux_domain_columns| !aux_domain_columns.is_empty());
+    aux_domain_columns
+        .map(|aux_domain_columns| {
+            let columns = aux_domain_columns
+                .iter()
+                .cloned()
+                .chain([column.to_owned()])
+                .collect();
+            get_min_max_multiple_columns(dataset, columns, None)
+        })
+        .transpose()
+}
+
 impl JavascriptConfig {
     #[allow(clippy::too_many_arguments)]
     fn from_column_config(
@@ -667,6 +1114,9 @@ impl JavascriptConfig {
         description: Option<&str>,
         report_name: &String,
         title: &String,
+        column_stats: &HashMap<String, ColumnStats>,
+        missing_values: &HashMap<String, String>,
+        sample: Option<SampleInfo>,
     ) -> Self {
         let column_classification = classify_table(dataset).unwrap();
         let header_label_length = if let Some(headers) = header_specs {
@@ -709,7 +1159,17 @@ impl JavascriptConfig {
             webview_host: webview_host.to_string(),
             is_single_page,
             page_size,
-            columns: columns.iter().map(|c| c.to_string()).chain(additional_columns.as_ref().unwrap_or(&HashMap::new()).keys().map(|c| c.to_string())).collect(),
+            columns: columns
+                .iter()
+                .map(|c| c.to_string())
+                .chain(
+                    additional_columns
+                        .as_ref()
+                        .unwrap_or(&HashMap::new())
+                        .keys()
+                        .map(|c| c.to_string()),
+                )
+                .collect(),
             displayed_columns: column_display_mode_filter(DisplayMode::Normal),
             hidden_columns: column_display_mode_filter(DisplayMode::Hidden),
             displayed_numeric_columns: classify_table(dataset)
@@ -719,11 +1179,27 @@ impl JavascriptConfig {
                 .filter(|(_, v)| *v)
                 .map(|(k, _)| k)
                 .collect(),
-            tick_titles: filter_plot_columns(config, |(_, k)| k.plot.as_ref().unwrap().tick_plot.is_some()),
-            bar_titles: filter_plot_columns(config, |(_, k)| k.plot.as_ref().unwrap().bar_plot.is_some()),
-            heatmap_titles: filter_plot_columns(config, |(_, k)| k.plot.as_ref().unwrap().heatmap.is_some()),
-            custom_plot_titles: filter_columns_for(config, additional_columns, |(_, k)| k.custom_plot.is_some(), |(_, k)| k.custom_plot.is_some()),
-            links: filter_columns_for(config, additional_columns, |(_, k)| k.link_to_url.is_some(), |(_, k)| k.link_to_url.is_some()),
+            tick_titles: filter_plot_columns(config, |(_, k)| {
+                k.plot.as_ref().unwrap().tick_plot.is_some()
+            }),
+            bar_titles: filter_plot_columns(config, |(_, k)| {
+                k.plot.as_ref().unwrap().bar_plot.is_some()
+            }),
+            heatmap_titles: filter_plot_columns(config, |(_, k)| {
+                k.plot.as_ref().unwrap().heatmap.is_some()
+            }),
+            custom_plot_titles: filter_columns_for(
+                config,
+                additional_columns,
+                |(_, k)| k.custom_plot.is_some(),
+                |(_, k)| k.custom_plot.is_some(),
+            ),
+            links: filter_columns_for(
+                config,
+                additional_columns,
+                |(_, k)| k.link_to_url.is_some(),
+                |(_, k)| k.link_to_url.is_some(),
+            ),
             column_config: config
                 .iter()
                 .map(|(k, v)| {
@@ -731,17 +1207,33 @@ impl JavascriptConfig {
                         k.to_string(),
                         JavascriptColumnConfig::from_column_spec(
                             v,
-                            column_classification.get(k).unwrap_or_else(|| panic!("bug: failed to obtain column type for column '{k}'")),
+                            column_classification.get(k).unwrap_or_else(|| {
+                                panic!("bug: failed to obtain column type for column '{k}'")
+                            }),
                         ),
                     )
                 })
                 .chain(
-            additional_columns.as_ref().unwrap_or(&HashMap::new()).iter().map(|(k, _)| (k.to_owned(), JavascriptColumnConfig {
-                label: None,
-                is_float: false,
-                precision: 0
-            }))
-            )
+                    additional_columns
+                        .as_ref()
+                        .unwrap_or(&HashMap::new())
+                        .keys()
+                        .map(|k| {
+                            (
+                                k.to_owned(),
+                                JavascriptColumnConfig {
+                                    label: None,
+                                    is_float: false,
+                                    precision: 0,
+                                    date_format: None,
+                                    input_date_format: None,
+                                    has_scatter_plot: false,
+                                    has_boxplot: false,
+                                    has_violin_plot: false,
+                                },
+                            )
+                        }),
+                )
                 .collect(),
             header_label_length,
             ticks: config
@@ -795,78 +1287,57 @@ impl JavascriptConfig {
                     )
                 })
                 .collect(),
-            brush_domains: config
-                .iter()
-                .filter_map(|(title, k)| k.plot.as_ref().map(|plot| (title, plot)))
-                .filter_map(|(title, k)| k.tick_plot.as_ref().map(|tick_plot| (title, tick_plot)))
-                .filter_map(|(title, k)| k.domain.as_ref().map(|domain| (title, domain)))
-                .map(|(title, k)| (title.to_string(), k.to_vec()))
-                .chain(
-                    config
-                        .iter()
-                        .filter(|(title, _)| column_classification.get(&title.to_string()).unwrap().is_numeric())
-                        .filter_map(|(title, k)| k.plot.as_ref().map(|plot| (title, plot)))
-                        .filter_map(|(title, k)| k.heatmap.as_ref().map(|heatmap| (title, heatmap)))
-                        .filter(|(_, k)| k.custom_content.is_none())
-                        .filter_map(|(title, k)| k.domain.as_ref().map(|domain| (title, domain)))
-                        .map(|(title, k)| {
-                            (
-                                title.to_string(),
-                                k.iter().map(|s| f32::from_str(s).context(format!("Could not parse given domain value {s} for column {title}.")).unwrap()).collect_vec(),
-                            )
-                        }),
-                )
-                .collect(),
-            aux_domains: config
-                .iter()
-                .filter_map(|(title, k)| k.plot.as_ref().map(|plot| (title, plot)))
-                .filter_map(|(title, k)| k.tick_plot.as_ref().map(|tick_plot| (title, tick_plot)))
-                .filter_map(|(title, k)| {
-                    k.aux_domain_columns
-                        .0
-                        .as_ref()
-                        .map(|domains| (title, domains))
-                })
-                .map(|(title, k)| (title.to_string(), k.to_vec()))
-                .chain(
-                    config
-                        .iter()
-                        .filter_map(|(title, k)| k.plot.as_ref().map(|plot| (title, plot)))
-                        .filter_map(|(title, k)| k.heatmap.as_ref().map(|heatmap| (title, heatmap)))
-                        .filter_map(|(title, k)| {
-                            k.aux_domain_columns
-                                .0
-                                .as_ref()
-                                .map(|domains| (title, domains))
-                        })
-                        .map(|(title, k)| (title.to_string(), k.to_vec())),
-                )
-                .collect(),
             link_urls: config
                 .iter()
                 .filter(|(_, v)| v.link_to_url.is_some())
                 .map(|(k, v)| JavascriptLinkConfig {
                     title: k.to_string(),
-                    links: v.link_to_url.as_ref().unwrap().entries.iter().map(|(link_name, link_spec)| JavascriptLink {
-                        name: link_name.to_string(),
-                        link: link_spec.to_owned(),
-                    }).collect(),
-                    custom_content: v.link_to_url.as_ref().unwrap().custom_content.to_owned().map(|c| JavascriptFunction(c).name()),
+                    links: v
+                        .link_to_url
+                        .as_ref()
+                        .unwrap()
+                        .entries
+                        .iter()
+                        .map(|(link_name, link_spec)| JavascriptLink {
+                            name: link_name.to_string(),
+                            link: link_spec.to_owned(),
+                        })
+                        .collect(),
+                    custom_content: v
+                        .link_to_url
+                        .as_ref()
+                        .unwrap()
+                        .custom_content
+                        .to_owned()
+                        .map(|c| JavascriptFunction(c).name()),
                 })
                 .chain(
                     additional_columns
                         .as_ref()
                         .unwrap_or(&HashMap::new())
                         .iter()
-                        .filter(|(_,v)| v.link_to_url.is_some())
+                        .filter(|(_, v)| v.link_to_url.is_some())
                         .map(|(k, v)| JavascriptLinkConfig {
                             title: k.to_string(),
-                            links: v.link_to_url.as_ref().unwrap().entries.iter().map(|(link_name, link_spec)| JavascriptLink {
-                                name: link_name.to_string(),
-                                link: link_spec.to_owned(),
-                            }).collect(),
-                            custom_content: v.link_to_url.as_ref().unwrap().custom_content.to_owned().map(|c| JavascriptFunction(c).name()),
-                        })
+                            links: v
+                                .link_to_url
+                                .as_ref()
+                                .unwrap()
+                                .entries
+                                .iter()
+                                .map(|(link_name, link_spec)| JavascriptLink {
+                                    name: link_name.to_string(),
+                                    link: link_spec.to_owned(),
+                                })
+                                .collect(),
+                            custom_content: v
+                                .link_to_url
+                                .as_ref()
+                                .unwrap()
+                                .custom_content
+                                .to_owned()
+                                .map(|c| JavascriptFunction(c).name()),
+                        }),
                 )
                 .collect(),
             ellipsis: config
@@ -877,12 +1348,103 @@ impl JavascriptConfig {
                     ellipsis: v.ellipsis.as_ref().unwrap().to_owned(),
                 })
                 .collect(),
+            url_columns: config
+                .iter()
+                .filter(|(k, v)| {
+                    v.url_column || column_classification.get(*k) == Some(&ColumnType::Url)
+                })
+                .filter(|(_, v)| !v.image_column && !has_custom_rendering(v))
+                .map(|(k, v)| JavascriptUrlConfig {
+                    title: k.to_string(),
+                    truncate: v.url_truncate_length.unwrap_or(DEFAULT_URL_TRUNCATE_LENGTH),
+                })
+                .collect(),
+            image_columns: config
+                .iter()
+                .filter(|(k, v)| is_image_column(v, column_classification.get(*k)))
+                .map(|(k, v)| JavascriptImageConfig {
+                    title: k.to_string(),
+                    height: v.thumbnail_height.unwrap_or(DEFAULT_THUMBNAIL_HEIGHT),
+                    width: v.thumbnail_width.unwrap_or(DEFAULT_THUMBNAIL_WIDTH),
+                })
+                .collect(),
+            boolean_columns: config
+                .iter()
+                .filter(|(_, v)| !has_custom_rendering(v))
+                .filter_map(|(k, v)| {
+                    let vocabulary = v.boolean_vocabulary(column_classification.get(k))?;
+                    Some(JavascriptBooleanConfig {
+                        title: k.to_string(),
+                        true_values: vocabulary.true_values,
+                        false_values: vocabulary.false_values,
+                    })
+                })
+                .collect(),
+            value_colors: config
+                .iter()
+                .filter_map(|(k, v)| Some((k.to_owned(), v.value_colors.clone()?)))
+                .collect(),
+            percentage_columns: column_classification
+                .iter()
+                .filter(|(_, column_type)| **column_type == ColumnType::Percentage)
+                .map(|(column, _)| column.to_owned())
+                .sorted()
+                .collect(),
+            duration_columns: column_classification
+                .iter()
+                .filter(|(_, column_type)| **column_type == ColumnType::Duration)
+                .map(|(column, _)| column.to_owned())
+                .sorted()
+                .collect(),
+            sort_orders: config
+                .iter()
+                .filter_map(|(k, v)| match &v.nominal_sort {
+                    NominalSort::Custom(order) => Some((k.to_owned(), order.clone())),
+                    _ => None,
+                })
+                .collect(),
+            natural_sort_columns: config
+                .iter()
+                .filter(|(_, v)| v.natural_sort)
+                .map(|(k, _)| k.to_owned())
+                .sorted()
+                .collect(),
+            unfilterable_columns: config
+                .iter()
+                .filter(|(_, v)| !v.filterable)
+                .map(|(k, _)| k.to_owned())
+                .sorted()
+                .collect(),
+            number_formats: column_classification
+                .iter()
+                .filter(|(_, column_type)| column_type.is_numeric())
+                .map(|(column, _)| {
+                    (
+                        column.to_owned(),
+                        JavascriptNumberConfig::new(dataset, column),
+                    )
+                })
+                .collect(),
+            na_values: column_classification
+                .keys()
+                .map(|column| (column.to_owned(), dataset.na_values(column)))
+                .collect(),
             format: config
                 .iter()
                 .filter(|(_, k)| k.custom.is_some())
-                .map(|(k, v)| (k.to_owned(), JavascriptFunction(v.custom.as_ref().unwrap().to_owned()).name()))
+                .map(|(k, v)| {
+                    (
+                        k.to_owned(),
+                        JavascriptFunction(v.custom.as_ref().unwrap().to_owned()).name(),
+                    )
+                })
+                .collect(),
+            additional_colums: additional_columns
+                .as_ref()
+                .unwrap_or(&HashMap::new())
+                .iter()
+                .map(|(k, v)| (k.to_owned(), JavascriptFunction(v.value.to_string()).name()))
                 .collect(),
-            additional_colums: additional_columns.as_ref().unwrap_or(&HashMap::new()).iter().map(|(k, v)| (k.to_owned(), JavascriptFunction(v.value.to_string()).name())).collect(),
             unique_column_values: dataset.unique_column_values().unwrap(),
             pages,
             view_sizes: view_sizes.to_owned(),
@@ -890,6 +1452,14 @@ impl JavascriptConfig {
             default_view: default_view.to_owned(),
             has_excel_sheet,
             description: description.map(escape_html),
+            skipped_lines: dataset
+                .show_skipped_lines
+                .then(|| skipped_lines(dataset).unwrap().join("\n"))
+                .map(|lines| escape_html(&lines)),
+            column_stats: column_stats.to_owned(),
+            missing_values: missing_values.to_owned(),
+            sample,
+            classified_rows: dataset.classified_rows().unwrap(),
             report_name: report_name.to_owned(),
             time: local.format("%a %b %e %T %Y").to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
@@ -938,11 +1508,33 @@ where
         .collect()
 }
 
+/// Returns labels like "5.2% missing" for the columns whose fraction of missing values exceeds
+/// the given threshold.
+fn missing_value_labels(dataset: &DatasetSpecs, threshold: f64) -> Result<HashMap<String, String>> {
+    Ok(compute_missing_fractions(dataset)?
+        .into_iter()
+        .filter(|(_, fraction)| *fraction > 0.0 && *fraction > threshold)
+        .map(|(column, fraction)| (column, format!("{:.1}% missing", fraction * 100.0)))
+        .collect())
+}
+
+/// Number of sampled rows that are rendered out of the total number of rows of a view.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+struct SampleInfo {
+    rows: usize,
+    total: usize,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 struct JavascriptColumnConfig {
     label: Option<String>,
     is_float: bool,
     precision: u32,
+    date_format: Option<String>,
+    input_date_format: Option<String>,
+    has_scatter_plot: bool,
+    has_boxplot: bool,
+    has_violin_plot: bool,
 }
 
 impl JavascriptColumnConfig {
@@ -951,6 +1543,19 @@ impl JavascriptColumnConfig {
             label: spec.label.clone(),
             is_float: column_type == &ColumnType::Float,
             precision: spec.precision.unwrap(),
+            date_format: if column_type == &ColumnType::Date {
+                spec.date_format.clone()
+            } else {
+                None
+            },
+            input_date_format: if column_type == &ColumnType::Date {
+                spec.input_date_format.clone()
+            } else {
+                None
+            },
+            has_scatter_plot: spec.scatter.is_some(),
+            has_boxplot: spec.boxplot,
+            has_violin_plot: spec.violin,
         }
     }
 }
@@ -1025,6 +1630,164 @@ struct JavascriptEllipsisConfig {
     ellipsis: u32,
 }
 
+/// Number of characters after which displayed URLs are truncated by default.
+const DEFAULT_URL_TRUNCATE_LENGTH: usize = 60;
+/// Default maximal height of image thumbnails in pixels.
+const DEFAULT_THUMBNAIL_HEIGHT: u32 = 64;
+/// Default maximal width of image thumbnails in pixels.
+const DEFAULT_THUMBNAIL_WIDTH: u32 = 128;
+
+/// Returns true if the given column is explicitly configured to be rendered in a custom way,
+/// which takes precedence over rendering its values as links or images.
+fn has_custom_rendering(spec: &RenderColumnSpec) -> bool {
+    spec.link_to_url.is_some()
+        || spec.link_to_table.is_some()
+        || spec.custom.is_some()
+        || spec.custom_plot.is_some()
+        || spec.ellipsis.is_some()
+        || spec.plot.is_some()
+}
+
+/// Returns true if the values of a column with the given spec and type are rendered as image
+/// thumbnails.
+pub(crate) fn is_image_column(spec: &RenderColumnSpec, column_type: Option<&ColumnType>) -> bool {
+    (spec.image_column || column_type == Some(&ColumnType::ImageUrl)) && !has_custom_rendering(spec)
+}
+
+/// Returns the image columns among the given columns of the given dataset.
+pub(crate) fn image_columns(
+    dataset: &DatasetSpecs,
+    columns: &HashMap<String, RenderColumnSpec>,
+) -> Result<Vec<String>> {
+    let column_types = classify_table(dataset)?;
+    Ok(dataset
+        .reader()?
+        .headers()?
+        .iter()
+        .filter(|column| match columns.get(*column) {
+            Some(spec) => is_image_column(spec, column_types.get(*column)),
+            None => column_types.get(*column) == Some(&ColumnType::ImageUrl),
+        })
+        .cloned()
+        .collect())
+}
+
+/// Returns the distinct local images referenced by the given columns of the given dataset by
+/// column. Only relative paths within the working directory are considered, since those can
+/// be copied into the report under the same path.
+pub(crate) fn local_images(
+    dataset: &DatasetSpecs,
+    columns: &[String],
+) -> Result<Vec<(String, Vec<PathBuf>)>> {
+    let headers = dataset.reader()?.headers()?;
+    let indices = columns
+        .iter()
+        .filter_map(|column| Some((column, headers.iter().position(|h| h == column)?)))
+        .collect_vec();
+    let mut images = vec![BTreeSet::new(); indices.len()];
+    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        for ((column, index), images) in indices.iter().zip(images.iter_mut()) {
+            let value = record.get(*index).map_or("", |value| value.trim());
+            if !dataset.is_na(column, value) && is_local_path(value) {
+                images.insert(PathBuf::from(value));
+            }
+        }
+    }
+    Ok(indices
+        .into_iter()
+        .zip(images)
+        .map(|((column, _), images)| (column.to_owned(), images.into_iter().collect()))
+        .collect())
+}
+
+/// Returns true if the given value is a relative path within the working directory, i.e. it
+/// has no scheme and does not leave the directory.
+fn is_local_path(value: &str) -> bool {
+    let has_scheme = value.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+    });
+    !value.is_empty()
+        && !has_scheme
+        && Path::new(value)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Writes the type of each column of the given dataset along with the observations it has been
+/// inferred from to `types.json` in the given output directory of a view, so that changes of
+/// inferred types can be detected by diffing it.
+fn render_type_report(out_path: &Path, dataset: &DatasetSpecs) -> Result<()> {
+    let report = column_type_report(dataset)?;
+    fs::write(
+        out_path.join("types.json"),
+        serde_json::to_string_pretty(&report)? + "\n",
+    )?;
+    Ok(())
+}
+
+/// Copies the given local images that exist into the given output directory of a view under
+/// the same relative path, so that the report shows them wherever it is moved to.
+fn copy_local_images(out_path: &Path, images: &[(String, Vec<PathBuf>)]) -> Result<()> {
+    for image in images.iter().flat_map(|(_, images)| images) {
+        if !image.is_file() {
+            continue;
+        }
+        let target = out_path.join(image);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(image, &target)
+            .with_context(|| format!("Failed to copy image {}", image.display()))?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct JavascriptUrlConfig {
+    title: String,
+    truncate: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct JavascriptImageConfig {
+    title: String,
+    height: u32,
+    width: u32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct JavascriptBooleanConfig {
+    title: String,
+    true_values: Vec<String>,
+    false_values: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct JavascriptNumberConfig {
+    format: Option<NumberFormat>,
+    strip_affixes: Vec<String>,
+    strip_regex: Option<String>,
+}
+
+impl JavascriptNumberConfig {
+    fn new(dataset: &DatasetSpecs, column: &str) -> Self {
+        let (strip_affixes, strip_regex) = match dataset.strip(column) {
+            Some(Strip::Affixes(affixes)) => (affixes.clone(), None),
+            Some(Strip::Regex(regex)) => (vec![], Some(regex.as_str().to_owned())),
+            None => (vec![], None),
+        };
+        JavascriptNumberConfig {
+            format: dataset.number_format(column).copied(),
+            strip_affixes,
+            strip_regex,
+        }
+    }
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 struct JavascriptFunction(String);
 
@@ -1161,6 +1924,7 @@ fn render_custom_javascript_functions<P: AsRef<Path>>(
 }
 
 /// Renders an empty page when datasets are empty
+#[allow(clippy::too_many_arguments)]
 fn render_empty_dataset<P: AsRef<Path>>(
     output_path: P,
     name: &str,
@@ -1168,6 +1932,8 @@ fn render_empty_dataset<P: AsRef<Path>>(
     tables: &[String],
     has_excel_sheet: bool,
     view_sizes: &HashMap<String, String>,
+    theme: Theme,
+    custom_css: &[String],
 ) -> Result<()> {
     let mut templates = Tera::default();
     templates.add_raw_template(
@@ -1175,6 +1941,8 @@ fn render_empty_dataset<P: AsRef<Path>>(
         include_str!("../../../templates/empty.html.tera"),
     )?;
     let mut context = Context::new();
+    context.insert("theme", &theme);
+    context.insert("custom_css", custom_css);
     let local: DateTime<Local> = Local::now();
 
     context.insert("view_sizes", &view_sizes);
@@ -1200,8 +1968,11 @@ fn render_empty_dataset<P: AsRef<Path>>(
 fn render_search_dialogs<P: AsRef<Path>>(
     path: P,
     titles: &[String],
+    render_columns: &HashMap<String, RenderColumnSpec>,
     dataset: &DatasetSpecs,
     page_size: usize,
+    theme: Theme,
+    custom_css: &[String],
 ) -> Result<()> {
     let output_path = Path::new(path.as_ref()).join("search");
     fs::create_dir(&output_path)?;
@@ -1215,11 +1986,13 @@ fn render_search_dialogs<P: AsRef<Path>>(
             let records = &reader
                 .records()?
                 .skip(dataset.header_rows - 1)
-                .map(|row| row.get(column).unwrap().to_string())
+                .map_ok(|row| row.get(column).unwrap().to_string())
                 .enumerate()
-                .map(|(i, row)| (row, row_address_factory.get(i)))
-                .map(|(row, address)| (row, address.page + 1, address.row))
-                .collect_vec();
+                .map(|(i, row)| {
+                    let address = row_address_factory.get(i);
+                    Ok((row?, address.page + 1, address.row))
+                })
+                .collect::<Result<Vec<_>>>()?;
 
             let compressed_data = compress(json!(records))?;
 
@@ -1229,9 +2002,16 @@ fn render_search_dialogs<P: AsRef<Path>>(
                 include_str!("../../../templates/search_dialog.html.tera"),
             )?;
             let mut context = Context::new();
+            context.insert("theme", &theme);
+            context.insert("custom_css", custom_css);
             context.insert("data", &json!(compressed_data).to_string());
             context.insert("records", &records);
-            context.insert("title", &title);
+            context.insert(
+                "title",
+                render_columns
+                    .get(title)
+                    .map_or(title.as_str(), |spec| spec.display_name(title)),
+            );
 
             let file_path = Path::new(&output_path)
                 .join(Path::new(&format!("column_{column}")).with_extension("html"));
@@ -1255,9 +2035,21 @@ fn get_linked_tables(table: &str, specs: &ItemsSpec) -> Result<LinkedTable> {
         .links
         .as_ref()
         .unwrap()
-        .iter()
-        .filter_map(|(_, link_spec)| link_spec.table_row.as_ref())
+        .values()
+        .filter_map(|link_spec| link_spec.table_row.as_ref())
         .map(|link| link.split_once('/').unwrap())
+        .chain(
+            table_spec
+                .render_table
+                .iter()
+                .flat_map(|render_table| render_table.columns.iter())
+                .filter_map(|(column, column_spec)| {
+                    Some((
+                        column_spec.link_to_table.as_deref()?,
+                        column_spec.link_column.as_deref().unwrap_or(column),
+                    ))
+                }),
+        )
         .collect_vec();
 
     let mut result = HashMap::new();
@@ -1272,6 +2064,14 @@ fn get_linked_tables(table: &str, specs: &ItemsSpec) -> Result<LinkedTable> {
                 })
             }
         };
+        // Rows are linked on the pages of the sorted rows of the linked view.
+        let sorted = linked_table
+            .render_table
+            .as_ref()
+            .map(|render_table| render_table.sorted_dataset(other_dataset))
+            .transpose()?
+            .flatten();
+        let other_dataset = sorted.as_ref().unwrap_or(other_dataset);
         let page_size = specs.views.get(*table).unwrap().page_size;
 
         let column_index = ColumnIndex::new(other_dataset, column, page_size)?;
@@ -1301,7 +2101,7 @@ fn render_tick_plot(
     })?;
 
     let (min, max) = if let Some(domain) = &tick_plot.domain {
-        (domain[0], domain[1])
+        (f64::from(domain[0]), f64::from(domain[1]))
     } else if let Some(aux_domain_columns) = &tick_plot.aux_domain_columns.0 {
         let columns = aux_domain_columns
             .iter()
@@ -1348,7 +2148,7 @@ fn render_bar_plot(
     })?;
 
     let (min, max) = if let Some(domain) = &bar_plot.domain {
-        (domain[0], domain[1])
+        (f64::from(domain[0]), f64::from(domain[1]))
     } else if let Some(aux_domain_columns) = &bar_plot.aux_domain_columns.0 {
         let columns = aux_domain_columns
             .iter()
@@ -1402,30 +2202,37 @@ pub(crate) fn get_column_domain(
                     .map(|(index, _)| index)
                     .collect()
             })?;
-            Ok(json!(reader
+            let values = reader
                 .records()?
                 .skip(dataset.header_rows - 1)
-                .flat_map(|r| r
-                    .iter()
-                    .enumerate()
-                    .filter(|(index, _)| column_indexes.contains(index))
-                    .filter(|(_, value)| !value.as_str().is_na())
-                    .map(|(_, value)| value.to_string())
-                    .collect_vec())
-                .unique()
-                .sorted()
-                .collect_vec())
-            .to_string())
+                .process_results(|records| {
+                    records
+                        .flat_map(|r| {
+                            r.iter()
+                                .enumerate()
+                                .filter(|(index, _)| column_indexes.contains(index))
+                                .filter(|(_, value)| !value.as_str().is_na())
+                                .map(|(_, value)| value.to_string())
+                                .collect_vec()
+                        })
+                        .unique()
+                        .sorted()
+                        .collect_vec()
+                })?;
+            Ok(json!(values).to_string())
         } else {
-            Ok(json!(reader
+            let values = reader
                 .records()?
                 .skip(dataset.header_rows - 1)
-                .map(|r| r.get(column_index).unwrap().to_owned())
-                .filter(|value| !value.as_str().is_na())
-                .unique()
-                .sorted()
-                .collect_vec())
-            .to_string())
+                .process_results(|records| {
+                    records
+                        .map(|r| r.get(column_index).unwrap().to_owned())
+                        .filter(|value| !dataset.is_na(title, value))
+                        .unique()
+                        .sorted()
+                        .collect_vec()
+                })?;
+            Ok(json!(values).to_string())
         }
     } else if let Some(aux_domain_columns) = &heatmap.aux_domain_columns.0 {
         let columns = aux_domain_columns
@@ -1444,7 +2251,7 @@ fn get_min_max_multiple_columns(
     dataset: &DatasetSpecs,
     columns: Vec<String>,
     precision: Option<u32>,
-) -> Result<(f32, f32)> {
+) -> Result<(f64, f64)> {
     let mut mins = Vec::new();
     let mut maxs = Vec::new();
     for column in columns {
@@ -1454,8 +2261,8 @@ fn get_min_max_multiple_columns(
         maxs.push(max);
     }
     Ok((
-        mins.into_iter().reduce(f32::min).unwrap(),
-        maxs.into_iter().reduce(f32::max).unwrap(),
+        mins.into_iter().reduce(f64::min).unwrap(),
+        maxs.into_iter().reduce(f64::max).unwrap(),
     ))
 }
 
@@ -1474,6 +2281,8 @@ fn render_plot_page<P: AsRef<Path>>(
     report_name: &String,
     has_excel_sheet: bool,
     view_sizes: &HashMap<String, String>,
+    theme: Theme,
+    custom_css: &[String],
 ) -> Result<()> {
     let headers = dataset
         .reader()?
@@ -1485,19 +2294,19 @@ fn render_plot_page<P: AsRef<Path>>(
         .reader()?
         .records()?
         .skip(&dataset.header_rows - 1)
-        .map(|row| {
+        .map_ok(|row| {
             row.iter()
                 .enumerate()
                 .map(|(index, record)| (headers.get(index).unwrap().to_owned(), record.to_owned()))
                 .collect()
         })
-        .collect_vec();
+        .collect::<Result<_>>()?;
     if !links.is_empty() {
         let linkouts = dataset
             .reader()?
             .records()?
             .skip(&dataset.header_rows - 1)
-            .map(|row| {
+            .map_ok(|row| {
                 render_linkouts(
                     &row.iter().map(|s| s.to_owned()).collect_vec(),
                     linked_tables,
@@ -1507,7 +2316,7 @@ fn render_plot_page<P: AsRef<Path>>(
                 )
                 .unwrap()
             })
-            .collect_vec();
+            .collect::<Result<Vec<_>>>()?;
 
         assert_eq!(records.len(), linkouts.len());
 
@@ -1527,6 +2336,8 @@ fn render_plot_page<P: AsRef<Path>>(
         include_str!("../../../templates/plot.html.tera"),
     )?;
     let mut context = Context::new();
+    context.insert("theme", &theme);
+    context.insert("custom_css", custom_css);
 
     let local: DateTime<Local> = Local::now();
 
@@ -1583,6 +2394,8 @@ fn render_html_page<P: AsRef<Path>>(
     report_name: &String,
     has_excel_sheet: bool,
     view_sizes: &HashMap<String, String>,
+    theme: Theme,
+    custom_css: &[String],
 ) -> Result<()> {
     let headers = dataset
         .reader()?
@@ -1594,13 +2407,13 @@ fn render_html_page<P: AsRef<Path>>(
         .reader()?
         .records()?
         .skip(&dataset.header_rows - 1)
-        .map(|row| {
+        .map_ok(|row| {
             row.iter()
                 .enumerate()
                 .map(|(index, record)| (headers.get(index).unwrap().to_owned(), record.to_owned()))
                 .collect()
         })
-        .collect_vec();
+        .collect::<Result<_>>()?;
 
     let script = fs::read_to_string(script_path)?;
 
@@ -1610,6 +2423,8 @@ fn render_html_page<P: AsRef<Path>>(
         include_str!("../../../templates/html.html.tera"),
     )?;
     let mut context = Context::new();
+    context.insert("theme", &theme);
+    context.insert("custom_css", custom_css);
 
     let local: DateTime<Local> = Local::now();
 
@@ -1662,6 +2477,8 @@ fn render_img_page<P: AsRef<Path>>(
     img_path: String,
     report_name: &String,
     view_sizes: &HashMap<String, String>,
+    theme: Theme,
+    custom_css: &[String],
 ) -> Result<()> {
     let img_file = Path::new(&img_path);
     let img_file_name = img_file.file_name().unwrap();
@@ -1674,6 +2491,8 @@ fn render_img_page<P: AsRef<Path>>(
         include_str!("../../../templates/img.html.tera"),
     )?;
     let mut context = Context::new();
+    context.insert("theme", &theme);
+    context.insert("custom_css", custom_css);
 
     let local: DateTime<Local> = Local::now();
 
@@ -1712,6 +2531,7 @@ fn render_img_page<P: AsRef<Path>>(
 }
 
 /// Renders a plot page from given render-plot spec containing multiple datasets
+#[allow(clippy::too_many_arguments)]
 fn render_plot_page_with_multiple_datasets<P: AsRef<Path>>(
     output_path: P,
     tables: &[String],
@@ -1720,6 +2540,8 @@ fn render_plot_page_with_multiple_datasets<P: AsRef<Path>>(
     datasets: HashMap<String, &DatasetSpecs>,
     views: &HashMap<String, ItemSpecs>,
     default_view: &Option<String>,
+    theme: Theme,
+    custom_css: &[String],
 ) -> Result<()> {
     let mut data = HashMap::new();
 
@@ -1730,7 +2552,7 @@ fn render_plot_page_with_multiple_datasets<P: AsRef<Path>>(
         let records: Vec<HashMap<String, String>> = reader
             .records()?
             .skip(&dataset.header_rows - 1)
-            .map(|row| {
+            .map_ok(|row| {
                 row.iter()
                     .enumerate()
                     .map(|(index, record)| {
@@ -1738,7 +2560,7 @@ fn render_plot_page_with_multiple_datasets<P: AsRef<Path>>(
                     })
                     .collect()
             })
-            .collect_vec();
+            .collect::<Result<_>>()?;
 
         data.insert(name.to_string(), records);
     }
@@ -1751,6 +2573,8 @@ fn render_plot_page_with_multiple_datasets<P: AsRef<Path>>(
         include_str!("../../../templates/plot.html.tera"),
     )?;
     let mut context = Context::new();
+    context.insert("theme", &theme);
+    context.insert("custom_css", custom_css);
 
     let local: DateTime<Local> = Local::now();
 
@@ -1795,6 +2619,86 @@ struct Linkout {
     url: String,
 }
 
+/// Returns the background colors of the cells of the given row that match a color rule.
+fn render_cell_colors(
+    row: &[String],
+    titles: &[String],
+    render_columns: &HashMap<String, RenderColumnSpec>,
+) -> HashMap<String, String> {
+    titles
+        .iter()
+        .zip(row)
+        .filter_map(|(title, value)| {
+            let rules = &render_columns.get(title)?.color_rules;
+            cell_color(rules, value).map(|color| (title.to_owned(), color.to_owned()))
+        })
+        .collect()
+}
+
+/// Returns a data URI of the given rows as base64 encoded CSV including the header row.
+fn csv_data_uri(titles: &[String], rows: &[&Vec<String>]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(titles)?;
+    for row in rows {
+        writer.write_record(*row)?;
+    }
+    let content = writer.into_inner().context("Failed to write CSV.")?;
+    Ok(format!(
+        "data:text/csv;charset=utf-8;base64,{}",
+        BASE64_STANDARD.encode(content)
+    ))
+}
+
+/// Returns the numeric cells of the given row formatted as configured for their columns. The
+/// raw values are kept for sorting and plots. Custom formatters take precedence.
+fn render_formatted_numbers(
+    row: &[String],
+    titles: &[String],
+    render_columns: &HashMap<String, RenderColumnSpec>,
+) -> HashMap<String, String> {
+    titles
+        .iter()
+        .zip(row)
+        .filter_map(|(title, value)| {
+            let spec = render_columns.get(title)?;
+            let format = spec.format.as_ref().filter(|_| spec.custom.is_none())?;
+            let number = f64::from_str(value).ok()?;
+            Some((title.to_owned(), format_number(number, format)))
+        })
+        .collect()
+}
+
+/// Returns the URLs of the linked rows of other views for the cells of the given row.
+fn render_cell_links(
+    row: &[String],
+    titles: &[String],
+    render_columns: &HashMap<String, RenderColumnSpec>,
+    linked_tables: &LinkedTable,
+) -> HashMap<String, String> {
+    titles
+        .iter()
+        .zip(row)
+        .filter_map(|(title, value)| {
+            let column_spec = render_columns.get(title)?;
+            let table = column_spec.link_to_table.as_ref()?;
+            let linked_column = column_spec.link_column.as_ref().unwrap_or(title);
+            let address = linked_tables
+                .get(&(table.to_string(), linked_column.to_string()))?
+                .index
+                .get(value)?;
+            Some((
+                title.to_owned(),
+                format!(
+                    "../{}/index_{}.html?highlight={}",
+                    table,
+                    address.page + 1,
+                    address.row
+                ),
+            ))
+        })
+        .collect()
+}
+
 /// Renders the additional column with buttons for tables that contains the linkouts
 fn render_link_column(
     row: &[String],
@@ -1900,6 +2804,7 @@ fn render_excel_sheet<P: AsRef<Path>>(specs: &ItemsSpec, output_path: P) -> Resu
                 }
                 sw.append_row(row)?;
                 for result in rdr.records().unwrap() {
+                    let result = result.map_err(std::io::Error::other)?;
                     let mut row = simple_excel_writer::Row::new();
                     for field in result.iter() {
                         row.add_cell(field.to_string());
@@ -1943,12 +2848,28 @@ pub enum DatasetError {
 pub enum SpecError {
     #[error("Could not parse specs of plot for {column:?} in view {view:?}. Please make sure your specs contain valid JSON.")]
     CouldNotParse { column: String, view: String },
+    #[error("Could not parse custom Vega-Lite spec {path:?}: {reason}")]
+    InvalidVegaSpec { path: PathBuf, reason: String },
+    #[error("Custom Vega-Lite spec {path:?} does not contain a data object. Please add \"data\": {{}}, into which the values of the column are injected.")]
+    MissingVegaSpecData { path: PathBuf },
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::render::portable::{render_tick_plot, JavascriptFunction};
-    use crate::spec::{Color, ColorDefinition, ColorRange, DatasetSpecs, ScaleType, TickPlot};
+    use crate::render::portable::{
+        get_linked_tables, is_local_path, progress_bars, render_cell_links,
+        render_formatted_numbers, render_tick_plot, ItemRenderer, JavascriptFunction,
+    };
+    use crate::render::Renderer;
+    use crate::spec::{
+        Color, ColorDefinition, ColorRange, DatasetSpecs, ItemsSpec, RenderColumnSpec, ScaleType,
+        TickPlot,
+    };
+    use crate::utils::remote::DownloadSettings;
+    use base64::prelude::*;
+    use indicatif::ProgressBar;
+    use std::collections::HashMap;
+    use std::fs;
     use std::path::PathBuf;
 
     #[test]
@@ -1993,10 +2914,10 @@ mod tests {
 
         let dataset = DatasetSpecs {
             path: PathBuf::from("tests/data/uniform_datatypes.csv"),
-            separator: ',',
+            separator: Some(','),
             header_rows: 1,
-            offer_excel: false,
-            links: None,
+            double_quote: true,
+            ..Default::default()
         };
 
         let tick_plot = render_tick_plot("price", &dataset, &tick_plot_spec, 2);
@@ -2025,10 +2946,10 @@ mod tests {
 
         let dataset = DatasetSpecs {
             path: PathBuf::from("tests/data/uniform_datatypes.csv"),
-            separator: ',',
+            separator: Some(','),
             header_rows: 1,
-            offer_excel: false,
-            links: None,
+            double_quote: true,
+            ..Default::default()
         };
 
         let tick_plot = render_tick_plot("price", &dataset, &tick_plot_spec, 2);
@@ -2047,10 +2968,10 @@ mod tests {
 
         let dataset = DatasetSpecs {
             path: PathBuf::from("tests/data/uniform_datatypes.csv"),
-            separator: ',',
+            separator: Some(','),
             header_rows: 1,
-            offer_excel: false,
-            links: None,
+            double_quote: true,
+            ..Default::default()
         };
 
         let bar_plot = render_tick_plot("price", &dataset, &bar_plot_spec, 2);
@@ -2079,14 +3000,346 @@ mod tests {
 
         let dataset = DatasetSpecs {
             path: PathBuf::from("tests/data/uniform_datatypes.csv"),
-            separator: ',',
+            separator: Some(','),
             header_rows: 1,
-            offer_excel: false,
-            links: None,
+            double_quote: true,
+            ..Default::default()
         };
 
         let bar_plot = render_tick_plot("price", &dataset, &bar_plot_spec, 2);
         assert!(bar_plot.is_ok());
         assert!(serde_json::from_str::<serde_json::Value>(&bar_plot.unwrap()).is_ok());
     }
+
+    #[test]
+    fn test_hidden_progress_bars() {
+        let progress = progress_bars(false);
+        assert!(progress.is_hidden());
+        let progress_bar = progress.add(ProgressBar::new(10));
+        progress_bar.inc(10);
+        assert!(progress_bar.is_hidden());
+    }
+
+    #[test]
+    fn test_cross_table_links() {
+        let specs =
+            ItemsSpec::from_file("tests/cross-links.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let linked_tables = get_linked_tables("variants", &specs).unwrap();
+        let render_columns = &specs
+            .views
+            .get("variants")
+            .unwrap()
+            .render_table
+            .as_ref()
+            .unwrap()
+            .columns;
+        let titles = ["variant", "gene", "impact"].map(String::from);
+        let links = render_cell_links(
+            &["rs1", "TP53", "high"].map(String::from),
+            &titles,
+            render_columns,
+            &linked_tables,
+        );
+        assert_eq!(
+            links,
+            HashMap::from([(
+                String::from("gene"),
+                String::from("../genes/index_3.html?highlight=0")
+            )])
+        );
+        let links = render_cell_links(
+            &["rs2", "KRAS", "moderate"].map(String::from),
+            &titles,
+            render_columns,
+            &linked_tables,
+        );
+        assert_eq!(
+            links.get("gene").unwrap(),
+            "../genes/index_2.html?highlight=1"
+        );
+        // Values without a matching row are not linked.
+        let links = render_cell_links(
+            &["rs3", "ABC1", "low"].map(String::from),
+            &titles,
+            render_columns,
+            &linked_tables,
+        );
+        assert!(links.is_empty());
+    }
+
+    #[test]
+    fn test_render_formatted_numbers() {
+        let render_columns: HashMap<String, RenderColumnSpec> = serde_yaml::from_str(
+            r#"
+            count:
+                format: ",d"
+            score:
+                format: ".3e"
+            "#,
+        )
+        .unwrap();
+        let titles = ["count", "score", "name"].map(String::from);
+        let formatted = render_formatted_numbers(
+            &["1234567", "0.000123456", "a"].map(String::from),
+            &titles,
+            &render_columns,
+        );
+        assert_eq!(
+            formatted,
+            HashMap::from([
+                (String::from("count"), String::from("1,234,567")),
+                (String::from("score"), String::from("1.235e-4"))
+            ])
+        );
+        // Missing and non-numeric values are left as they are.
+        let formatted = render_formatted_numbers(
+            &["NA", "n/a", "b"].map(String::from),
+            &titles,
+            &render_columns,
+        );
+        assert!(formatted.is_empty());
+    }
+
+    #[test]
+    fn test_render_dataset_with_bom() {
+        let specs = ItemsSpec::from_file("tests/bom.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-bom");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let view_path = output_path.join("prices");
+        let config = fs::read_to_string(view_path.join("config.js")).unwrap();
+        let data = fs::read_to_string(view_path.join("data").join("data_1.js")).unwrap();
+        let plots = fs::read_to_string(view_path.join("plots").join("plots.js")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        assert!(config.contains(r#""columns":["first","last","ccnumber","price"]"#));
+        for rendered in [config, data, plots] {
+            assert!(!rendered.contains('\u{feff}'));
+        }
+    }
+
+    #[test]
+    fn test_render_missing_value_badges() {
+        let specs =
+            ItemsSpec::from_file("tests/missing.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-missing");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let config = fs::read_to_string(output_path.join("samples").join("config.js")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        // Columns without missing values get no badge.
+        assert!(config.contains(r#""missing_values":{"depth":"30.0% missing"}"#));
+    }
+
+    #[test]
+    fn test_render_csv_download() {
+        let specs =
+            ItemsSpec::from_file("tests/csv_download.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-csv-download");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let embedded_csv = |view: &str| {
+            let data =
+                fs::read_to_string(output_path.join(view).join("data").join("data_2.js")).unwrap();
+            let line = data
+                .lines()
+                .find_map(|line| line.strip_prefix("const csv_download = "))
+                .unwrap();
+            let uri: String = serde_json::from_str(line.trim_end_matches(';')).unwrap();
+            let encoded = uri
+                .strip_prefix("data:text/csv;charset=utf-8;base64,")
+                .unwrap();
+            String::from_utf8(BASE64_STANDARD.decode(encoded).unwrap()).unwrap()
+        };
+        let page = embedded_csv("page");
+        let full = embedded_csv("full");
+        fs::remove_dir_all(&output_path).unwrap();
+        assert_eq!(
+            page,
+            "sample,depth,note\nE,30,ok\nF,N/A,ok\nG,8,ok\nH,9,ok\n"
+        );
+        assert_eq!(full, fs::read_to_string("tests/data/missing.csv").unwrap());
+    }
+
+    #[test]
+    fn test_render_display_name() {
+        let specs =
+            ItemsSpec::from_file("tests/display_name.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-display-name");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let view_path = output_path.join("scores");
+        let config = fs::read_to_string(view_path.join("config.js")).unwrap();
+        let plots = fs::read_to_string(view_path.join("plots").join("plots.js")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        // The table header is rendered from the label of the column config.
+        assert!(config.contains(r#""score":{"label":"Adjusted p-value""#));
+        assert!(config.contains(r#""columns":["name","group","score"]"#));
+        assert!(plots.contains(r#""title": "Adjusted p-value""#));
+        assert!(!plots.contains(r#""title": "score""#));
+    }
+
+    #[test]
+    fn test_render_dark_theme() {
+        let specs = ItemsSpec::from_file("tests/dark.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-dark-theme");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let page = fs::read_to_string(output_path.join("scores").join("index_1.html")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        assert!(page.contains(r#"<html lang="en" data-bs-theme="dark">"#));
+    }
+
+    #[test]
+    fn test_render_url_columns() {
+        let specs = ItemsSpec::from_file("tests/urls.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-urls");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let config = fs::read_to_string(output_path.join("issues").join("config.js")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        for url_column in [
+            r#"{"title":"issue","truncate":30}"#,
+            r#"{"title":"almost","truncate":60}"#,
+            r#"{"title":"mixed","truncate":60}"#,
+        ] {
+            assert!(config.contains(url_column));
+        }
+        // Detection is disabled by configuring another type.
+        assert!(!config.contains(r#"{"title":"mirror""#));
+    }
+
+    #[test]
+    fn test_render_image_columns() {
+        let specs =
+            ItemsSpec::from_file("tests/images.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-images");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let config = fs::read_to_string(output_path.join("images").join("config.js")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        for image_column in [
+            r#"{"title":"thumbnail","height":64,"width":128}"#,
+            r#"{"title":"logo","height":32,"width":48}"#,
+            r#"{"title":"homepage","height":64,"width":128}"#,
+        ] {
+            assert!(config.contains(image_column));
+        }
+        // Image columns are not rendered as plain links.
+        assert!(config.contains(r#""url_columns":[]"#));
+    }
+
+    #[test]
+    fn test_render_local_images() {
+        let specs =
+            ItemsSpec::from_file("tests/local_images.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-local-images");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let view_path = output_path.join("local-images");
+        let copied = fs::read_to_string(view_path.join("tests/images/dot.svg"));
+        let plots = fs::read_to_string(view_path.join("plots").join("plots.js")).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        assert_eq!(
+            copied.unwrap(),
+            fs::read_to_string("tests/images/dot.svg").unwrap()
+        );
+        // No histogram of the image paths is plotted.
+        assert!(plots.contains("let show_plot_1 = false;"));
+        assert!(plots.contains("let plot_0 = {"));
+    }
+
+    #[test]
+    fn test_render_type_report() {
+        let specs =
+            ItemsSpec::from_file("tests/local_images.yaml", &DownloadSettings::default()).unwrap();
+        specs.validate().unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-type-report");
+        fs::create_dir_all(&output_path).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let report = fs::read_to_string(output_path.join("local-images").join("types.json"));
+        fs::remove_dir_all(&output_path).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&report.unwrap()).unwrap();
+        assert_eq!(
+            report,
+            serde_json::json!([
+                {
+                    "column": "sample",
+                    "type": "string",
+                    "observations": {"integer": 0, "float": 0, "string": 5, "empty": 0},
+                    "overridden": false
+                },
+                {
+                    "column": "image",
+                    "type": "image-url",
+                    "observations": {"integer": 0, "float": 0, "string": 5, "empty": 0},
+                    "overridden": false
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_is_local_path() {
+        assert!(is_local_path("tests/images/dot.svg"));
+        assert!(is_local_path("./dot.svg"));
+        assert!(!is_local_path(""));
+        assert!(!is_local_path("https://example.com/dot.svg"));
+        assert!(!is_local_path("data:image/png;base64,AAAA"));
+        assert!(!is_local_path("../dot.svg"));
+        assert!(!is_local_path("/tmp/dot.svg"));
+    }
 }
@@ -1,16 +1,30 @@
 use crate::render::portable::utils::{minify_js, round};
-use crate::spec::DatasetSpecs;
-use crate::utils::column_type::IsNa;
-use crate::utils::column_type::{classify_table, ColumnType};
-use anyhow::Result;
+use crate::render::portable::{is_image_column, SpecError};
+use crate::spec::{
+    BinScale, Color, DatasetSpecs, DateGranularity, MaxCategories, NominalSort, NumberFormat,
+    RenderColumnSpec, ScatterPlotConfig, Strip,
+};
+use crate::utils::column_type::{
+    classify_table, normalize_number, parse_date_with_format, parse_duration, parse_number,
+    BooleanVocabulary, ColumnType,
+};
+use crate::utils::kde::{compute_kde, quantile, silverman_bandwidth};
+use crate::utils::natural_sort::natural_compare;
+use crate::utils::reader::TableReader;
+use anyhow::{bail, Context as _, Result};
+use indexmap::IndexMap;
 use itertools::Itertools;
+use log::warn;
 use ndhistogram::axis::Uniform;
 use ndhistogram::{ndhistogram, Histogram};
 use serde::Serialize;
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Value};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::str::FromStr;
 use tera::{Context, Tera};
@@ -19,6 +33,10 @@ use tera::{Context, Tera};
 pub(crate) fn render_plots<P: AsRef<Path>>(
     output_path: P,
     dataset: &DatasetSpecs,
+    render_columns: &HashMap<String, RenderColumnSpec>,
+    default_bins: Option<usize>,
+    default_max_categories: Option<MaxCategories>,
+    correlation_matrix: bool,
     debug: bool,
 ) -> Result<()> {
     let column_types = classify_table(dataset)?;
@@ -29,32 +47,222 @@ pub(crate) fn render_plots<P: AsRef<Path>>(
     fs::create_dir(&path)?;
     let mut plots = Vec::new();
 
-    for (index, column) in reader.headers()?.iter().enumerate() {
+    let headers = reader.headers()?;
+    let display_name = |column: &'_ String| {
+        render_columns
+            .get(column)
+            .map_or(column.as_str(), |spec| spec.display_name(column))
+            .to_owned()
+    };
+    for (index, column) in headers.iter().enumerate() {
+        // The distribution of image paths is not informative.
+        let is_image = match render_columns.get(column) {
+            Some(spec) => is_image_column(spec, column_types.get(column)),
+            None => column_types.get(column) == Some(&ColumnType::ImageUrl),
+        };
+        if is_image {
+            plots.push(missing_plot(index));
+            continue;
+        }
         let mut templates = Tera::default();
         let mut context = Context::new();
-        context.insert("title", &column);
+        let title = display_name(column);
+        context.insert("title", &title);
         context.insert("index", &index);
-        match column_types.get(column) {
-            None => unreachable!(),
-            Some(ColumnType::String) | Some(ColumnType::None) => {
-                let plot = generate_nominal_plot(dataset, index)?;
+        let spec = render_columns.get(column);
+        let max_categories = spec
+            .and_then(|spec| spec.max_categories)
+            .or(default_max_categories);
+        let categorical = column_types.get(column) == Some(&ColumnType::Integer)
+            && match spec.and_then(|spec| spec.categorical) {
+                Some(categorical) => categorical,
+                None => {
+                    let max_values = match max_categories {
+                        Some(MaxCategories::Count(count)) => count,
+                        _ => MAX_CATEGORICAL_INTEGERS,
+                    };
+                    has_few_values(dataset, index, max_values)?
+                }
+            };
+        // Integer columns with few distinct values, e.g. chromosomes or flags, are plotted like
+        // nominal ones with a bar per value.
+        let plot_type = if categorical {
+            Some(&ColumnType::String)
+        } else {
+            column_types.get(column)
+        };
+        let table = match plot_type {
+            // Columns without any values have no distribution to plot.
+            None | Some(ColumnType::None) => {
+                plots.push(missing_plot(index));
+                continue;
+            }
+            Some(ColumnType::String)
+            | Some(ColumnType::Url)
+            | Some(ColumnType::ImageUrl)
+            | Some(ColumnType::Boolean) => {
+                let max_categories = match max_categories {
+                    Some(MaxCategories::Count(count)) => count,
+                    Some(MaxCategories::All) => usize::MAX,
+                    None if categorical => MAX_CATEGORICAL_INTEGERS,
+                    None => MAX_NOMINAL_BINS,
+                };
+                let column_type = column_types.get(column);
+                let vocabulary = match render_columns.get(column) {
+                    Some(spec) => spec.boolean_vocabulary(column_type),
+                    None => {
+                        (column_type == Some(&ColumnType::Boolean)).then(BooleanVocabulary::default)
+                    }
+                };
+                let sort = match spec {
+                    Some(spec) if !categorical || spec.nominal_sort != NominalSort::default() => {
+                        spec.nominal_sort.clone()
+                    }
+                    _ if categorical => NominalSort::Numeric,
+                    _ => NominalSort::default(),
+                };
+                let (plot, omitted) = generate_nominal_plot(
+                    dataset,
+                    index,
+                    max_categories,
+                    vocabulary.as_ref(),
+                    &sort,
+                    spec.is_some_and(|spec| spec.natural_sort),
+                )?
+                .unzip();
+                context.insert("sort_by_count", &(sort == NominalSort::ByCount));
                 templates.add_raw_template(
                     "plot.js.tera",
                     include_str!("../../../templates/nominal_plot.js.tera"),
                 )?;
-                context.insert("table", &json!(plot).to_string())
+                if let Some(omitted) = omitted.filter(|omitted| *omitted > 0) {
+                    context.insert("other_color", OTHER_COLOR);
+                    context.insert("omitted", &omitted);
+                }
+                if max_categories == usize::MAX
+                    && plot
+                        .as_ref()
+                        .is_some_and(|records| records.len() > MAX_UNTRUNCATED_CATEGORIES)
+                {
+                    warn!("The histogram of column {column:?} shows all of its {} values since max-categories is all, which makes it hard to read and enlarges the report.", plot.as_ref().unwrap().len());
+                }
+                if let Some(value_colors) = render_columns
+                    .get(column)
+                    .and_then(|spec| spec.value_colors.as_ref())
+                {
+                    let (domain, range) =
+                        value_color_scale(value_colors, plot.as_deref().unwrap_or_default());
+                    context.insert("color_domain", &json!(domain).to_string());
+                    context.insert("color_range", &json!(range).to_string());
+                }
+                json!(plot)
+            }
+            Some(ColumnType::Integer) | Some(ColumnType::Float) | Some(ColumnType::Percentage) => {
+                let bins = histogram_bins(column, spec, default_bins);
+                let log_scale = render_columns
+                    .get(column)
+                    .and_then(|spec| spec.log_scale)
+                    .unwrap_or(false);
+                let scale = if render_columns
+                    .get(column)
+                    .is_some_and(|spec| spec.percent_as_fraction)
+                {
+                    0.01
+                } else {
+                    1.0
+                };
+                templates.add_raw_template(
+                    "plot.js.tera",
+                    include_str!("../../../templates/numeric_plot.js.tera"),
+                )?;
+                context.insert("log_scale", &log_scale);
+                let bin_scale = render_columns
+                    .get(column)
+                    .and_then(|spec| spec.scale)
+                    .unwrap_or_default();
+                if bin_scale == BinScale::Log {
+                    let mut plot = generate_log_plot(dataset, index, bins, scale)?;
+                    if log_scale {
+                        drop_empty_bins(&mut plot);
+                    }
+                    context.insert("log_bins", &true);
+                    context.insert("axis_label", "format(datum.value, '.0e')");
+                    context.insert("from_label", "format(datum.bin_start, '.2e')");
+                    context.insert("to_label", "format(datum.bin_end, '.2e')");
+                    json!(plot)
+                } else if column_types.get(column) == Some(&ColumnType::Integer) && scale == 1.0 {
+                    let mut plot = generate_integer_plot(dataset, index, bins)?;
+                    if log_scale {
+                        drop_empty_bins(&mut plot);
+                    }
+                    json!(plot)
+                } else {
+                    let mut plot = generate_numeric_plot(dataset, index, bins, scale)?;
+                    if log_scale {
+                        drop_empty_bins(&mut plot);
+                    }
+                    json!(plot)
+                }
             }
-            Some(ColumnType::Integer) | Some(ColumnType::Float) => {
-                let plot = generate_numeric_plot(dataset, index)?;
+            Some(ColumnType::Duration) => {
+                let bins = histogram_bins(column, spec, default_bins);
+                let plot = generate_duration_plot(dataset, index, bins)?;
                 templates.add_raw_template(
                     "plot.js.tera",
                     include_str!("../../../templates/numeric_plot.js.tera"),
                 )?;
-                context.insert("table", &json!(plot).to_string())
+                context.insert("log_scale", &false);
+                context.insert("axis_label", &duration_label("datum.value"));
+                context.insert("from_label", &duration_label("datum.bin_start"));
+                context.insert("to_label", &duration_label("datum.bin_end"));
+                json!(plot)
+            }
+            Some(ColumnType::Date) => {
+                let spec = render_columns.get(column);
+                let granularity = spec
+                    .and_then(|spec| spec.date_granularity)
+                    .unwrap_or_default();
+                let input_format = spec.and_then(|spec| spec.input_date_format.as_deref());
+                let plot = generate_date_plot(dataset, index, granularity, input_format)?;
+                templates.add_raw_template(
+                    "plot.js.tera",
+                    include_str!("../../../templates/date_plot.js.tera"),
+                )?;
+                json!(plot)
+            }
+        };
+        let js = match render_columns
+            .get(column)
+            .and_then(|spec| spec.custom_vega_spec.as_ref())
+        {
+            Some(spec_path) => render_custom_vega_spec(spec_path, index, table)?,
+            None => {
+                context.insert("table", &table.to_string());
+                templates.render("plot.js.tera", &context)?
             }
         };
-        let js = templates.render("plot.js.tera", &context)?;
         plots.push(js);
+        if let Some(scatter) = render_columns
+            .get(column)
+            .and_then(|spec| spec.scatter.as_ref())
+        {
+            plots.push(render_scatter_plot(
+                dataset,
+                &headers,
+                index,
+                scatter,
+                display_name,
+            )?);
+        }
+        if render_columns.get(column).is_some_and(|spec| spec.boxplot) {
+            plots.push(render_boxplot(dataset, &path, index, &title)?);
+        }
+        if render_columns.get(column).is_some_and(|spec| spec.violin) {
+            plots.push(render_violin_plot(dataset, index, &title)?);
+        }
+    }
+    if correlation_matrix {
+        plots.extend(render_correlation_matrix(dataset, &column_types, &path)?);
     }
     let js_plots = plots.join("\n");
     let file_path = path.join(Path::new(&"plots".to_string()).with_extension("js"));
@@ -64,73 +272,767 @@ pub(crate) fn render_plots<P: AsRef<Path>>(
     Ok(())
 }
 
-/// Generates plot records for columns of types Float and Integer
-fn generate_numeric_plot(
+/// Renders the given custom Vega-Lite spec as the plot of the column with the given index,
+/// injecting the generated plot records as its data values
+fn render_custom_vega_spec(spec_path: &Path, index: usize, table: Value) -> Result<String> {
+    let contents = fs::read_to_string(spec_path).context(format!(
+        "Could not read custom Vega-Lite spec {spec_path:?}."
+    ))?;
+    let mut spec: Value =
+        serde_json::from_str(&contents).map_err(|error| SpecError::InvalidVegaSpec {
+            path: spec_path.to_owned(),
+            reason: error.to_string(),
+        })?;
+    let show_plot = !table.is_null();
+    match spec.get_mut("data").and_then(Value::as_object_mut) {
+        Some(data) => {
+            data.insert(
+                "values".to_string(),
+                if show_plot { table } else { json!([]) },
+            );
+        }
+        None => bail!(SpecError::MissingVegaSpecData {
+            path: spec_path.to_owned()
+        }),
+    }
+    Ok(format!(
+        "let show_plot_{index} = {show_plot};\nlet plot_{index} = {spec};"
+    ))
+}
+
+/// Renders the scatter plot that is configured for the column with the given index
+fn render_scatter_plot(
     dataset: &DatasetSpecs,
-    column_index: usize,
-) -> Result<Option<Vec<BinnedPlotRecord>>> {
+    headers: &[String],
+    index: usize,
+    scatter: &ScatterPlotConfig,
+    display_name: impl Fn(&String) -> String,
+) -> Result<String> {
+    let position = |column: &String| headers.iter().position(|header| header == column).unwrap();
+    let plot = generate_scatter_data(
+        dataset,
+        position(&scatter.x_column),
+        position(&scatter.y_column),
+        scatter.color_column.as_ref().map(position),
+    )?;
+    let mut templates = Tera::default();
+    templates.add_raw_template(
+        "scatter_plot.js.tera",
+        include_str!("../../../templates/scatter_plot.js.tera"),
+    )?;
+    let mut context = Context::new();
+    context.insert("index", &index);
+    context.insert("x_title", &display_name(&scatter.x_column));
+    context.insert("y_title", &display_name(&scatter.y_column));
+    context.insert(
+        "color_title",
+        &scatter.color_column.as_ref().map(display_name),
+    );
+    context.insert("opacity", &scatter.opacity);
+    context.insert("table", &json!(plot).to_string());
+    Ok(templates.render("scatter_plot.js.tera", &context)?)
+}
+
+/// Renders the box plot of the column with the given index and writes its summary statistics
+/// to `boxplot_{index}.json` in the given plot directory
+fn render_boxplot(
+    dataset: &DatasetSpecs,
+    plot_path: &Path,
+    index: usize,
+    title: &str,
+) -> Result<String> {
+    let plot = generate_boxplot(dataset, index)?;
+    fs::write(
+        plot_path.join(format!("boxplot_{index}.json")),
+        serde_json::to_string(&plot)?,
+    )?;
+    let mut templates = Tera::default();
+    templates.add_raw_template(
+        "boxplot.js.tera",
+        include_str!("../../../templates/boxplot.js.tera"),
+    )?;
+    let mut context = Context::new();
+    context.insert("index", &index);
+    context.insert("title", title);
+    context.insert("table", &json!(plot.map(|plot| vec![plot])).to_string());
+    Ok(templates.render("boxplot.js.tera", &context)?)
+}
+
+/// Renders the heatmap of the pairwise correlations of all numeric columns and writes them to
+/// `correlation.json` in the given plot directory. Tables with less than two numeric columns
+/// are skipped.
+fn render_correlation_matrix(
+    dataset: &DatasetSpecs,
+    column_types: &HashMap<String, ColumnType>,
+    plot_path: &Path,
+) -> Result<Option<String>> {
+    let correlations = generate_correlation_matrix(dataset, column_types)?;
+    if correlations.is_empty() {
+        warn!(
+            "Skipping correlation matrix of dataset {:?} as it has less than two numeric columns.",
+            dataset.path
+        );
+        return Ok(None);
+    }
+    fs::write(
+        plot_path.join("correlation.json"),
+        serde_json::to_string(&correlations)?,
+    )?;
+    let columns = correlations
+        .iter()
+        .map(|record| &record.col_a)
+        .unique()
+        .collect_vec();
+    let mut templates = Tera::default();
+    templates.add_raw_template(
+        "correlation_matrix.js.tera",
+        include_str!("../../../templates/correlation_matrix.js.tera"),
+    )?;
+    let mut context = Context::new();
+    context.insert("columns", &json!(columns).to_string());
+    context.insert("table", &json!(correlations).to_string());
+    Ok(Some(
+        templates.render("correlation_matrix.js.tera", &context)?,
+    ))
+}
+
+/// Computes the Pearson correlation coefficients of all pairs of numeric columns in a single
+/// pass, using Welford's online algorithm for the co-moments of each pair. Only records where
+/// both values are numbers are taken into account. Returns no records if there are less than
+/// two numeric columns.
+fn generate_correlation_matrix(
+    dataset: &DatasetSpecs,
+    column_types: &HashMap<String, ColumnType>,
+) -> Result<Vec<CorrelationRecord>> {
     let mut reader = dataset.reader()?;
+    let headers = reader.headers()?;
+    let numeric = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column_types.get(*column).is_some_and(|t| t.is_numeric()))
+        .map(|(index, _)| index)
+        .collect_vec();
+    if numeric.len() < 2 {
+        return Ok(Vec::new());
+    }
+    let pairs = numeric
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &a)| numeric[i..].iter().map(move |&b| (a, b)))
+        .collect_vec();
+    let mut moments = vec![CoMoments::default(); pairs.len()];
+    for record in reader.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let values = numeric
+            .iter()
+            .map(|&index| numeric_value(dataset, &headers[index], record.get(index)?))
+            .collect_vec();
+        let value = |index: usize| values[numeric.iter().position(|&i| i == index).unwrap()];
+        for ((a, b), moments) in pairs.iter().zip(moments.iter_mut()) {
+            if let (Some(a), Some(b)) = (value(*a), value(*b)) {
+                moments.update(a, b);
+            }
+        }
+    }
+    Ok(pairs
+        .iter()
+        .zip(moments)
+        .flat_map(|(&(a, b), moments)| {
+            let r = moments.correlation();
+            let record = |col_a: usize, col_b: usize| CorrelationRecord {
+                col_a: headers[col_a].clone(),
+                col_b: headers[col_b].clone(),
+                r,
+            };
+            // The matrix is symmetric, so each pair is emitted in both orders.
+            if a == b {
+                vec![record(a, b)]
+            } else {
+                vec![record(a, b), record(b, a)]
+            }
+        })
+        .collect())
+}
 
-    let (min, max) = get_min_max(dataset, column_index, None)?;
+/// Co-moments of a pair of columns that are updated one pair of values at a time.
+#[derive(Debug, Clone, Default)]
+struct CoMoments {
+    count: usize,
+    mean_a: f64,
+    mean_b: f64,
+    m2_a: f64,
+    m2_b: f64,
+    c: f64,
+}
+
+impl CoMoments {
+    fn update(&mut self, a: f64, b: f64) {
+        self.count += 1;
+        let delta_a = a - self.mean_a;
+        self.mean_a += delta_a / self.count as f64;
+        let delta_b = b - self.mean_b;
+        self.mean_b += delta_b / self.count as f64;
+        self.m2_a += delta_a * (a - self.mean_a);
+        self.m2_b += delta_b * (b - self.mean_b);
+        self.c += delta_a * (b - self.mean_b);
+    }
 
-    if min == max {
+    /// Returns the Pearson correlation coefficient, which is undefined for less than two
+    /// values or constant columns.
+    fn correlation(&self) -> Option<f64> {
+        let denominator = (self.m2_a * self.m2_b).sqrt();
+        (self.count > 1 && denominator > 0.0).then(|| (self.c / denominator).clamp(-1.0, 1.0))
+    }
+}
+
+/// Computes the quartiles of the given numeric column and the extents of the whiskers, which
+/// reach to the most extreme values within 1.5 times the interquartile range of the box
+fn generate_boxplot(dataset: &DatasetSpecs, column_index: usize) -> Result<Option<BoxPlotRecord>> {
+    let values = sorted_numeric_values(dataset, column_index)?;
+    if values.is_empty() {
         return Ok(None);
     }
+    let fences = outlier_fences(&values);
+    let within_fences = || {
+        values
+            .iter()
+            .copied()
+            .filter(|value| fences.contains(value))
+    };
+    Ok(Some(BoxPlotRecord {
+        min: values[0],
+        q1: quantile(&values, 0.25),
+        median: quantile(&values, 0.5),
+        q3: quantile(&values, 0.75),
+        max: values[values.len() - 1],
+        lower_whisker: within_fences().next().unwrap_or(values[0]),
+        upper_whisker: within_fences()
+            .next_back()
+            .unwrap_or(values[values.len() - 1]),
+        outliers: outliers(&values),
+    }))
+}
 
-    let bin_width = (max - min) / NUMERIC_BINS as f32;
-    let mut hist = ndhistogram!(Uniform::new(NUMERIC_BINS, min, max));
-    let mut nan = 0;
+/// Renders the violin plot of the column with the given index
+fn render_violin_plot(dataset: &DatasetSpecs, index: usize, title: &str) -> Result<String> {
+    let plot = generate_violin_data(dataset, index)?;
+    let mut templates = Tera::default();
+    templates.add_raw_template(
+        "violin_plot.js.tera",
+        include_str!("../../../templates/violin_plot.js.tera"),
+    )?;
+    let mut context = Context::new();
+    context.insert("index", &index);
+    context.insert("title", title);
+    context.insert(
+        "table",
+        &json!(plot.as_ref().map(|plot| &plot.densities)).to_string(),
+    );
+    context.insert(
+        "outliers",
+        &json!(plot.as_ref().map(|plot| plot
+            .outliers
+            .iter()
+            .map(|value| json!({ "x": value }))
+            .collect_vec()))
+        .to_string(),
+    );
+    context.insert(
+        "max_density",
+        &plot.as_ref().map_or(0.0, |plot| plot.max_density()),
+    );
+    Ok(templates.render("violin_plot.js.tera", &context)?)
+}
+
+/// Estimates the density of the given numeric column with a Gaussian kernel. The densities of
+/// the top half of the violin are followed by their mirror image for the bottom half
+fn generate_violin_data(dataset: &DatasetSpecs, column_index: usize) -> Result<Option<ViolinPlot>> {
+    let values = sorted_numeric_values(dataset, column_index)?;
+    let bandwidth = silverman_bandwidth(&values);
+    if bandwidth <= 0.0 {
+        return Ok(None);
+    }
+    let top = compute_kde(&values, bandwidth, VIOLIN_POINTS)
+        .into_iter()
+        .map(|(x, density)| ViolinRecord { x, density })
+        .collect_vec();
+    let bottom = top.iter().map(|record| ViolinRecord {
+        x: record.x,
+        density: -record.density,
+    });
+    Ok(Some(ViolinPlot {
+        densities: top.iter().cloned().chain(bottom).collect(),
+        outliers: outliers(&values),
+    }))
+}
+
+/// Returns the sorted numeric values of the given column, ignoring missing values
+fn sorted_numeric_values(dataset: &DatasetSpecs, column_index: usize) -> Result<Vec<f64>> {
+    let mut reader = dataset.reader()?;
+    let headers = reader.headers()?;
+    let column = headers.get(column_index).map_or("", String::as_str);
+    let mut values = reader
+        .records()?
+        .skip(dataset.header_rows - 1)
+        .process_results(|records| {
+            records
+                .filter_map(|record| numeric_value(dataset, column, record.get(column_index)?))
+                .collect_vec()
+        })?;
+    values.sort_by(|a, b| a.total_cmp(b));
+    Ok(values)
+}
+
+/// Range of the given sorted values outside of which values are considered outliers, i.e.
+/// more than 1.5 times the interquartile range below the first or above the third quartile
+fn outlier_fences(sorted: &[f64]) -> RangeInclusive<f64> {
+    let q1 = quantile(sorted, 0.25);
+    let q3 = quantile(sorted, 0.75);
+    let iqr = q3 - q1;
+    (q1 - 1.5 * iqr)..=(q3 + 1.5 * iqr)
+}
+
+fn outliers(sorted: &[f64]) -> Vec<f64> {
+    let fences = outlier_fences(sorted);
+    sorted
+        .iter()
+        .copied()
+        .filter(|value| !fences.contains(value))
+        .collect()
+}
+
+/// Generates scatter plot records from two numeric columns, skipping rows where either value
+/// is missing
+fn generate_scatter_data(
+    dataset: &DatasetSpecs,
+    x_index: usize,
+    y_index: usize,
+    color_index: Option<usize>,
+) -> Result<Vec<ScatterRecord>> {
+    let mut reader = dataset.reader()?;
+    let headers = reader.headers()?;
+    let column = |index: usize| headers.get(index).map_or("", String::as_str);
+    let mut records = Vec::new();
+    for record in reader.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let x = numeric_value(dataset, column(x_index), record.get(x_index).unwrap());
+        let y = numeric_value(dataset, column(y_index), record.get(y_index).unwrap());
+        if let (Some(x), Some(y)) = (x, y) {
+            let color = color_index
+                .map(|index| record.get(index).unwrap().to_owned())
+                .filter(|value| !dataset.is_na(column(color_index.unwrap()), value));
+            records.push(ScatterRecord { x, y, color });
+        }
+    }
+    Ok(records)
+}
+
+/// Parses the given value of the given column as a finite number, honoring the number format,
+/// stripping and missing values configured for the column.
+fn numeric_value(dataset: &DatasetSpecs, column: &str, value: &str) -> Option<f64> {
+    if dataset.is_na(column, value) {
+        return None;
+    }
+    parse_number(value, dataset.number_format(column), dataset.strip(column))
+        .filter(|number| number.is_finite())
+}
+
+/// Returns the number of bins of the histogram of the given column, warning about numbers too
+/// large to tell the bars apart.
+fn histogram_bins(
+    column: &str,
+    spec: Option<&RenderColumnSpec>,
+    default_bins: Option<usize>,
+) -> usize {
+    let bins = spec
+        .and_then(|spec| spec.bins)
+        .or(default_bins)
+        .unwrap_or(NUMERIC_BINS);
+    if bins > MAX_READABLE_BINS {
+        warn!("The histogram of column {column:?} has {bins} bins, whose bars are hardly distinguishable beyond {MAX_READABLE_BINS} bins.");
+    }
+    bins
+}
+
+/// Returns the start, end and count of the regular bins of a histogram given as start, end and
+/// count of all its bins. Values equal to the upper bound of the histogram range, i.e. the column
+/// maximum, fall into the overflow bin and are counted in the last bin instead, like values in the
+/// underflow bin are counted in the first one.
+fn regular_bins(
+    bins: impl Iterator<Item = (Option<f64>, Option<f64>, u32)>,
+) -> Vec<(f64, f64, u32)> {
+    let mut regular = Vec::new();
+    let (mut underflow, mut overflow) = (0, 0);
+    for bin in bins {
+        match bin {
+            (Some(start), Some(end), count) => regular.push((start, end, count)),
+            (None, _, count) => underflow += count,
+            (_, None, count) => overflow += count,
+        }
+    }
+    if let Some(first) = regular.first_mut() {
+        first.2 += underflow;
+    }
+    if let Some(last) = regular.last_mut() {
+        last.2 += overflow;
+    }
+    regular
+}
 
+/// Generates plot records for columns of types Float and Integer. Columns with at most as many
+/// distinct numbers as bins get a bin centered on each number instead, so that a constant column
+/// gets a single bin of zero width.
+fn generate_numeric_plot(
+    dataset: &DatasetSpecs,
+    column_index: usize,
+    bins: usize,
+    scale: f64,
+) -> Result<Option<Vec<BinnedPlotRecord>>> {
+    // The numbers are collected in a single pass over the table and binned once their range is
+    // known.
+    let mut reader = dataset.reader()?;
+    let (column, number_format, strip) = reader_notation(dataset, &mut reader, column_index)?;
+    let mut values = Vec::new();
+    let mut nan = 0;
+    let mut no_numbers = 0;
     for record in reader.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
         let value = record.get(column_index).unwrap();
-        if let Ok(number) = f32::from_str(value) {
-            hist.fill(&number)
+        if dataset.is_na(&column, value) {
+            nan += 1;
+        } else if let Some(number) = parse_number(value, number_format.as_ref(), strip.as_ref())
+            .filter(|number| !number.is_nan())
+        {
+            values.push(number * scale);
         } else {
             nan += 1;
+            no_numbers += 1;
+        }
+    }
+    // Values beyond the rows the column type was inferred from may contradict it.
+    let expects_numbers = strip.is_none()
+        && !dataset.column_types.contains_key(&column)
+        && dataset.min_numeric_fraction(&column).is_none();
+    if no_numbers > 0 && expects_numbers {
+        if let Some(rows) = dataset.classify_rows.limit() {
+            warn!("Plotted {no_numbers} values of column {column:?} as missing because they are no numbers, although its type was inferred as numeric from the first {rows} rows. Consider setting classify-rows to all.");
         }
     }
 
-    let mut result = hist
-        .iter()
-        .map(|h| BinnedPlotRecord {
-            bin_start: h.bin.start().unwrap_or(min - bin_width),
-            bin_end: h.bin.end().unwrap_or(max + bin_width),
-            value: *h.value as u32,
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    // Constant columns and columns without any numbers, e.g. only `nan` values or values that do
+    // not fit a configured type, have no range to bin. Their numbers are counted in a single bin
+    // of zero width, next to the missing values.
+    let mut hist = (min < max).then(|| ndhistogram!(Uniform::new(bins, min, max)));
+    // Counts of the distinct numbers, which are no longer tracked beyond the number of bins.
+    let mut distinct = Some(HashMap::new());
+    for number in &values {
+        if let Some(hist) = &mut hist {
+            hist.fill(number);
+        }
+        if let Some(counts) = &mut distinct {
+            *counts.entry(number.to_bits()).or_insert(0) += 1;
+            if counts.len() > bins {
+                distinct = None;
+            }
+        }
+    }
+
+    let total = values.len() as u32 + nan;
+    let mut result = match distinct {
+        Some(counts) => {
+            let numbers = counts
+                .into_iter()
+                .map(|(bits, count)| (f64::from_bits(bits), count))
+                .sorted_by(|(a, _), (b, _)| a.total_cmp(b))
+                .collect_vec();
+            // Bins are as wide as the smallest gap between numbers, so that they do not overlap.
+            let width = match numbers.len() {
+                1 => 0.0,
+                _ => numbers
+                    .windows(2)
+                    .map(|pair| pair[1].0 - pair[0].0)
+                    .fold(f64::INFINITY, f64::min),
+            };
+            numbers
+                .into_iter()
+                .map(|(number, count)| BinnedPlotRecord {
+                    bin_start: number - width / 2.0,
+                    bin_end: number + width / 2.0,
+                    value: count,
+                    percentage: percentage(count, total),
+                })
+                .collect_vec()
+        }
+        None => regular_bins(hist.iter().flat_map(|hist| {
+            hist.iter()
+                .map(|h| (h.bin.start(), h.bin.end(), *h.value as u32))
+        }))
+        .into_iter()
+        .map(|(bin_start, bin_end, value)| BinnedPlotRecord {
+            bin_start,
+            bin_end,
+            value,
+            percentage: percentage(value, total),
         })
-        .collect_vec();
+        .collect_vec(),
+    };
 
     if nan > 0 {
         result.push(BinnedPlotRecord {
-            bin_start: f32::NAN,
-            bin_end: f32::NAN,
+            bin_start: f64::NAN,
+            bin_end: f64::NAN,
             value: nan,
+            percentage: percentage(nan, total),
+        })
+    }
+
+    Ok((!result.is_empty()).then_some(result))
+}
+
+/// Generates plot records for numeric columns whose values are binned by their order of
+/// magnitude, e.g. p-values. Values that are not positive have no logarithm and are counted in
+/// a bin ending at zero, missing values and values that are no numbers in a bin without bounds.
+fn generate_log_plot(
+    dataset: &DatasetSpecs,
+    column_index: usize,
+    bins: usize,
+    scale: f64,
+) -> Result<Option<Vec<BinnedPlotRecord>>> {
+    let (number_format, strip) = column_notation(dataset, column_index)?;
+    let column = column_name(dataset, column_index)?;
+    let mut exponents = Vec::new();
+    let mut non_positive = 0;
+    let mut missing = 0;
+    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let value = record.get(column_index).unwrap();
+        let number = (!dataset.is_na(&column, value))
+            .then(|| parse_number(value, number_format.as_ref(), strip.as_ref()))
+            .flatten()
+            .filter(|number| !number.is_nan());
+        match number.map(|number| number * scale) {
+            Some(number) if number > 0.0 => exponents.push(number.log10()),
+            Some(_) => non_positive += 1,
+            None => missing += 1,
+        }
+    }
+    let min = exponents.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = exponents.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if exponents.is_empty() || min == max {
+        return Ok(None);
+    }
+    if non_positive > 0 {
+        warn!("Plotted {non_positive} values of column {column:?} separately because they are not positive and the column is binned on a log scale.");
+    }
+
+    let mut hist = ndhistogram!(Uniform::new(bins, min, max));
+    for exponent in &exponents {
+        hist.fill(exponent);
+    }
+    let total = exponents.len() as u32 + non_positive + missing;
+    let mut result = regular_bins(
+        hist.iter()
+            .map(|h| (h.bin.start(), h.bin.end(), *h.value as u32)),
+    )
+    .into_iter()
+    .map(|(start, end, value)| BinnedPlotRecord {
+        bin_start: 10f64.powf(start),
+        bin_end: 10f64.powf(end),
+        value,
+        percentage: percentage(value, total),
+    })
+    .collect_vec();
+    if non_positive > 0 {
+        result.push(BinnedPlotRecord {
+            bin_start: f64::NEG_INFINITY,
+            bin_end: 0.0,
+            value: non_positive,
+            percentage: percentage(non_positive, total),
         })
     }
+    if missing > 0 {
+        result.push(BinnedPlotRecord {
+            bin_start: f64::NAN,
+            bin_end: f64::NAN,
+            value: missing,
+            percentage: percentage(missing, total),
+        })
+    }
+    Ok(Some(result))
+}
+
+/// Generates plot records for columns of type Integer. Bins are computed on the integers
+/// themselves, so that their bounds are exact even beyond the precision of floats, e.g. for
+/// genomic positions. Missing values and values that are no integers are counted in a bin
+/// without bounds.
+fn generate_integer_plot(
+    dataset: &DatasetSpecs,
+    column_index: usize,
+    bins: usize,
+) -> Result<Option<Vec<BinnedPlotRecord<Option<i128>>>>> {
+    let (number_format, strip) = column_notation(dataset, column_index)?;
+    let column = column_name(dataset, column_index)?;
+    let mut values = Vec::new();
+    let mut missing = 0;
+    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let value = record.get(column_index).unwrap();
+        let integer = (!dataset.is_na(&column, value))
+            .then(|| {
+                let value = strip
+                    .as_ref()
+                    .map_or(Cow::Borrowed(value.as_str()), |strip| strip.apply(value));
+                i64::from_str(&normalize_number(&value, number_format.as_ref())).ok()
+            })
+            .flatten();
+        match integer {
+            Some(integer) => values.push(i128::from(integer)),
+            None => missing += 1,
+        }
+    }
+    let total = values.len() as u32 + missing;
+    // Columns without any integers only get the bin of the missing values.
+    let mut result = match (values.iter().min(), values.iter().max()) {
+        (Some(&min), Some(&max)) => {
+            // Bins cover whole integers, so there are fewer of them for small ranges and a
+            // constant column gets a single bin.
+            let span = max - min + 1;
+            let bin_width = (span + bins as i128 - 1) / bins as i128;
+            let mut counts = vec![0; ((span + bin_width - 1) / bin_width) as usize];
+            for value in &values {
+                counts[((value - min) / bin_width) as usize] += 1;
+            }
+            counts
+                .into_iter()
+                .enumerate()
+                .map(|(bin, value)| BinnedPlotRecord {
+                    bin_start: Some(min + bin as i128 * bin_width),
+                    bin_end: Some(min + (bin as i128 + 1) * bin_width),
+                    value,
+                    percentage: percentage(value, total),
+                })
+                .collect_vec()
+        }
+        _ => Vec::new(),
+    };
+    if missing > 0 {
+        result.push(BinnedPlotRecord {
+            bin_start: None,
+            bin_end: None,
+            value: missing,
+            percentage: percentage(missing, total),
+        })
+    }
+    Ok((!result.is_empty()).then_some(result))
+}
+
+/// Maximal number of values that are not durations listed in the warning about them.
+const MAX_UNPARSEABLE_EXAMPLES: usize = 3;
 
+/// Generates plot records for columns of type Duration, binning the durations by their length
+/// in seconds. Missing values and values that are no durations are counted in a bin without
+/// bounds.
+fn generate_duration_plot(
+    dataset: &DatasetSpecs,
+    column_index: usize,
+    bins: usize,
+) -> Result<Option<Vec<BinnedPlotRecord>>> {
+    let column = column_name(dataset, column_index)?;
+    let mut values = Vec::new();
+    let mut missing = 0;
+    let mut unparseable = Vec::new();
+    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let value = record.get(column_index).unwrap();
+        if dataset.is_na(&column, value) {
+            missing += 1;
+        } else if let Some(seconds) = parse_duration(value) {
+            values.push(seconds);
+        } else {
+            missing += 1;
+            unparseable.push(value.to_owned());
+        }
+    }
+    if !unparseable.is_empty() {
+        warn!(
+            "Plotted {} values of duration column {column:?} as missing because they are no durations, e.g. {}.",
+            unparseable.len(),
+            unparseable
+                .iter()
+                .unique()
+                .take(MAX_UNPARSEABLE_EXAMPLES)
+                .map(|value| format!("{value:?}"))
+                .join(", ")
+        );
+    }
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if values.is_empty() || min == max {
+        return Ok(None);
+    }
+    let mut hist = ndhistogram!(Uniform::new(bins, min, max));
+    for seconds in &values {
+        hist.fill(seconds);
+    }
+    let total = values.len() as u32 + missing;
+    let mut result = regular_bins(
+        hist.iter()
+            .map(|h| (h.bin.start(), h.bin.end(), *h.value as u32)),
+    )
+    .into_iter()
+    .map(|(bin_start, bin_end, value)| BinnedPlotRecord {
+        bin_start,
+        bin_end,
+        value,
+        percentage: percentage(value, total),
+    })
+    .collect_vec();
+    if missing > 0 {
+        result.push(BinnedPlotRecord {
+            bin_start: f64::NAN,
+            bin_end: f64::NAN,
+            value: missing,
+            percentage: percentage(missing, total),
+        })
+    }
     Ok(Some(result))
 }
 
+/// Returns a Vega expression formatting the given expression, which evaluates to a number of
+/// seconds, as a readable duration like `1h 32m`.
+fn duration_label(seconds: &str) -> String {
+    format!(
+        "!isValid({seconds}) ? 'NA' : \
+         {seconds} >= 86400 ? floor({seconds} / 86400) + 'd ' + floor({seconds} % 86400 / 3600) + 'h' : \
+         {seconds} >= 3600 ? floor({seconds} / 3600) + 'h ' + floor({seconds} % 3600 / 60) + 'm' : \
+         {seconds} >= 60 ? floor({seconds} / 60) + 'm ' + floor({seconds} % 60) + 's' : \
+         format({seconds}, '.2~f') + 's'"
+    )
+}
+
 /// Finds the numeric minimum and maximum value of a csv column
 pub(crate) fn get_min_max(
     dataset: &DatasetSpecs,
     column_index: usize,
     precision: Option<u32>,
-) -> Result<(f32, f32)> {
-    let mut min_reader = dataset.reader()?;
-    let mut max_reader = dataset.reader()?;
+) -> Result<(f64, f64)> {
+    let mut reader = dataset.reader()?;
+    let (_, number_format, strip) = reader_notation(dataset, &mut reader, column_index)?;
+    let parse = |value: &str| parse_number(value, number_format.as_ref(), strip.as_ref());
 
-    let min = min_reader
-        .records()?
-        .skip(dataset.header_rows - 1)
-        .map(|r| r.get(column_index).unwrap().to_string())
-        .filter_map(|s| s.parse().ok())
-        .fold(f32::INFINITY, |a, b| a.min(b));
-    let max = max_reader
+    let (min, max) = reader
         .records()?
         .skip(dataset.header_rows - 1)
-        .map(|r| r.get(column_index).unwrap().to_string())
-        .filter_map(|s| s.parse().ok())
-        .fold(f32::NEG_INFINITY, |a, b| a.max(b));
+        .process_results(|records| {
+            records
+                .filter_map(|r| parse(r.get(column_index).unwrap()))
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), number| {
+                    (min.min(number), max.max(number))
+                })
+        })?;
 
     if let Some(p) = precision {
         Ok((round(min, p), round(max, p)))
@@ -139,19 +1041,70 @@ pub(crate) fn get_min_max(
     }
 }
 
-/// Generates plot records for columns of type String
+/// Returns the number format of the column with the given index and what to strip from its
+/// values before parsing them, as configured by the view rendering the dataset.
+fn column_notation(
+    dataset: &DatasetSpecs,
+    column_index: usize,
+) -> Result<(Option<NumberFormat>, Option<Strip>)> {
+    let column = column_name(dataset, column_index)?;
+    Ok((
+        dataset.number_format(&column).copied(),
+        dataset.strip(&column).cloned(),
+    ))
+}
+
+/// Returns the name of the column with the given index, its number format and what to strip
+/// from its values like [`column_notation`], but reads the name from the given reader instead of
+/// opening another one.
+fn reader_notation(
+    dataset: &DatasetSpecs,
+    reader: &mut TableReader,
+    column_index: usize,
+) -> Result<(String, Option<NumberFormat>, Option<Strip>)> {
+    let column = reader
+        .headers()?
+        .get(column_index)
+        .cloned()
+        .unwrap_or_default();
+    let number_format = dataset.number_format(&column).copied();
+    let strip = dataset.strip(&column).cloned();
+    Ok((column, number_format, strip))
+}
+
+/// Returns the name of the column with the given index.
+fn column_name(dataset: &DatasetSpecs, column_index: usize) -> Result<String> {
+    let headers = dataset.reader()?.headers()?;
+    Ok(headers.get(column_index).cloned().unwrap_or_default())
+}
+
+/// Generates plot records for columns of type String in the given order, together with the
+/// number of values beyond the given maximal number of categories that are summarized in the
+/// last record. Values of boolean columns are counted as true or false according to the given
+/// vocabulary. Keys are compared by their numbers if natural is set, e.g. `sample2` before
+/// `sample10`.
 fn generate_nominal_plot(
     dataset: &DatasetSpecs,
     column_index: usize,
-) -> Result<Option<Vec<PlotRecord>>> {
+    max_categories: usize,
+    vocabulary: Option<&BooleanVocabulary>,
+    sort: &NominalSort,
+    natural: bool,
+) -> Result<Option<(Vec<PlotRecord>, usize)>> {
     let mut reader = dataset.reader()?;
+    let column = column_name(dataset, column_index)?;
 
     let mut count_values = HashMap::new();
 
     for result in reader.records()?.skip(dataset.header_rows - 1) {
+        let result = result?;
         let value = result.get(column_index).unwrap();
-        if !value.as_str().is_na() {
-            let entry = count_values.entry(value.to_owned()).or_insert_with(|| 0);
+        if !dataset.is_na(&column, value) {
+            let value = match vocabulary.and_then(|vocabulary| vocabulary.parse(value)) {
+                Some(boolean) => boolean.to_string(),
+                None => value.to_owned(),
+            };
+            let entry = count_values.entry(value).or_insert_with(|| 0);
             *entry += 1;
         } else {
             let entry = count_values.entry("NA".to_owned()).or_insert_with(|| 0);
@@ -159,11 +1112,13 @@ fn generate_nominal_plot(
         }
     }
 
+    let total = count_values.values().sum();
     let mut plot_data = count_values
         .iter()
         .map(|(k, v)| PlotRecord {
             key: k.to_string(),
             value: *v,
+            percentage: percentage(*v, total),
         })
         .collect_vec();
 
@@ -172,36 +1127,304 @@ fn generate_nominal_plot(
         return Ok(None);
     };
 
-    if plot_data.len() > MAX_NOMINAL_BINS {
-        plot_data.sort_by(|a, b| b.value.cmp(&a.value));
-        plot_data = plot_data.into_iter().take(MAX_NOMINAL_BINS).collect();
+    let compare_keys = |a: &PlotRecord, b: &PlotRecord| {
+        if natural {
+            natural_compare(&a.key, &b.key)
+        } else {
+            a.key.cmp(&b.key)
+        }
+    };
+    match sort {
+        // Sort by frequency and key so that the rendered plots are reproducible
+        NominalSort::ByCount => {
+            plot_data.sort_by(|a, b| b.value.cmp(&a.value).then_with(|| compare_keys(a, b)))
+        }
+        NominalSort::Alphabetical => plot_data.sort_by(compare_keys),
+        NominalSort::Numeric => {
+            plot_data.sort_by(
+                |a, b| match (a.key.parse::<f64>().ok(), b.key.parse::<f64>().ok()) {
+                    (Some(number_a), Some(number_b)) => number_a.total_cmp(&number_b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => compare_keys(a, b),
+                },
+            )
+        }
+        NominalSort::Custom(order) => {
+            let position = |record: &PlotRecord| {
+                let position = order.iter().position(|value| value == &record.key);
+                position.unwrap_or(order.len())
+            };
+            plot_data.sort_by(|a, b| {
+                position(a)
+                    .cmp(&position(b))
+                    .then_with(|| compare_keys(a, b))
+            })
+        }
+    }
+
+    let mut omitted = 0;
+    if plot_data.len() > max_categories {
+        omitted = plot_data.len() - max_categories;
+        let other = plot_data
+            .drain(max_categories..)
+            .map(|record| record.value)
+            .sum();
+        // Summarize all remaining values so that the plot still represents all rows
+        plot_data.push(PlotRecord {
+            key: OTHER_KEY.to_owned(),
+            value: other,
+            percentage: percentage(other, total),
+        });
+    }
+
+    Ok(Some((plot_data, omitted)))
+}
+
+/// Returns whether the given column has at most the given number of distinct values besides
+/// missing ones. Counting stops at the first value beyond that number, so that only few values
+/// are kept in memory.
+fn has_few_values(dataset: &DatasetSpecs, column_index: usize, max_values: usize) -> Result<bool> {
+    let column = column_name(dataset, column_index)?;
+    let mut values = HashSet::new();
+    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let value = record.get(column_index).map_or("", String::as_str);
+        if !dataset.is_na(&column, value)
+            && values.insert(value.to_owned())
+            && values.len() > max_values
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Generates plot records for columns of type Date by counting the dates per month or year.
+/// Values that cannot be parsed with the given input format are counted as missing.
+fn generate_date_plot(
+    dataset: &DatasetSpecs,
+    column_index: usize,
+    granularity: DateGranularity,
+    input_format: Option<&str>,
+) -> Result<Option<Vec<PlotRecord>>> {
+    let mut reader = dataset.reader()?;
+    let format = match granularity {
+        DateGranularity::Month => "%Y-%m",
+        DateGranularity::Year => "%Y",
+    };
+
+    let mut count_values = HashMap::new();
+
+    for record in reader.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        let value = record.get(column_index).unwrap();
+        let key = match parse_date_with_format(value, input_format) {
+            Some(date) => date.format(format).to_string(),
+            None => "NA".to_owned(),
+        };
+        *count_values.entry(key).or_insert(0) += 1;
+    }
+
+    if count_values.is_empty() {
+        return Ok(None);
     }
 
-    Ok(Some(plot_data))
+    let total = count_values.values().sum();
+    Ok(Some(
+        count_values
+            .into_iter()
+            .sorted()
+            .map(|(key, value)| PlotRecord {
+                key,
+                value,
+                percentage: percentage(value, total),
+            })
+            .collect(),
+    ))
 }
 
-const MAX_NOMINAL_BINS: usize = 10;
+/// Maximal number of distinct values of nominal columns that are plotted or offered as filter
+/// by default.
+pub(crate) const MAX_NOMINAL_BINS: usize = 10;
+/// Maximal number of distinct values of integer columns that are plotted as categories by
+/// default.
+const MAX_CATEGORICAL_INTEGERS: usize = 12;
+const OTHER_KEY: &str = "other";
+/// Number of values beyond which showing all values of a nominal column emits a warning.
+const MAX_UNTRUNCATED_CATEGORIES: usize = 100;
+const OTHER_COLOR: &str = "gray";
+/// Color of bars of nominal plots, which is the first color of the default Vega-Lite palette.
+const DEFAULT_BAR_COLOR: &str = "#4c78a8";
+
 const NUMERIC_BINS: usize = 20;
+/// Number of bins beyond which a warning is emitted since the bars become too narrow.
+const MAX_READABLE_BINS: usize = 200;
+const VIOLIN_POINTS: usize = 100;
+
+/// Removes the empty bins from the given histogram, which cannot be shown on a log scale.
+fn drop_empty_bins<T>(plot: &mut Option<Vec<BinnedPlotRecord<T>>>) {
+    if let Some(records) = plot.as_mut() {
+        records.retain(|record| record.value > 0);
+    }
+}
+
+/// Returns the percentage of the given count among the given total number of rows.
+fn percentage(count: u32, total: u32) -> f32 {
+    count as f32 / total as f32 * 100.0
+}
 
-#[derive(Serialize, Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+/// Returns the javascript of the column with the given index that has no plot, for which a
+/// placeholder is shown instead.
+fn missing_plot(index: usize) -> String {
+    format!("let show_plot_{index} = false;\nlet plot_{index} = null;")
+}
+
+/// Returns the domain and range of the color scale of a nominal plot with the given records
+/// and configured value colors. The configured values come first in their order, followed by
+/// the other plotted values in the default color.
+fn value_color_scale(
+    value_colors: &IndexMap<String, Color>,
+    records: &[PlotRecord],
+) -> (Vec<String>, Vec<String>) {
+    let others = records
+        .iter()
+        .filter(|record| !value_colors.contains_key(&record.key))
+        .map(|record| {
+            let color = if record.key == OTHER_KEY {
+                OTHER_COLOR
+            } else {
+                DEFAULT_BAR_COLOR
+            };
+            (&record.key, color)
+        });
+    value_colors
+        .iter()
+        .map(|(value, color)| (value, color.0.as_str()))
+        .chain(others)
+        .map(|(value, color)| (value.to_owned(), color.to_owned()))
+        .unzip()
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
 struct PlotRecord {
     key: String,
     value: u32,
+    /// Share of the rows with this key between 0 and 100.
+    percentage: f32,
 }
 
+/// Record of a bin of a histogram, whose bounds are floats unless the binned values are
+/// integers.
 #[derive(Serialize, Debug, Clone, PartialEq)]
-struct BinnedPlotRecord {
-    bin_start: f32,
-    bin_end: f32,
+struct BinnedPlotRecord<T = f64> {
+    bin_start: T,
+    bin_end: T,
     value: u32,
+    /// Share of the rows within this bin between 0 and 100.
+    percentage: f32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct BoxPlotRecord {
+    min: f64,
+    q1: f64,
+    median: f64,
+    q3: f64,
+    max: f64,
+    lower_whisker: f64,
+    upper_whisker: f64,
+    outliers: Vec<f64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct CorrelationRecord {
+    col_a: String,
+    col_b: String,
+    r: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct ViolinRecord {
+    x: f64,
+    density: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ViolinPlot {
+    densities: Vec<ViolinRecord>,
+    outliers: Vec<f64>,
+}
+
+impl ViolinPlot {
+    fn max_density(&self) -> f64 {
+        self.densities
+            .iter()
+            .map(|record| record.density)
+            .fold(0.0, f64::max)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct ScatterRecord {
+    x: f64,
+    y: f64,
+    color: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::render::portable::plot::{generate_nominal_plot, PlotRecord};
-    use crate::spec::DatasetSpecs;
+    use crate::render::portable::plot::{
+        column_name, generate_boxplot, generate_correlation_matrix, generate_date_plot,
+        generate_duration_plot, generate_integer_plot, generate_log_plot, generate_nominal_plot,
+        generate_numeric_plot, generate_scatter_data, generate_violin_data, get_min_max,
+        has_few_values, percentage, regular_bins, render_plots, sorted_numeric_values, PlotRecord,
+        ScatterRecord, MAX_CATEGORICAL_INTEGERS, MAX_NOMINAL_BINS, VIOLIN_POINTS,
+    };
+    use crate::spec::{
+        ClassifyRows, DatasetSpecs, DateGranularity, MaxCategories, NominalSort, NumberFormat,
+        RenderColumnSpec, ScatterPlotConfig, StrippedColumn,
+    };
+    use crate::utils::column_type::{
+        classify_table, diagnose_columns, BooleanVocabulary, ColumnType,
+    };
+    use crate::utils::reader::OPENED_READERS;
+    use itertools::Itertools;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::fs;
     use std::str::FromStr;
 
+    /// Renders the plots of the given dataset into a temporary directory, which is removed when
+    /// the returned handle is dropped.
+    fn render_plots_to_tempdir(
+        dataset: &DatasetSpecs,
+        render_columns: &HashMap<String, RenderColumnSpec>,
+        default_max_categories: Option<MaxCategories>,
+        correlation_matrix: bool,
+    ) -> anyhow::Result<tempfile::TempDir> {
+        let output = tempfile::tempdir()?;
+        render_plots(
+            output.path(),
+            dataset,
+            render_columns,
+            None,
+            default_max_categories,
+            correlation_matrix,
+            true,
+        )?;
+        Ok(output)
+    }
+
+    /// Returns the script defining the plots of the given dataset.
+    fn rendered_plots(
+        dataset: &DatasetSpecs,
+        render_columns: &HashMap<String, RenderColumnSpec>,
+    ) -> String {
+        let output = render_plots_to_tempdir(dataset, render_columns, None, false).unwrap();
+        fs::read_to_string(output.path().join("plots").join("plots.js")).unwrap()
+    }
+
     #[test]
     fn test_nominal_plot_generation() {
         let dataset = DatasetSpecs {
@@ -209,28 +1432,1135 @@ mod tests {
                 .to_string()
                 .parse()
                 .unwrap(),
-            separator: char::from_str(",").unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
             header_rows: 1,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
-        let mut records = generate_nominal_plot(&dataset, 0).unwrap().unwrap();
-        records.sort_unstable();
+        let mut records = generate_nominal_plot(
+            &dataset,
+            0,
+            MAX_NOMINAL_BINS,
+            None,
+            &NominalSort::ByCount,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .0;
+        records.sort_unstable_by(|a, b| a.key.cmp(&b.key));
         let mut expected = vec![
             PlotRecord {
                 key: String::from("George"),
                 value: 2,
+                percentage: percentage(2, 4),
             },
             PlotRecord {
                 key: String::from("Delia"),
                 value: 1,
+                percentage: percentage(1, 4),
             },
             PlotRecord {
                 key: String::from("Winnie"),
                 value: 1,
+                percentage: percentage(1, 4),
             },
         ];
-        expected.sort_unstable();
+        expected.sort_unstable_by(|a, b| a.key.cmp(&b.key));
         assert_eq!(records, expected);
     }
+
+    #[test]
+    fn test_numeric_plot_generation_with_custom_bins() {
+        let dataset = DatasetSpecs {
+            path: "tests/data/uniform_datatypes.csv"
+                .to_string()
+                .parse()
+                .unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let records = generate_numeric_plot(&dataset, 3, 3, 1.0).unwrap().unwrap();
+        // The maximum is counted in the last of the 3 bins.
+        assert_eq!(records.len(), 3);
+        assert_eq!(records.iter().map(|record| record.value).sum::<u32>(), 4);
+
+        // Each of the 4 distinct prices gets its own bin centered on it.
+        let records = generate_numeric_plot(&dataset, 3, 50, 1.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(records.len(), 4);
+        assert!(records.iter().all(|record| record.value == 1));
+        let width = records[0].bin_end - records[0].bin_start;
+        assert!(records
+            .windows(2)
+            .all(|pair| pair[0].bin_end <= pair[1].bin_start + width * 1e-9));
+        assert_eq!(
+            (records[0].bin_start + records[0].bin_end) / 2.0,
+            -350855931677.9008
+        );
+    }
+
+    #[test]
+    fn test_numeric_plot_bin_edges() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/bin_edges.csv").unwrap();
+        let records = generate_numeric_plot(&dataset, 1, 4, 1.0).unwrap().unwrap();
+        // Values on a bin boundary fall into the upper bin, the maximum into the last bin.
+        assert_eq!(
+            records
+                .iter()
+                .take(4)
+                .map(|record| (record.bin_start, record.bin_end, record.value))
+                .collect_vec(),
+            vec![(0.0, 1.0, 2), (1.0, 2.0, 1), (2.0, 3.0, 3), (3.0, 4.0, 3)]
+        );
+        assert_eq!(
+            records[..4].iter().map(|record| record.value).sum::<u32>(),
+            9
+        );
+        assert!(records[4].bin_start.is_nan());
+        assert_eq!(records[4].value, 1);
+        assert_eq!(records.len(), 5);
+    }
+
+    #[test]
+    fn test_constant_column_plot() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/constant_columns.csv").unwrap();
+        let records = generate_numeric_plot(&dataset, 1, 20, 1.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .take(1)
+                .map(|record| (record.bin_start, record.bin_end, record.value))
+                .collect_vec(),
+            vec![(0.5, 0.5, 4)]
+        );
+        assert!(records[1].bin_start.is_nan());
+        assert_eq!(records[1].value, 1);
+        assert_eq!(records.len(), 2);
+
+        let records = generate_integer_plot(&dataset, 2, 20).unwrap().unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.bin_start, record.bin_end, record.value))
+                .collect_vec(),
+            vec![(Some(0), Some(1), 5)]
+        );
+    }
+
+    #[test]
+    fn test_numeric_plot_reads_table_once() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/bin_edges.csv").unwrap();
+        let opened = || OPENED_READERS.with(|opened| opened.get());
+        let before = opened();
+        generate_numeric_plot(&dataset, 1, 4, 1.0).unwrap().unwrap();
+        assert_eq!(opened() - before, 1);
+
+        let before = opened();
+        assert_eq!(get_min_max(&dataset, 1, None).unwrap(), (0.0, 4.0));
+        assert_eq!(opened() - before, 1);
+    }
+
+    #[test]
+    fn test_regular_bins() {
+        let bins = vec![
+            (None, Some(0.0), 1),
+            (Some(0.0), Some(1.0), 2),
+            (Some(1.0), Some(2.0), 0),
+            (Some(2.0), None, 3),
+        ];
+        assert_eq!(
+            regular_bins(bins.into_iter()),
+            vec![(0.0, 1.0, 3), (1.0, 2.0, 3)]
+        );
+    }
+
+    #[test]
+    fn test_percentage_plot_generation() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/percentages.csv").unwrap();
+        let records = generate_numeric_plot(&dataset, 1, 2, 0.01)
+            .unwrap()
+            .unwrap();
+        assert!((records[0].bin_start - 0.875).abs() < 1e-6);
+        assert_eq!(records.iter().map(|record| record.value).sum::<u32>(), 4);
+        // The missing value is counted separately.
+        let missing = records.last().unwrap();
+        assert!(missing.bin_start.is_nan());
+        assert_eq!(missing.value, 1);
+    }
+
+    #[test]
+    fn test_locale_number_plot_generation() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/locale_numbers.csv, separator: ';'}").unwrap();
+        dataset.default_number_format = Some(NumberFormat {
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+        });
+        let records = generate_numeric_plot(&dataset, 1, 2, 1.0).unwrap().unwrap();
+        assert_eq!(records[0].bin_start, 12.5);
+        assert_eq!(records.iter().map(|record| record.value).sum::<u32>(), 4);
+        // The missing value is counted separately.
+        assert!(records.last().unwrap().bin_start.is_nan());
+
+        // Numbers with unambiguous thousands separators are parsed without a format.
+        dataset.default_number_format = None;
+        let records = generate_numeric_plot(&dataset, 2, 2, 1.0).unwrap().unwrap();
+        assert_eq!(records[0].bin_start, 987.0);
+        assert!(!records.last().unwrap().bin_start.is_nan());
+    }
+
+    #[test]
+    fn test_stripped_number_plot_generation() {
+        let mut dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/units.csv").unwrap();
+        let strip = serde_yaml::from_str(r#"["$"]"#).unwrap();
+        dataset.stripped_columns.insert(
+            "price".to_string(),
+            StrippedColumn {
+                strip,
+                min_fraction: 0.9,
+            },
+        );
+        let records = generate_numeric_plot(&dataset, 1, 2, 1.0).unwrap().unwrap();
+        assert_eq!(records[0].bin_start, 3.25);
+        // The empty cell is counted separately.
+        let missing = records.last().unwrap();
+        assert!(missing.bin_start.is_nan());
+        assert_eq!(missing.value, 1);
+    }
+
+    #[test]
+    fn test_render_plots_with_type_overrides() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/type_overrides.csv").unwrap();
+        dataset.column_types = HashMap::from([
+            ("id".to_string(), ColumnType::String),
+            ("count".to_string(), ColumnType::Integer),
+        ]);
+        // The value that is not a number is counted as missing.
+        let records = generate_numeric_plot(&dataset, 1, 2, 1.0).unwrap().unwrap();
+        let missing = records.last().unwrap();
+        assert!(missing.bin_start.is_nan());
+        assert_eq!(missing.value, 1);
+
+        let plots = rendered_plots(&dataset, &HashMap::new());
+        // Sample IDs are counted as categories instead of being binned as numbers.
+        assert!(plots.contains(r#""key":"000123""#));
+    }
+
+    #[test]
+    fn test_numeric_plot_beyond_classified_rows() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/late_strings.csv").unwrap();
+        dataset.classify_rows = ClassifyRows::First(5);
+        // The value that is no number is counted as missing instead of failing.
+        let records = generate_numeric_plot(&dataset, 1, 4, 1.0).unwrap().unwrap();
+        let missing = records.last().unwrap();
+        assert!(missing.bin_start.is_nan());
+        assert_eq!(missing.value, 1);
+    }
+
+    #[test]
+    fn test_numeric_plot_of_mostly_numbers() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/mostly_numbers.csv").unwrap();
+        dataset.default_min_numeric_fraction = Some(0.75);
+        let column_types = classify_table(&dataset).unwrap();
+        assert_eq!(column_types["score"], ColumnType::Float);
+        let records = generate_numeric_plot(&dataset, 1, 3, 1.0).unwrap().unwrap();
+        let missing = records.last().unwrap();
+        assert!(missing.bin_start.is_nan());
+        assert_eq!(missing.value, 2);
+    }
+
+    #[test]
+    fn test_plots_with_na_values() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/na_tokens.csv").unwrap();
+        dataset.default_na_values = Some(vec![".".to_string(), "-".to_string()]);
+        dataset.na_values = HashMap::from([(
+            "depth".to_string(),
+            vec!["NA".to_string(), ".".to_string(), "-".to_string()],
+        )]);
+        let record = |key: &str, value| PlotRecord {
+            key: key.to_string(),
+            value,
+            percentage: percentage(value, 5),
+        };
+        let records = generate_nominal_plot(&dataset, 2, 10, None, &NominalSort::ByCount, false)
+            .unwrap()
+            .unwrap()
+            .0;
+        assert_eq!(
+            records,
+            vec![record("NA", 2), record("ok", 2), record("fail", 1)]
+        );
+        let records = generate_numeric_plot(&dataset, 1, 2, 1.0).unwrap().unwrap();
+        let missing = records.last().unwrap();
+        assert!(missing.bin_start.is_nan());
+        assert_eq!(missing.value, 3);
+    }
+
+    #[test]
+    fn test_render_plots_with_log_scale() {
+        let dataset = DatasetSpecs {
+            path: "tests/data/uniform_datatypes.csv"
+                .to_string()
+                .parse()
+                .unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let render_columns = HashMap::from([(
+            "price".to_string(),
+            RenderColumnSpec {
+                log_scale: Some(true),
+                ..Default::default()
+            },
+        )]);
+        let plots = rendered_plots(&dataset, &render_columns);
+        assert!(plots.contains(r#""scale": {"type": "log", "base": 10}"#));
+    }
+
+    #[test]
+    fn test_render_plots_of_compressed_dataset() {
+        let render_plots_of = |path: &str| {
+            let dataset = DatasetSpecs {
+                path: path.parse().unwrap(),
+                separator: Some(','),
+                header_rows: 1,
+                double_quote: true,
+                ..Default::default()
+            };
+            rendered_plots(&dataset, &HashMap::new())
+        };
+        let expected = render_plots_of("tests/data/uniform_datatypes.csv");
+        assert_eq!(
+            render_plots_of("tests/data/uniform_datatypes.csv.gz"),
+            expected
+        );
+        assert_eq!(
+            render_plots_of("tests/data/uniform_datatypes.csv.zst"),
+            expected
+        );
+        assert_eq!(
+            render_plots_of("tests/data/uniform_datatypes.csv.bz2"),
+            expected
+        );
+        assert_eq!(
+            render_plots_of("tests/data/uniform_datatypes.csv.xz"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_nominal_plot_generation_with_other_category() {
+        let dataset = DatasetSpecs {
+            path: "tests/data/many_categories.csv"
+                .to_string()
+                .parse()
+                .unwrap(),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let records = generate_nominal_plot(
+            &dataset,
+            0,
+            MAX_NOMINAL_BINS,
+            None,
+            &NominalSort::ByCount,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .0;
+        assert_eq!(records.len(), MAX_NOMINAL_BINS + 1);
+        assert_eq!(records.last().unwrap().key, "other");
+        assert_eq!(
+            records.iter().map(|record| record.value).sum::<u32>() as usize,
+            dataset.size().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_nominal_plot_generation_with_max_categories() {
+        let dataset = DatasetSpecs {
+            path: "tests/data/many_categories.csv"
+                .to_string()
+                .parse()
+                .unwrap(),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let records = generate_nominal_plot(&dataset, 0, 200, None, &NominalSort::ByCount, false)
+            .unwrap()
+            .unwrap()
+            .0;
+        assert_eq!(records.len(), 15);
+        assert!(records.iter().all(|record| record.key != "other"));
+        let records = generate_nominal_plot(&dataset, 0, 2, None, &NominalSort::ByCount, false)
+            .unwrap()
+            .unwrap()
+            .0;
+        assert_eq!(records.len(), 3);
+        assert_eq!(records.last().unwrap().key, "other");
+        let (_, omitted) =
+            generate_nominal_plot(&dataset, 0, 2, None, &NominalSort::ByCount, false)
+                .unwrap()
+                .unwrap();
+        assert_eq!(omitted, 13);
+        let (records, omitted) =
+            generate_nominal_plot(&dataset, 0, usize::MAX, None, &NominalSort::ByCount, false)
+                .unwrap()
+                .unwrap();
+        assert_eq!((records.len(), omitted), (15, 0));
+
+        // The table-wide max-categories applies to columns without their own.
+        let render = |render_columns: &HashMap<String, RenderColumnSpec>| {
+            let output = render_plots_to_tempdir(
+                &dataset,
+                render_columns,
+                Some(MaxCategories::Count(5)),
+                false,
+            )
+            .unwrap();
+            fs::read_to_string(output.path().join("plots").join("plots.js")).unwrap()
+        };
+        let plots = render(&HashMap::new());
+        assert!(plots.contains("'other (10 values)'"));
+        let column = column_name(&dataset, 0).unwrap();
+        let spec: RenderColumnSpec = serde_yaml::from_str("max-categories: all").unwrap();
+        let plots = render(&HashMap::from([(column, spec)]));
+        assert!(!plots.contains("other"));
+    }
+
+    #[test]
+    fn test_boolean_plot_generation() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/booleans.csv").unwrap();
+        let records = generate_nominal_plot(
+            &dataset,
+            2,
+            MAX_NOMINAL_BINS,
+            Some(&BooleanVocabulary::default()),
+            &NominalSort::ByCount,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .0;
+        assert_eq!(
+            records,
+            vec![
+                PlotRecord {
+                    key: "true".to_string(),
+                    value: 3,
+                    percentage: percentage(3, 6),
+                },
+                PlotRecord {
+                    key: "false".to_string(),
+                    value: 2,
+                    percentage: percentage(2, 6),
+                },
+                PlotRecord {
+                    key: "NA".to_string(),
+                    value: 1,
+                    percentage: percentage(1, 6),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crlf_plot_generation() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/crlf.csv").unwrap();
+        let records = generate_nominal_plot(
+            &dataset,
+            2,
+            MAX_NOMINAL_BINS,
+            None,
+            &NominalSort::ByCount,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .0;
+        assert_eq!(
+            records,
+            vec![
+                PlotRecord {
+                    key: "ok".to_string(),
+                    value: 3,
+                    percentage: percentage(3, 4),
+                },
+                PlotRecord {
+                    key: "failed".to_string(),
+                    value: 1,
+                    percentage: percentage(1, 4),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_date_plot_generation() {
+        let dataset = DatasetSpecs {
+            path: "tests/data/dates.csv".to_string().parse().unwrap(),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let records = generate_date_plot(&dataset, 1, DateGranularity::Month, None)
+            .unwrap()
+            .unwrap();
+        let expected = vec![
+            PlotRecord {
+                key: String::from("2023-01"),
+                value: 2,
+                percentage: percentage(2, 4),
+            },
+            PlotRecord {
+                key: String::from("2023-03"),
+                value: 1,
+                percentage: percentage(1, 4),
+            },
+            PlotRecord {
+                key: String::from("2024-07"),
+                value: 1,
+                percentage: percentage(1, 4),
+            },
+        ];
+        assert_eq!(records, expected);
+        let records = generate_date_plot(&dataset, 2, DateGranularity::Year, None)
+            .unwrap()
+            .unwrap();
+        let expected = vec![
+            PlotRecord {
+                key: String::from("2023"),
+                value: 2,
+                percentage: percentage(2, 4),
+            },
+            PlotRecord {
+                key: String::from("2024"),
+                value: 1,
+                percentage: percentage(1, 4),
+            },
+            PlotRecord {
+                key: String::from("NA"),
+                value: 1,
+                percentage: percentage(1, 4),
+            },
+        ];
+        assert_eq!(records, expected);
+    }
+
+    #[test]
+    fn test_ambiguous_date_plot_generation() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/ambiguous_dates.csv").unwrap();
+        let record = |key: &str, value| PlotRecord {
+            key: key.to_string(),
+            value,
+            percentage: percentage(value, 4),
+        };
+        let records = generate_date_plot(&dataset, 1, DateGranularity::Month, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(records, vec![record("2023-08", 3), record("NA", 1)]);
+        // Values not matching the given input format are counted as missing.
+        let records =
+            generate_date_plot(&dataset, 1, DateGranularity::Month, Some("%m/%d/%Y %H:%M"))
+                .unwrap()
+                .unwrap();
+        assert_eq!(
+            records,
+            vec![record("2023-01", 1), record("2023-02", 1), record("NA", 2)]
+        );
+    }
+
+    #[test]
+    fn test_scatter_data_generation() {
+        let dataset = serde_yaml::from_str("path: tests/data/scatter.csv").unwrap();
+        let records = generate_scatter_data(&dataset, 1, 2, Some(3)).unwrap();
+        let expected = vec![
+            ScatterRecord {
+                x: 1.5,
+                y: 10.0,
+                color: Some(String::from("control")),
+            },
+            ScatterRecord {
+                x: 2.0,
+                y: 20.0,
+                color: Some(String::from("treated")),
+            },
+            ScatterRecord {
+                x: 4.0,
+                y: 50.0,
+                color: None,
+            },
+        ];
+        assert_eq!(records, expected);
+    }
+
+    #[test]
+    fn test_scatter_data_of_formatted_numbers() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/locale_numbers.csv, separator: ';'}").unwrap();
+        dataset.number_formats.insert(
+            "eu".to_string(),
+            NumberFormat {
+                decimal_separator: ',',
+                thousands_separator: Some('.'),
+            },
+        );
+        dataset
+            .na_values
+            .insert("us".to_string(), vec!["987".to_string()]);
+        let records = generate_scatter_data(&dataset, 1, 2, None).unwrap();
+        let points = records
+            .iter()
+            .map(|record| (record.x, record.y))
+            .collect_vec();
+        assert_eq!(points, vec![(1234.56, 1234.5), (12.5, 12000.0)]);
+
+        // Values that are no finite numbers are not plotted.
+        let dataset = serde_yaml::from_str("path: tests/data/non_finite.csv").unwrap();
+        let records = generate_scatter_data(&dataset, 1, 2, None).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].x, records[0].y), (3.0, 4.0));
+    }
+
+    fn render_scatter_plots(scatter: ScatterPlotConfig) -> String {
+        let dataset = serde_yaml::from_str("path: tests/data/scatter.csv").unwrap();
+        let render_columns = HashMap::from([(
+            "expression".to_string(),
+            RenderColumnSpec {
+                scatter: Some(scatter),
+                ..Default::default()
+            },
+        )]);
+        rendered_plots(&dataset, &render_columns)
+    }
+
+    #[test]
+    fn test_render_scatter_plot() {
+        let plots = render_scatter_plots(ScatterPlotConfig {
+            x_column: "expression".to_string(),
+            y_column: "coverage".to_string(),
+            color_column: None,
+            opacity: None,
+        });
+        // The histogram is still rendered alongside the scatter plot
+        assert!(plots.contains("let plot_1 ="));
+        assert!(plots.contains("let scatter_plot_1 ="));
+        assert!(plots.contains(r#""mark": {"type": "point", "filled": true}"#));
+        assert!(plots.contains(r#""title": "coverage""#));
+        assert!(!plots.contains(r#""color": {"field": "color""#));
+    }
+
+    #[test]
+    fn test_render_scatter_plot_with_color() {
+        let plots = render_scatter_plots(ScatterPlotConfig {
+            x_column: "expression".to_string(),
+            y_column: "coverage".to_string(),
+            color_column: Some("condition".to_string()),
+            opacity: Some(0.5),
+        });
+        assert!(plots.contains(r#""opacity": 0.5"#));
+        assert!(plots
+            .contains(r#""color": {"field": "color", "type": "nominal", "title": "condition"}"#));
+        assert!(plots.contains(r#""color":"treated""#));
+    }
+
+    #[test]
+    fn test_boxplot_generation() {
+        let dataset = serde_yaml::from_str("path: tests/data/boxplot.csv").unwrap();
+        let boxplot = generate_boxplot(&dataset, 1).unwrap().unwrap();
+        // The eleven values are 1 to 10 and 50, so the quartiles lie halfway between values.
+        assert!((boxplot.q1 - 3.5).abs() < 1e-9);
+        assert!((boxplot.q3 - 8.5).abs() < 1e-9);
+        assert_eq!(boxplot.median, 6.0);
+        assert_eq!((boxplot.min, boxplot.max), (1.0, 50.0));
+        assert_eq!((boxplot.lower_whisker, boxplot.upper_whisker), (1.0, 10.0));
+        assert_eq!(boxplot.outliers, vec![50.0]);
+    }
+
+    #[test]
+    fn test_boxplot_of_formatted_numbers() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/locale_numbers.csv, separator: ';'}").unwrap();
+        dataset.number_formats.insert(
+            "eu".to_string(),
+            NumberFormat {
+                decimal_separator: ',',
+                thousands_separator: Some('.'),
+            },
+        );
+        dataset
+            .na_values
+            .insert("us".to_string(), vec!["987".to_string()]);
+        let boxplot = generate_boxplot(&dataset, 1).unwrap().unwrap();
+        assert_eq!(
+            (boxplot.min, boxplot.median, boxplot.max),
+            (12.5, 1234.56, 1_000_000.0)
+        );
+        assert_eq!(
+            sorted_numeric_values(&dataset, 2).unwrap(),
+            vec![1234.5, 12000.0, 5_000_000.25]
+        );
+
+        // Values that are no finite numbers are not plotted.
+        let dataset = serde_yaml::from_str("path: tests/data/non_finite.csv").unwrap();
+        assert_eq!(sorted_numeric_values(&dataset, 1).unwrap(), vec![2.0, 3.0]);
+        assert_eq!(sorted_numeric_values(&dataset, 2).unwrap(), vec![1.0, 4.0]);
+    }
+
+    #[test]
+    fn test_render_boxplot() {
+        let dataset = serde_yaml::from_str("path: tests/data/boxplot.csv").unwrap();
+        let render_columns = HashMap::from([(
+            "value".to_string(),
+            RenderColumnSpec {
+                boxplot: true,
+                ..Default::default()
+            },
+        )]);
+        let output = render_plots_to_tempdir(&dataset, &render_columns, None, false).unwrap();
+        let plots_path = output.path().join("plots");
+        let boxplot = fs::read_to_string(plots_path.join("boxplot_1.json")).unwrap();
+        let plots = fs::read_to_string(plots_path.join("plots.js")).unwrap();
+        assert!(boxplot.contains(r#""median":6.0"#));
+        assert!(plots.contains("let plot_1 ="));
+        assert!(plots.contains("let boxplot_1 ="));
+    }
+
+    #[test]
+    fn test_correlation_matrix_generation() {
+        let dataset = serde_yaml::from_str("path: tests/data/correlation.csv").unwrap();
+        let column_types = classify_table(&dataset).unwrap();
+        let correlations = generate_correlation_matrix(&dataset, &column_types).unwrap();
+        // Three numeric columns yield the diagonal and both orders of the three pairs.
+        assert_eq!(correlations.len(), 9);
+        let r = |col_a: &str, col_b: &str| {
+            correlations
+                .iter()
+                .find(|record| record.col_a == col_a && record.col_b == col_b)
+                .unwrap()
+                .r
+                .unwrap()
+        };
+        // y is a linear function of x.
+        assert!((r("x", "y") - 1.0).abs() < 1e-12);
+        assert_eq!(r("x", "y"), r("y", "x"));
+        assert!((r("z", "z") - 1.0).abs() < 1e-12);
+        assert!(r("x", "z").abs() < 1.0);
+    }
+
+    #[test]
+    fn test_correlation_matrix_of_formatted_numbers() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/formatted_correlation.csv, separator: ';'}")
+                .unwrap();
+        dataset.number_formats.insert(
+            "a".to_string(),
+            NumberFormat {
+                decimal_separator: ',',
+                thousands_separator: Some('.'),
+            },
+        );
+        dataset
+            .na_values
+            .insert("b".to_string(), vec!["999".to_string()]);
+        let column_types = HashMap::from([
+            ("a".to_string(), ColumnType::Float),
+            ("b".to_string(), ColumnType::Integer),
+        ]);
+        let correlations = generate_correlation_matrix(&dataset, &column_types).unwrap();
+        // Without the missing value, b is a linear function of a.
+        let r = correlations
+            .iter()
+            .find(|record| record.col_a == "a" && record.col_b == "b")
+            .unwrap()
+            .r
+            .unwrap();
+        assert!((r - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_render_correlation_matrix() {
+        let dataset = serde_yaml::from_str("path: tests/data/correlation.csv").unwrap();
+        let output = render_plots_to_tempdir(&dataset, &HashMap::new(), None, true).unwrap();
+        let plots_path = output.path().join("plots");
+        let plots = fs::read_to_string(plots_path.join("plots.js")).unwrap();
+        assert!(plots.contains("let correlation_matrix ="));
+        assert!(plots_path.join("correlation.json").exists());
+
+        // Tables with a single numeric column are skipped.
+        let dataset = serde_yaml::from_str("path: tests/data/stats.csv").unwrap();
+        let output = render_plots_to_tempdir(&dataset, &HashMap::new(), None, true).unwrap();
+        let plots_path = output.path().join("plots");
+        let plots = fs::read_to_string(plots_path.join("plots.js")).unwrap();
+        assert!(!plots.contains("correlation_matrix"));
+        assert!(!plots_path.join("correlation.json").exists());
+    }
+
+    #[test]
+    fn test_violin_data_generation() {
+        let dataset = serde_yaml::from_str("path: tests/data/boxplot.csv").unwrap();
+        let violin = generate_violin_data(&dataset, 1).unwrap().unwrap();
+        assert_eq!(violin.densities.len(), 2 * VIOLIN_POINTS);
+        let (top, bottom) = violin.densities.split_at(VIOLIN_POINTS);
+        assert!(top.iter().all(|record| record.density > 0.0));
+        for (top, bottom) in top.iter().zip(bottom) {
+            assert_eq!((top.x, top.density), (bottom.x, -bottom.density));
+        }
+        assert_eq!((top[0].x, top[VIOLIN_POINTS - 1].x), (1.0, 50.0));
+        assert_eq!(violin.outliers, vec![50.0]);
+    }
+
+    #[test]
+    fn test_render_violin_plot() {
+        let dataset = serde_yaml::from_str("path: tests/data/boxplot.csv").unwrap();
+        let render_columns = HashMap::from([(
+            "value".to_string(),
+            RenderColumnSpec {
+                violin: true,
+                ..Default::default()
+            },
+        )]);
+        let plots = rendered_plots(&dataset, &render_columns);
+        assert!(plots.contains("let show_violin_plot_1 = true;"));
+        assert!(plots.contains(r#""orient": "horizontal""#));
+        assert!(plots.contains(r#""data": {"values": [{"x":50.0}]}"#));
+    }
+
+    fn render_custom_vega_spec_plots(spec: &str) -> anyhow::Result<String> {
+        let dataset = serde_yaml::from_str("path: tests/data/uniform_datatypes.csv").unwrap();
+        let render_columns = HashMap::from([(
+            "first".to_string(),
+            RenderColumnSpec {
+                custom_vega_spec: Some(spec.into()),
+                ..Default::default()
+            },
+        )]);
+        let output = render_plots_to_tempdir(&dataset, &render_columns, None, false)?;
+        Ok(fs::read_to_string(
+            output.path().join("plots").join("plots.js"),
+        )?)
+    }
+
+    #[test]
+    fn test_nominal_sort() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/statuses.csv").unwrap();
+        let keys = |sort: NominalSort, max_categories| {
+            generate_nominal_plot(&dataset, 1, max_categories, None, &sort, false)
+                .unwrap()
+                .unwrap()
+                .0
+                .into_iter()
+                .map(|record| record.key)
+                .collect_vec()
+        };
+        assert_eq!(
+            keys(NominalSort::ByCount, 10),
+            vec!["PASS", "FAIL", "WARN", "unknown"]
+        );
+        assert_eq!(
+            keys(NominalSort::Alphabetical, 10),
+            vec!["FAIL", "PASS", "WARN", "unknown"]
+        );
+        // Values missing from the custom order follow in alphabetical order.
+        let custom = NominalSort::Custom(vec!["unknown".to_string(), "WARN".to_string()]);
+        assert_eq!(
+            keys(custom.clone(), 10),
+            vec!["unknown", "WARN", "FAIL", "PASS"]
+        );
+        // The order determines which values are kept.
+        assert_eq!(keys(custom, 2), vec!["unknown", "WARN", "other"]);
+
+        let sort: NominalSort = serde_yaml::from_str("[high, low]").unwrap();
+        assert_eq!(
+            sort,
+            NominalSort::Custom(vec!["high".to_string(), "low".to_string()])
+        );
+        assert_eq!(
+            serde_yaml::from_str::<NominalSort>("alphabetical").unwrap(),
+            NominalSort::Alphabetical
+        );
+        assert_eq!(
+            serde_yaml::from_str::<NominalSort>("numeric").unwrap(),
+            NominalSort::Numeric
+        );
+        assert!(serde_yaml::from_str::<NominalSort>("by-value").is_err());
+    }
+
+    #[test]
+    fn test_categorical_integer_plot() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/chromosomes.csv").unwrap();
+        // Missing values are not counted.
+        assert!(has_few_values(&dataset, 2, 3).unwrap());
+        assert!(!has_few_values(&dataset, 2, 2).unwrap());
+        assert!(!has_few_values(&dataset, 1, MAX_CATEGORICAL_INTEGERS).unwrap());
+
+        let plots = rendered_plots(&dataset, &HashMap::new());
+        assert!(plots.contains(r#"{"key":"0","value":7"#));
+        assert!(!plots.contains(r#"{"key":"10""#));
+
+        // More categories make more columns categorical, whose bars are sorted numerically.
+        let spec: RenderColumnSpec = serde_yaml::from_str("max-categories: 20").unwrap();
+        let plots = rendered_plots(&dataset, &HashMap::from([("chromosome".to_string(), spec)]));
+        let position = |key: &str| plots.find(&format!(r#"{{"key":"{key}""#)).unwrap();
+        assert!(position("2") < position("10"));
+
+        let spec: RenderColumnSpec = serde_yaml::from_str("categorical: false").unwrap();
+        let plots = rendered_plots(
+            &dataset,
+            &HashMap::from([("filter_flag".to_string(), spec)]),
+        );
+        assert!(!plots.contains(r#"{"key":"0""#));
+    }
+
+    #[test]
+    fn test_render_custom_nominal_sort() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/severities.csv").unwrap();
+        let spec: RenderColumnSpec =
+            serde_yaml::from_str("nominal-sort: [Low, Medium, High, Critical]").unwrap();
+        let render_columns = HashMap::from([("severity".to_string(), spec)]);
+        let plots = rendered_plots(&dataset, &render_columns);
+        // The bars are in the configured order instead of starting with the most frequent value.
+        assert!(plots.contains(r#""values": [{"key":"Low","value":2"#));
+        assert!(plots.contains(r#""sort": null"#));
+    }
+
+    #[test]
+    fn test_natural_nominal_sort() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/sample_ids.csv").unwrap();
+        let keys = |sort: NominalSort, natural| {
+            generate_nominal_plot(&dataset, 1, 10, None, &sort, natural)
+                .unwrap()
+                .unwrap()
+                .0
+                .into_iter()
+                .map(|record| record.key)
+                .collect_vec()
+        };
+        assert_eq!(
+            keys(NominalSort::Alphabetical, true),
+            vec!["b1", "b2", "b10"]
+        );
+        assert_eq!(
+            keys(NominalSort::Alphabetical, false),
+            vec!["b1", "b10", "b2"]
+        );
+        // Values missing from the custom order follow in natural order.
+        let custom = NominalSort::Custom(vec!["b2".to_string()]);
+        assert_eq!(keys(custom, true), vec!["b2", "b1", "b10"]);
+    }
+
+    #[test]
+    fn test_integer_plot_near_max() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/large_integers.csv").unwrap();
+        let column_types = classify_table(&dataset).unwrap();
+        assert_eq!(column_types["position"], ColumnType::Integer);
+        // Integers beyond 64 bits are treated as floats.
+        assert_eq!(column_types["huge"], ColumnType::Float);
+        assert_eq!(
+            diagnose_columns(&dataset)
+                .unwrap()
+                .overflowing_integer_columns,
+            vec!["huge"]
+        );
+
+        let records = generate_integer_plot(&dataset, 0, 4).unwrap().unwrap();
+        let start = i128::from(i64::MAX) - 7;
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (
+                    record.bin_start.unwrap(),
+                    record.bin_end.unwrap(),
+                    record.value
+                ))
+                .collect_vec(),
+            vec![
+                (start, start + 2, 2),
+                (start + 2, start + 4, 2),
+                (start + 4, start + 6, 2),
+                (start + 6, start + 8, 2),
+            ]
+        );
+        // The bounds are serialized without rounding.
+        assert!(json!(records).to_string().contains("9223372036854775806"));
+
+        // Small ranges are not split into fractions of integers.
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/missing.csv").unwrap();
+        let records = generate_integer_plot(&dataset, 1, 30).unwrap().unwrap();
+        assert_eq!(records.len(), 25);
+        assert!(records.iter().all(|record| record
+            .bin_start
+            .is_none_or(|start| record.bin_end == Some(start + 1))));
+        assert_eq!(records.last().unwrap().bin_start, None);
+        assert_eq!(records.last().unwrap().value, 3);
+    }
+
+    #[test]
+    fn test_plot_percentages() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/many_categories.csv").unwrap();
+        let records = generate_nominal_plot(&dataset, 0, 3, None, &NominalSort::ByCount, false)
+            .unwrap()
+            .unwrap()
+            .0;
+        let sum: f32 = records.iter().map(|record| record.percentage).sum();
+        assert!((sum - 100.0).abs() < 1e-3);
+
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/missing.csv").unwrap();
+        let records = generate_numeric_plot(&dataset, 1, 3, 1.0).unwrap().unwrap();
+        let sum: f32 = records.iter().map(|record| record.percentage).sum();
+        assert!((sum - 100.0).abs() < 1e-3);
+        // Missing values are part of the total.
+        assert!(records.last().unwrap().percentage > 0.0);
+    }
+
+    #[test]
+    fn test_render_plots_of_empty_column() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/empty_column.csv").unwrap();
+        let plots = rendered_plots(&dataset, &HashMap::new());
+        assert!(plots.contains("let plot_1 = {"));
+        assert!(plots.contains("let show_plot_2 = false;\nlet plot_2 = null;"));
+    }
+
+    #[test]
+    fn test_numeric_plot_without_numbers() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/nan_column.csv").unwrap();
+        dataset
+            .column_types
+            .insert("depth".to_string(), ColumnType::Float);
+        let column_types = classify_table(&dataset).unwrap();
+        assert_eq!(column_types["score"], ColumnType::Float);
+        // Columns without any numbers only get the bar of the missing values.
+        for index in [1, 2] {
+            let records = generate_numeric_plot(&dataset, index, 20, 1.0)
+                .unwrap()
+                .unwrap();
+            assert_eq!(records.len(), 1);
+            assert!(records[0].bin_start.is_nan());
+            assert_eq!(records[0].value, 3);
+        }
+
+        let plots = rendered_plots(&dataset, &HashMap::new());
+        assert!(plots.contains("let show_plot_1 = true;"));
+        assert!(plots.contains("let show_plot_2 = true;"));
+    }
+
+    #[test]
+    fn test_log_plot() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/pvalues.csv").unwrap();
+        let records = generate_log_plot(&dataset, 1, 2, 1.0).unwrap().unwrap();
+        assert_eq!(
+            records.iter().map(|record| record.value).collect_vec(),
+            vec![2, 3, 1, 1]
+        );
+        let min = 3.2e-11f64.log10();
+        assert!((records[0].bin_start - 3.2e-11).abs() < 1e-20);
+        assert!((records[0].bin_end - 10f64.powf(min / 2.0)).abs() < 1e-12);
+        assert!((records[1].bin_end - 1.0).abs() < 1e-12);
+        // Zero has no logarithm and is counted in a bin ending at zero.
+        assert_eq!(records[2].bin_start, f64::NEG_INFINITY);
+        assert_eq!(records[2].bin_end, 0.0);
+        assert!(records[3].bin_start.is_nan());
+
+        let spec: RenderColumnSpec = serde_yaml::from_str("scale: log").unwrap();
+        let render_columns = HashMap::from([("pvalue".to_string(), spec)]);
+        let plots = rendered_plots(&dataset, &render_columns);
+        assert!(plots.contains(r#""scale": {"type": "log"}"#));
+        assert!(plots.contains(r#""labelExpr": "format(datum.value, '.0e')""#));
+    }
+
+    #[test]
+    fn test_render_value_colors() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/statuses.csv").unwrap();
+        let spec: RenderColumnSpec =
+            serde_yaml::from_str("value-colors: {PASS: green, FAIL: red, WARN: yellow}").unwrap();
+        let render_columns = HashMap::from([("status".to_string(), spec)]);
+        let plots = rendered_plots(&dataset, &render_columns);
+        // Configured values come first in their order, other values get the default color.
+        assert!(plots.contains(
+            r##""scale": {"domain": ["PASS","FAIL","WARN","unknown"], "range": ["green","red","yellow","#4c78a8"]}"##
+        ));
+    }
+
+    #[test]
+    fn test_duration_plot() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/durations.csv").unwrap();
+        dataset
+            .column_types
+            .insert("walltime".to_string(), ColumnType::Duration);
+        let records = generate_duration_plot(&dataset, 2, 2).unwrap().unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.bin_start, record.bin_end, record.value))
+                .take(2)
+                .collect_vec(),
+            vec![(30.0, 1815.0, 3), (1815.0, 3600.0, 1)]
+        );
+        // The value that is no duration is counted as missing.
+        assert!(records[2].bin_start.is_nan());
+        assert_eq!(records[2].value, 1);
+
+        let render_columns = HashMap::new();
+        let plots = rendered_plots(&dataset, &render_columns);
+        // Axis labels and tooltips show readable durations instead of seconds.
+        assert!(plots.contains(r#""axis": {"labelExpr": "!isValid(datum.value) ? 'NA' : "#));
+        assert!(plots.contains(r#""as": "from""#));
+    }
+
+    #[test]
+    fn test_render_custom_vega_spec() {
+        let plots = render_custom_vega_spec_plots("tests/data/custom_spec.vl.json").unwrap();
+        let spec = plots
+            .lines()
+            .find_map(|line| line.strip_prefix("let plot_0 = "))
+            .unwrap();
+        let spec: serde_json::Value = serde_json::from_str(spec.trim_end_matches(';')).unwrap();
+        assert_eq!(spec["mark"], "arc");
+        assert_eq!(
+            spec["data"]["values"][0],
+            serde_json::json!({"key": "George", "value": 2, "percentage": 50.0})
+        );
+        // The remaining columns still get the default histograms
+        assert!(plots.contains("let plot_1 = {"));
+    }
+
+    #[test]
+    fn test_render_custom_vega_spec_without_data() {
+        let error = render_custom_vega_spec_plots("tests/data/custom_spec_without_data.vl.json")
+            .unwrap_err();
+        assert!(error.to_string().contains("does not contain a data object"));
+        let error = render_custom_vega_spec_plots("tests/data/uniform_datatypes.csv").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Could not parse custom Vega-Lite spec"));
+    }
 }
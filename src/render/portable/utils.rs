@@ -1,18 +1,71 @@
 use crate::spec::ItemsSpec;
-use anyhow::Result;
+use anyhow::{Context as AnyhowContext, Result};
+use base64::prelude::*;
+use fancy_regex::{Captures, Regex};
+use lazy_static::lazy_static;
+use log::warn;
 use minify_js::{minify, Session, TopLevelMode};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tera::{Context, Tera};
+use thiserror::Error;
 
-pub(crate) fn render_static_files<P: AsRef<Path>>(path: P) -> Result<()> {
+lazy_static! {
+    static ref START_PAGE_RE: Regex =
+        Regex::new(r#"window\.location\.href = "(?P<page>[^"]+)""#).unwrap();
+    static ref SCRIPT_RE: Regex = Regex::new(
+        r#"<script(?P<before>[^>]*?)\ssrc=["']?(?P<ref>[^"'\s>]+)["']?(?P<after>[^>]*)>\s*</script>"#
+    )
+    .unwrap();
+    static ref STYLESHEET_RE: Regex =
+        Regex::new(r#"<link[^>]*?\shref=["']?(?P<ref>[^"'\s>]+)["']?[^>]*>"#).unwrap();
+    static ref IMAGE_RE: Regex =
+        Regex::new(r#"(?P<before><img[^>]*?\ssrc=)["']?(?P<ref>[^"'\s>]+)["']?"#).unwrap();
+}
+
+pub(crate) fn render_static_files<P: AsRef<Path>>(path: P, custom_css: &[PathBuf]) -> Result<()> {
     let path = Path::new(path.as_ref()).join("static");
     fs::create_dir(&path)?;
     let bundle = include_str!(concat!(env!("OUT_DIR"), "/web/dist/bundle.js"));
     let mut out = File::create(path.join(Path::new("bundle.js")))?;
     out.write_all(bundle.as_bytes())?;
+    // The range slider of numeric column filters is loaded next to the bundle.
+    fs::write(
+        path.join("nouislider.min.js"),
+        include_str!(concat!(
+            env!("OUT_DIR"),
+            "/web/node_modules/nouislider/dist/nouislider.min.js"
+        )),
+    )?;
+    fs::write(
+        path.join("nouislider.min.css"),
+        include_str!(concat!(
+            env!("OUT_DIR"),
+            "/web/node_modules/nouislider/dist/nouislider.min.css"
+        )),
+    )?;
+    // The multi-select of nominal column filters as well.
+    fs::write(
+        path.join("choices.min.js"),
+        include_str!(concat!(
+            env!("OUT_DIR"),
+            "/web/node_modules/choices.js/public/assets/scripts/choices.min.js"
+        )),
+    )?;
+    fs::write(
+        path.join("choices.min.css"),
+        include_str!(concat!(
+            env!("OUT_DIR"),
+            "/web/node_modules/choices.js/public/assets/styles/choices.min.css"
+        )),
+    )?;
+    for stylesheet in custom_css {
+        if let Some(name) = stylesheet.file_name() {
+            fs::copy(stylesheet, path.join(name))?;
+        }
+    }
     Ok(())
 }
 
@@ -45,6 +98,7 @@ pub(crate) fn render_index_file<P: AsRef<Path>>(path: P, specs: &ItemsSpec) -> R
     )?;
     let mut context = Context::new();
     context.insert("table", table);
+    context.insert("theme", &specs.theme);
     let file_path = Path::new(path.as_ref()).join(Path::new("index").with_extension("html"));
     let html = templates.render("index.html.tera", &context)?;
     let mut file = fs::File::create(file_path)?;
@@ -52,17 +106,127 @@ pub(crate) fn render_index_file<P: AsRef<Path>>(path: P, specs: &ItemsSpec) -> R
     Ok(())
 }
 
-pub(crate) fn round(x: f32, decimals: u32) -> f32 {
-    let y = 10i32.pow(decimals) as f32;
+pub(crate) fn round(x: f64, decimals: u32) -> f64 {
+    let y = 10i32.pow(decimals) as f64;
     (x * y).round() / y
 }
 
+/// Bundles the start page of the report rendered to the given directory into a single
+/// self-contained HTML file by inlining its local scripts, stylesheets and images.
+pub(crate) fn render_single_file(dir: &Path, out: &Path) -> Result<()> {
+    let index = fs::read_to_string(dir.join("index.html"))?;
+    let start_page = START_PAGE_RE
+        .captures(&index)?
+        .and_then(|captures| captures.name("page"))
+        .ok_or(SingleFileError::MissingStartPage {
+            path: dir.to_path_buf(),
+        })?;
+    let page_path = dir.join(start_page.as_str());
+    let base = page_path.parent().unwrap();
+    if base.join("data").join("data_2.js").exists() {
+        warn!("The single file report only contains the first page of its table.");
+    }
+    let read = |reference: &str| {
+        let path = base.join(reference);
+        fs::read(&path).context(format!(
+            "Failed to inline {path:?} into single file report."
+        ))
+    };
+    let html = fs::read_to_string(&page_path)?;
+    let html = inline_references(&html, &SCRIPT_RE, |captures, reference| {
+        // Closing script tags within the inlined code would end the script block early.
+        let script = String::from_utf8_lossy(&read(reference)?).replace("</script", "<\\/script");
+        Ok(format!(
+            "<script{}{}>{script}</script>",
+            &captures["before"], &captures["after"]
+        ))
+    })?;
+    let html = inline_references(&html, &STYLESHEET_RE, |_, reference| {
+        Ok(format!(
+            "<style>{}</style>",
+            String::from_utf8_lossy(&read(reference)?)
+        ))
+    })?;
+    let html = inline_references(&html, &IMAGE_RE, |captures, reference| {
+        Ok(format!(
+            "{}\"data:{};base64,{}\"",
+            &captures["before"],
+            mime_type(reference),
+            BASE64_STANDARD.encode(read(reference)?)
+        ))
+    })?;
+    fs::write(out, html)?;
+    Ok(())
+}
+
+/// Replaces all matches of the given pattern referring to a local file by the result of the
+/// given function. References to external URLs are kept.
+fn inline_references(
+    html: &str,
+    pattern: &Regex,
+    inline: impl Fn(&Captures, &str) -> Result<String>,
+) -> Result<String> {
+    let mut inlined = String::with_capacity(html.len());
+    let mut last = 0;
+    for captures in pattern.captures_iter(html) {
+        let captures = captures?;
+        let matched = captures.get(0).unwrap();
+        let reference = &captures["ref"];
+        inlined.push_str(&html[last..matched.start()]);
+        if reference.contains("://")
+            || reference.starts_with("//")
+            || reference.starts_with("data:")
+        {
+            inlined.push_str(matched.as_str());
+        } else {
+            inlined.push_str(&inline(&captures, reference)?);
+        }
+        last = matched.end();
+    }
+    inlined.push_str(&html[last..]);
+    Ok(inlined)
+}
+
+/// Returns the MIME type of the given image file based on its extension.
+fn mime_type(path: &str) -> &'static str {
+    match Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase())
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SingleFileError {
+    #[error("Could not find the start page of the report rendered to {path:?}.")]
+    MissingStartPage { path: PathBuf },
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::render::portable::utils::{render_single_file, SCRIPT_RE};
+    use crate::render::portable::ItemRenderer;
+    use crate::render::Renderer;
+    use crate::spec::Theme;
+    use crate::utils::remote::DownloadSettings;
     use crate::{render_index_file, render_static_files, ItemsSpec};
     use std::fs;
     use std::path::Path;
 
+    /// Returns the given HTML without the contents of its inline scripts.
+    fn without_inline_scripts(html: &str) -> String {
+        let script = fancy_regex::Regex::new(r"(?s)<script[^>]*>.*?</script>").unwrap();
+        script.replace_all(html, "<script></script>").to_string()
+    }
+
     #[test]
     fn test_render_index_file() {
         let spec = ItemsSpec {
@@ -73,6 +237,11 @@ mod tests {
             views: Default::default(),
             aux_libraries: None,
             webview_controls: false,
+            single_file: false,
+            theme: Theme::Light,
+            custom_css: vec![],
+            default_bins: None,
+            extends: None,
         };
         render_index_file(Path::new("/tmp"), &spec).unwrap();
         let rendered_file_content = fs::read_to_string("/tmp/index.html")
@@ -87,7 +256,7 @@ mod tests {
 
     #[test]
     fn test_render_static_files() {
-        render_static_files(Path::new("/tmp")).unwrap();
+        render_static_files(Path::new("/tmp"), &[]).unwrap();
         let bundle = include_str!(concat!(env!("OUT_DIR"), "/web/dist/bundle.js"));
 
         let rendered_file_content = fs::read_to_string("/tmp/static/bundle.js")
@@ -99,4 +268,107 @@ mod tests {
         }
         fs::remove_dir("/tmp/static").unwrap();
     }
+
+    #[test]
+    fn test_render_range_slider_files() {
+        let dir = tempfile::tempdir().unwrap();
+        render_static_files(dir.path(), &[]).unwrap();
+        for file in ["nouislider.min.js", "nouislider.min.css"] {
+            assert!(dir.path().join("static").join(file).exists());
+        }
+    }
+
+    #[test]
+    fn test_render_multi_select_files() {
+        let dir = tempfile::tempdir().unwrap();
+        render_static_files(dir.path(), &[]).unwrap();
+        for file in ["choices.min.js", "choices.min.css"] {
+            assert!(dir.path().join("static").join(file).exists());
+        }
+    }
+
+    #[test]
+    fn test_render_single_file() {
+        let specs =
+            ItemsSpec::from_file("tests/missing.yaml", &DownloadSettings::default()).unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-single-file");
+        fs::create_dir_all(&output_path).unwrap();
+        render_index_file(&output_path, &specs).unwrap();
+        render_static_files(&output_path, &specs.custom_css).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let out = std::env::temp_dir().join("datavzrd-test-single-file.html");
+        render_single_file(&output_path, &out).unwrap();
+        let html = fs::read_to_string(&out).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        fs::remove_file(&out).unwrap();
+        assert!(!SCRIPT_RE.is_match(&html).unwrap());
+        assert!(html.contains("const config = "));
+        assert!(html.contains("const data = "));
+        let markup = without_inline_scripts(&html);
+        assert!(!markup.contains("src="));
+        assert!(!markup.contains("href="));
+    }
+
+    #[test]
+    fn test_render_custom_css() {
+        let specs =
+            ItemsSpec::from_file("tests/custom_css.yaml", &DownloadSettings::default()).unwrap();
+        let output_path = std::env::temp_dir().join("datavzrd-test-custom-css");
+        fs::create_dir_all(&output_path).unwrap();
+        render_index_file(&output_path, &specs).unwrap();
+        render_static_files(&output_path, &specs.custom_css).unwrap();
+        ItemRenderer::builder()
+            .specs(specs)
+            .show_progress(false)
+            .build()
+            .render_tables(&output_path, "", true)
+            .unwrap();
+        let page = fs::read_to_string(output_path.join("scores").join("index_1.html")).unwrap();
+        let out = std::env::temp_dir().join("datavzrd-test-custom-css.html");
+        render_single_file(&output_path, &out).unwrap();
+        let html = fs::read_to_string(&out).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        fs::remove_file(&out).unwrap();
+        assert!(page.contains(r#"<link rel="stylesheet" href="../static/branding.css">"#));
+        assert!(html.contains(".my-institution-header {\n    color: #004b87;\n}"));
+    }
+
+    #[test]
+    fn test_inline_stylesheets_and_images() {
+        let output_path = std::env::temp_dir().join("datavzrd-test-single-file-assets");
+        let view_path = output_path.join("view");
+        fs::create_dir_all(&view_path).unwrap();
+        fs::write(
+            output_path.join("index.html"),
+            r#"<script>window.location.href = "view/index_1.html";</script>"#,
+        )
+        .unwrap();
+        fs::write(
+            view_path.join("index_1.html"),
+            r#"<link rel="stylesheet" href="style.css"><img src="./logo.svg" class="logo"><script src="https://cdn.example.org/lib.js"></script><script src=main.js></script>"#,
+        )
+        .unwrap();
+        fs::write(view_path.join("style.css"), "body { margin: 0; }").unwrap();
+        fs::write(view_path.join("logo.svg"), "<svg></svg>").unwrap();
+        fs::write(view_path.join("main.js"), r#"let tag = "</script>";"#).unwrap();
+        let out = output_path.join("report.html");
+        render_single_file(&output_path, &out).unwrap();
+        let html = fs::read_to_string(&out).unwrap();
+        fs::remove_dir_all(&output_path).unwrap();
+        assert_eq!(
+            html,
+            concat!(
+                "<style>body { margin: 0; }</style>",
+                r#"<img src="data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=" class="logo">"#,
+                // External scripts cannot be inlined without network access.
+                r#"<script src="https://cdn.example.org/lib.js"></script>"#,
+                r#"<script>let tag = "<\/script>";</script>"#
+            )
+        );
+    }
 }
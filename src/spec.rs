@@ -1,5 +1,6 @@
 use crate::render::portable::get_column_domain;
 use crate::render::portable::DatasetError;
+use crate::render::portable::{image_columns, local_images};
 use crate::spec::ConfigError::{
     ConflictingConfiguration, LinkToMissingView, LogScaleDomainIncludesZero, LogScaleIncludesZero,
     MissingLinkoutColumn, PlotAndTablePresentConfiguration, UnsupportedColorScheme,
@@ -7,20 +8,41 @@ use crate::spec::ConfigError::{
     WrongRangeLengthWithMidDomain,
 };
 use crate::utils::column_position;
-use crate::utils::column_type::{classify_table, ColumnType};
-use anyhow::{anyhow, Result};
+use crate::utils::column_type::{
+    classify_table, diagnose_columns, non_numeric_stripped_columns, parse_number,
+    tolerated_non_numeric_values, BooleanVocabulary, ColumnDiagnostics, ColumnType,
+};
+use crate::utils::computed::{BoundComputedColumns, ComputedColumn, ValueExpression};
+use crate::utils::filter::FilterExpression;
+use crate::utils::natural_sort::natural_compare;
+use crate::utils::reader::{
+    buffer_stdin, check_record_lengths, concatenate_tables, convert_to_temp_file,
+    decompress_to_temp_file, dedup_headers, detect_separator, duplicate_headers, glob_paths,
+    is_delimited, normalized_headers, open_table_reader, utf16_encoding, write_inline_table,
+    write_temp_csv, TableReader, STDIN_PATH,
+};
+use crate::utils::remote::{fetch_dataset, remote_url, DownloadSettings};
+use anyhow::Result;
 use anyhow::{bail, Context};
 use derefable::Derefable;
+use encoding_rs::Encoding;
 use fancy_regex::Regex;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 
 use crate::spells::SpellSpec;
 use format_serde_error::SerdeError;
-use serde::Deserialize;
+use indexmap::IndexMap;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::Error as _;
 use serde::Serialize;
-use std::borrow::BorrowMut;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer};
+use std::borrow::{BorrowMut, Cow};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::env;
 use std::fmt::Debug;
 use std::fs;
 use std::fs::File;
@@ -29,7 +51,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use thiserror::Error;
 
-#[derive(Derefable, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Derefable, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct ItemsSpec {
     #[serde(default, rename = "name")]
@@ -43,17 +65,175 @@ pub(crate) struct ItemsSpec {
     pub(crate) aux_libraries: Option<Vec<String>>,
     #[serde(default)]
     pub(crate) webview_controls: bool,
+    /// Whether to write the report as a single self-contained HTML file.
+    #[serde(default)]
+    pub(crate) single_file: bool,
+    /// Color theme of the rendered pages and plots.
+    #[serde(default)]
+    pub(crate) theme: Theme,
+    /// Stylesheets included into all pages after the default styles, e.g. for branding.
+    #[serde(default)]
+    pub(crate) custom_css: Vec<PathBuf>,
+    /// Number of bins of the histograms of numeric columns without their own number of bins.
+    /// Defaults to 20.
+    #[serde(default)]
+    pub(crate) default_bins: Option<usize>,
+    /// Base config, relative to the directory of this config, that this config is merged on
+    /// top of. Views and datasets of both configs are kept, with those of this config replacing
+    /// those of the same name. All other settings of this config replace those of the base.
+    #[serde(default)]
+    pub(crate) extends: Option<PathBuf>,
+}
+
+/// Returns the JSON Schema of config files as pretty-printed JSON, including the descriptions
+/// of all fields.
+pub(crate) fn config_schema() -> Result<String> {
+    let mut schema = schemars::schema_for!(ItemsSpec);
+    // Serde aliases are unknown to schemars, so the alias `display-name` of `label` is added
+    // by hand to keep configs using it valid.
+    if let Some(Schema::Object(column)) = schema.definitions.get_mut("RenderColumnSpec") {
+        let properties = &mut column.object().properties;
+        if let Some(label) = properties.get("label").cloned() {
+            properties.insert(String::from("display-name"), label);
+        }
+    }
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
+
+lazy_static! {
+    static ref ENV_VAR_RE: Regex = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+}
+
+/// Replaces references like `${MY_DATA_DIR}` in the string values of the given config by the
+/// values of the respective environment variables. Configs with references are serialized
+/// again, so that the values of the variables may contain any characters.
+pub(crate) fn substitute_env_vars(config: &str) -> Result<String> {
+    if !ENV_VAR_RE.is_match(config)? {
+        return Ok(config.to_string());
+    }
+    let mut value: serde_yaml::Value =
+        serde_yaml::from_str(config).map_err(|err| SerdeError::new(config.to_string(), err))?;
+    substitute_env_vars_in_value(&mut value)?;
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+fn substitute_env_vars_in_value(value: &mut serde_yaml::Value) -> Result<()> {
+    match value {
+        serde_yaml::Value::String(string) => {
+            let mut substituted = String::with_capacity(string.len());
+            let mut last = 0;
+            for captures in ENV_VAR_RE.captures_iter(string) {
+                let captures = captures?;
+                let matched = captures.get(0).unwrap();
+                let name = &captures[1];
+                let variable = env::var(name).map_err(|_| ConfigError::UndefinedEnvVar {
+                    name: name.to_string(),
+                })?;
+                substituted.push_str(&string[last..matched.start()]);
+                substituted.push_str(&variable);
+                last = matched.end();
+            }
+            substituted.push_str(&string[last..]);
+            *string = substituted;
+        }
+        serde_yaml::Value::Sequence(values) => {
+            for value in values {
+                substitute_env_vars_in_value(value)?;
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (_, value) in mapping.iter_mut() {
+                substitute_env_vars_in_value(value)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Reads the given config file with substituted environment variables, merged on top of the
+/// base configs it extends, if any.
+pub(crate) fn read_config(path: &Path) -> Result<String> {
+    read_extending_config(path, &mut Vec::new())
+}
+
+/// Returns the base configs the given config extends, directly or indirectly.
+pub(crate) fn extended_configs(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut chain = Vec::new();
+    read_extending_config(path, &mut chain)?;
+    Ok(chain.into_iter().skip(1).collect())
+}
+
+/// Reads the given config and the base configs it extends, which are appended to the given
+/// chain of configs extending each other.
+fn read_extending_config(path: &Path, chain: &mut Vec<PathBuf>) -> Result<String> {
+    let config = fs::read_to_string(path).context(format!(
+        "Could not find config file under given path {path:?}"
+    ))?;
+    let config = substitute_env_vars(&config)?;
+    let canonical = fs::canonicalize(path)?;
+    if let Some(start) = chain.iter().position(|config| config == &canonical) {
+        bail!(ConfigError::CircularExtends {
+            configs: chain[start..].to_vec()
+        })
+    }
+    chain.push(canonical);
+    let value: serde_yaml::Value =
+        serde_yaml::from_str(&config).map_err(|err| SerdeError::new(config.to_string(), err))?;
+    let (Some(child), Some(base)) = (
+        value.as_mapping(),
+        value.get("extends").and_then(serde_yaml::Value::as_str),
+    ) else {
+        return Ok(config);
+    };
+    let base_path = path.parent().unwrap_or(Path::new("")).join(base);
+    let base: serde_yaml::Value = serde_yaml::from_str(&read_extending_config(&base_path, chain)?)?;
+    let mut child = child.clone();
+    child.remove(&serde_yaml::Value::from("extends"));
+    let merged = merge_specs(base.as_mapping().cloned().unwrap_or_default(), child);
+    Ok(serde_yaml::to_string(&merged)?)
+}
+
+/// Merges the given child config on top of the given base config. Views and datasets of both
+/// are kept, with those of the child replacing those of the base with the same name. All other
+/// values of the child replace those of the base.
+fn merge_specs(mut base: serde_yaml::Mapping, child: serde_yaml::Mapping) -> serde_yaml::Mapping {
+    for (key, value) in child {
+        let is_named_map = matches!(key.as_str(), Some("views" | "datasets"));
+        match (base.get_mut(&key), value) {
+            (
+                Some(serde_yaml::Value::Mapping(base_entries)),
+                serde_yaml::Value::Mapping(entries),
+            ) if is_named_map => {
+                for (name, entry) in entries {
+                    base_entries.insert(name, entry);
+                }
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+    base
 }
 
 impl ItemsSpec {
-    pub(crate) fn from_file<P: AsRef<Path> + Debug>(path: P) -> Result<ItemsSpec> {
-        let config_file = fs::read_to_string(&path).context(format!(
-            "Could not find config file under given path {:?}",
-            &path
-        ))?;
+    pub(crate) fn from_file<P: AsRef<Path> + Debug>(
+        path: P,
+        download_settings: &DownloadSettings,
+    ) -> Result<ItemsSpec> {
+        let config_file = read_config(path.as_ref())?;
         let mut items_spec: ItemsSpec = serde_yaml::from_str(&config_file)
             .map_err(|err| SerdeError::new(config_file.to_string(), err))?;
-        for (_, spec) in items_spec.views.iter_mut() {
+        if let Some(path) = items_spec.custom_css.iter().find(|path| !path.is_file()) {
+            bail!(ConfigError::MissingCustomCss {
+                path: path.to_owned()
+            })
+        }
+        for (name, dataset) in items_spec.datasets.iter_mut() {
+            dataset.preprocess(name, download_settings)?;
+        }
+        for (name, spec) in items_spec.views.iter_mut() {
             if let Some(spell) = spec.spell.as_ref() {
                 let rendered_spec = spell.render_item_spec()?;
                 *spec = spec.merge_item_specs(&rendered_spec)?;
@@ -70,6 +250,12 @@ impl ItemsSpec {
                         })
                     }
                 };
+                // Computed columns are configured like any other column of the dataset.
+                let dataset = &spec
+                    .render_table
+                    .as_ref()
+                    .unwrap()
+                    .computed_dataset(name, dataset)?;
                 spec.preprocess_columns(dataset, items_spec.max_in_memory_rows)?;
             }
         }
@@ -80,7 +266,204 @@ impl ItemsSpec {
         self.datasets.values().any(|dataset| dataset.offer_excel)
     }
 
+    /// Returns the file names of the custom stylesheets as copied to the static directory.
+    pub(crate) fn custom_stylesheets(&self) -> Vec<String> {
+        self.custom_css
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Returns the number of undefined computed values of each view that has any.
+    pub(crate) fn invalid_computed_values(&self) -> Result<Vec<(String, usize)>> {
+        let mut invalid_values = Vec::new();
+        for (name, view) in &self.views {
+            if let (Some(render_table), Some(dataset)) = (&view.render_table, &view.dataset) {
+                if render_table.computed_columns.is_empty() {
+                    continue;
+                }
+                let dataset = DatasetSpecs {
+                    computed_columns: render_table.computed_columns()?,
+                    ..self.datasets.get(dataset).unwrap().clone()
+                };
+                let count = dataset.invalid_computed_values()?;
+                if count > 0 {
+                    invalid_values.push((name.to_owned(), count));
+                }
+            }
+        }
+        Ok(invalid_values)
+    }
+
+    /// Returns the column diagnostics of each dataset, sorted by dataset name.
+    pub(crate) fn column_diagnostics(&self) -> Result<Vec<(String, ColumnDiagnostics)>> {
+        let mut diagnostics = Vec::new();
+        for (name, dataset) in &self.datasets {
+            diagnostics.push((name.to_owned(), diagnose_columns(dataset)?));
+        }
+        diagnostics.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(diagnostics)
+    }
+
+    /// Returns the dataset names and headers that are changed by normalizing them, e.g. by
+    /// trimming surrounding whitespace, along with their normalized names.
+    pub(crate) fn normalized_headers(&self) -> Result<Vec<(String, String, String)>> {
+        let mut normalized = Vec::new();
+        for (name, dataset) in &self.datasets {
+            for (header, normalized_header) in normalized_headers(dataset)? {
+                normalized.push((name.to_owned(), header, normalized_header));
+            }
+        }
+        normalized.sort();
+        Ok(normalized)
+    }
+
+    /// Returns the views, columns and fractions of numbers of stripped columns that are rendered
+    /// as text because too few of their values are numbers after stripping.
+    pub(crate) fn non_numeric_stripped_columns(&self) -> Result<Vec<(String, String, f64)>> {
+        let mut non_numeric = Vec::new();
+        for (name, view) in &self.views {
+            if let (Some(render_table), Some(dataset)) = (&view.render_table, &view.dataset) {
+                if render_table
+                    .columns
+                    .values()
+                    .all(|spec| spec.strip.is_none())
+                {
+                    continue;
+                }
+                let Some(dataset) = self.datasets.get(dataset) else {
+                    continue;
+                };
+                if let Some(dataset) = render_table.view_dataset(dataset)? {
+                    for (column, fraction) in non_numeric_stripped_columns(&dataset)? {
+                        non_numeric.push((name.to_owned(), column, fraction));
+                    }
+                }
+            }
+        }
+        Ok(non_numeric)
+    }
+
+    /// Returns a short summary of each view sorted by name, as printed by `--validate`.
+    pub(crate) fn validation_summary(&self) -> Result<Vec<(String, String)>> {
+        let mut summary = Vec::new();
+        for (name, view) in &self.views {
+            let description = if let Some(datasets) = &view.datasets {
+                format!("{} datasets", datasets.len())
+            } else if let Some(dataset) = view.dataset.as_ref().and_then(|d| self.datasets.get(d)) {
+                let columns = dataset.reader()?.headers()?.len();
+                let source = if dataset.data.is_some() {
+                    "inline data"
+                } else {
+                    "path exists"
+                };
+                format!("{columns} columns, {source}")
+            } else {
+                "file exists".to_string()
+            };
+            summary.push((name.to_owned(), description));
+        }
+        summary.sort();
+        Ok(summary)
+    }
+
+    /// Returns the views and columns that are treated as numeric although some of their values
+    /// are no numbers, either because of a minimal numeric fraction or a configured numeric
+    /// type, along with the number of such values and the first of them with their rows.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn tolerated_non_numeric_values(
+        &self,
+    ) -> Result<Vec<(String, String, usize, Vec<(usize, String)>)>> {
+        let mut tolerated = Vec::new();
+        for (name, view) in &self.views {
+            if let (Some(render_table), Some(dataset)) = (&view.render_table, &view.dataset) {
+                if render_table.min_numeric_fraction.is_none()
+                    && render_table.columns.values().all(|spec| {
+                        spec.min_numeric_fraction.is_none()
+                            && !spec
+                                .column_type
+                                .is_some_and(|column_type| column_type.is_numeric())
+                    })
+                {
+                    continue;
+                }
+                let Some(dataset) = self.datasets.get(dataset) else {
+                    continue;
+                };
+                if let Some(dataset) = render_table.view_dataset(dataset)? {
+                    for (column, count, examples) in tolerated_non_numeric_values(&dataset)? {
+                        tolerated.push((name.to_owned(), column, count, examples));
+                    }
+                }
+            }
+        }
+        tolerated.sort();
+        Ok(tolerated)
+    }
+
+    /// Returns the views and columns without any values, for which no plots are shown. The given
+    /// column diagnostics of the datasets are reused for views rendering a whole dataset.
+    pub(crate) fn empty_columns(
+        &self,
+        column_diagnostics: &[(String, ColumnDiagnostics)],
+    ) -> Result<Vec<(String, String)>> {
+        let mut empty = Vec::new();
+        for (name, view) in &self.views {
+            if let (Some(render_table), Some(dataset_name)) = (&view.render_table, &view.dataset) {
+                let Some(dataset) = self.datasets.get(dataset_name) else {
+                    continue;
+                };
+                let columns = match render_table.view_dataset(dataset)? {
+                    Some(view_dataset) => diagnose_columns(&view_dataset)?.empty_columns,
+                    None => match column_diagnostics
+                        .iter()
+                        .find(|(dataset, _)| dataset == dataset_name)
+                    {
+                        Some((_, diagnostics)) => diagnostics.empty_columns.clone(),
+                        None => diagnose_columns(dataset)?.empty_columns,
+                    },
+                };
+                for column in columns {
+                    empty.push((name.to_owned(), column));
+                }
+            }
+        }
+        empty.sort();
+        Ok(empty)
+    }
+
+    /// Returns the views and image columns referencing local images that do not exist, along
+    /// with their paths.
+    pub(crate) fn missing_images(&self) -> Result<Vec<(String, String, Vec<PathBuf>)>> {
+        let mut missing = Vec::new();
+        for (name, view) in &self.views {
+            if let (Some(render_table), Some(dataset)) = (&view.render_table, &view.dataset) {
+                let Some(dataset) = self.datasets.get(dataset) else {
+                    continue;
+                };
+                let view_dataset = render_table.view_dataset(dataset)?;
+                let dataset = view_dataset.as_ref().unwrap_or(dataset);
+                let columns = image_columns(dataset, &render_table.columns)?;
+                for (column, images) in local_images(dataset, &columns)? {
+                    let images = images
+                        .into_iter()
+                        .filter(|image| !image.is_file())
+                        .collect_vec();
+                    if !images.is_empty() {
+                        missing.push((name.to_owned(), column, images));
+                    }
+                }
+            }
+        }
+        missing.sort();
+        Ok(missing)
+    }
+
     pub(crate) fn validate(&self) -> Result<()> {
+        if self.default_bins == Some(0) {
+            bail!(ConfigError::ZeroDefaultBins)
+        }
         if let Some(view) = &self.default_view {
             if !self.views.contains_key(view) {
                 bail!(ConfigError::MissingDefaultView {
@@ -89,6 +472,17 @@ impl ItemsSpec {
             }
         }
         for (name, view) in &self.views {
+            let referenced_file = view
+                .render_img
+                .as_ref()
+                .map(|img| &img.path)
+                .or(view.render_html.as_ref().map(|html| &html.script_path));
+            if let Some(path) = referenced_file.filter(|path| !Path::new(path).is_file()) {
+                bail!(ConfigError::MissingViewFile {
+                    view: name.to_string(),
+                    path: path.to_string()
+                })
+            }
             if let Some(render_table) = &view.render_table {
                 if view.datasets.is_none() && view.render_img.is_none() {
                     if view.dataset.is_none() {
@@ -106,6 +500,25 @@ impl ItemsSpec {
                             view: name.to_string()
                         });
                     }
+                    if render_table.sample == Some(0) {
+                        bail!(ConfigError::ZeroSampleSize {
+                            view: name.to_string()
+                        })
+                    }
+                    if render_table
+                        .min_numeric_fraction
+                        .is_some_and(|fraction| !(0.0..=1.0).contains(&fraction))
+                    {
+                        bail!(ConfigError::InvalidTableMinNumericFraction {
+                            view: name.to_string()
+                        })
+                    }
+                    if !(0.0..=1.0).contains(&render_table.missing_threshold) {
+                        bail!(ConfigError::InvalidMissingThreshold {
+                            view: name.to_string(),
+                            threshold: render_table.missing_threshold
+                        })
+                    }
                     if let Some(headers) = &render_table.headers {
                         if headers.get(&0_u32).is_some() {
                             bail!(ConfigError::HeadersFirstColumnCustomized {
@@ -114,16 +527,144 @@ impl ItemsSpec {
                         }
                     }
                     let dataset = self.datasets.get(view.dataset.as_ref().unwrap()).unwrap();
+                    let dataset = &render_table.computed_dataset(name, dataset)?;
                     let mut reader = dataset.reader()?;
                     let titles = reader.headers()?.iter().map(|s| s.to_owned()).collect_vec();
-                    let column_types = classify_table(dataset)?;
+                    if let Some(filter) = render_table.unmatched_column_filters(&titles).first() {
+                        bail!(ConfigError::UnmatchedColumnFilter {
+                            filter: filter.to_string(),
+                            view: name.to_string()
+                        })
+                    }
+                    if let Some(filter) = &render_table.filter {
+                        if let Err(error) = FilterExpression::from_str(filter)
+                            .and_then(|expression| expression.bind(&titles))
+                        {
+                            bail!(ConfigError::InvalidRowFilter {
+                                view: name.to_string(),
+                                reason: error.to_string()
+                            })
+                        }
+                    }
+                    let selected_dataset = render_table.view_dataset(dataset)?;
+                    let selected = selected_dataset.as_ref().unwrap_or(dataset);
+                    let selected_titles = titles
+                        .iter()
+                        .filter(|title| selected.is_selected(title))
+                        .cloned()
+                        .collect_vec();
+                    if render_table.max_categories == Some(MaxCategories::Count(0)) {
+                        bail!(ConfigError::ZeroDefaultMaxCategories {
+                            view: name.to_string()
+                        })
+                    }
+                    for key in render_table.sort_keys() {
+                        if !selected_titles.contains(&key.column) {
+                            bail!(ConfigError::MissingColumn {
+                                column: key.column,
+                                view: name.to_string()
+                            })
+                        }
+                    }
+                    if let Err(error) =
+                        RowColors::new(&render_table.row_color_rules, &selected_titles)
+                    {
+                        bail!(ConfigError::InvalidRowColorRule {
+                            view: name.to_string(),
+                            reason: error.to_string()
+                        })
+                    }
+                    let column_types = classify_table(selected)?;
                     for (column, render_columns) in &render_table.columns {
+                        // Configurations of excluded columns are ignored.
+                        if titles.contains(column) && !selected.is_selected(column) {
+                            continue;
+                        }
                         if !titles.contains(column) && !render_columns.optional.unwrap() {
                             bail!(ConfigError::MissingColumn {
                                 column: column.to_string(),
                                 view: name.to_string()
                             })
                         }
+                        if render_columns.bins == Some(0) {
+                            bail!(ConfigError::ZeroHistogramBins {
+                                column: column.to_string(),
+                                view: name.to_string()
+                            })
+                        }
+                        if render_columns
+                            .min_numeric_fraction
+                            .is_some_and(|fraction| !(0.0..=1.0).contains(&fraction))
+                        {
+                            bail!(ConfigError::InvalidMinNumericFraction {
+                                column: column.to_string(),
+                                view: name.to_string()
+                            })
+                        }
+                        if let NominalSort::Custom(order) = &render_columns.nominal_sort {
+                            if let Some(value) = order.iter().duplicates().next() {
+                                bail!(ConfigError::DuplicateSortValue {
+                                    value: value.to_string(),
+                                    column: column.to_string(),
+                                    view: name.to_string()
+                                })
+                            }
+                        }
+                        if render_columns.max_categories == Some(MaxCategories::Count(0)) {
+                            bail!(ConfigError::ZeroMaxCategories {
+                                column: column.to_string(),
+                                view: name.to_string()
+                            })
+                        }
+                        if render_columns.color_rules.iter().any(|rule| {
+                            rule.value.is_some() && (rule.min.is_some() || rule.max.is_some())
+                        }) {
+                            bail!(ConfigError::ConflictingColorRule {
+                                column: column.to_string(),
+                                view: name.to_string()
+                            })
+                        }
+                        if let Some(linked_view) = &render_columns.link_to_table {
+                            self.validate_table_link(column, render_columns, linked_view)?;
+                        }
+                        if let Some(scatter) = &render_columns.scatter {
+                            for scatter_column in scatter.columns() {
+                                if !titles.contains(scatter_column)
+                                    || !selected.is_selected(scatter_column)
+                                {
+                                    bail!(ConfigError::MissingColumn {
+                                        column: scatter_column.to_string(),
+                                        view: name.to_string()
+                                    })
+                                }
+                            }
+                            for axis_column in [&scatter.x_column, &scatter.y_column] {
+                                if !column_types.get(axis_column).unwrap().is_numeric() {
+                                    bail!(ConfigError::NonNumericScatterColumn {
+                                        column: axis_column.to_string(),
+                                        view: name.to_string()
+                                    })
+                                }
+                            }
+                            if scatter
+                                .opacity
+                                .is_some_and(|opacity| !(0.0..=1.0).contains(&opacity))
+                            {
+                                bail!(ConfigError::InvalidScatterOpacity {
+                                    column: column.to_string(),
+                                    view: name.to_string()
+                                })
+                            }
+                        }
+                        if (render_columns.boxplot || render_columns.violin)
+                            && titles.contains(column)
+                            && !column_types.get(column).unwrap().is_numeric()
+                        {
+                            bail!(ConfigError::NonNumericDistributionPlotColumn {
+                                column: column.to_string(),
+                                view: name.to_string()
+                            })
+                        }
                         if titles.contains(column) {
                             let mut possible_conflicting = Vec::new();
                             if render_columns.ellipsis.is_some() {
@@ -132,6 +673,9 @@ impl ItemsSpec {
                             if render_columns.link_to_url.is_some() {
                                 possible_conflicting.push("link-to-url".to_string());
                             }
+                            if render_columns.link_to_table.is_some() {
+                                possible_conflicting.push("link-to-table".to_string());
+                            }
                             if render_columns.custom.is_some() {
                                 possible_conflicting.push("custom".to_string());
                             }
@@ -248,6 +792,7 @@ impl ItemsSpec {
                                     let mut reader = dataset.reader()?;
                                     let colum_pos = column_position(column, dataset)?;
                                     for record in reader.records()? {
+                                        let record = record?;
                                         let value = record.get(colum_pos).unwrap();
                                         if let Ok(value) = value.parse::<f32>() {
                                             if (value < domain[0]
@@ -337,18 +882,91 @@ impl ItemsSpec {
                 }
             }
         }
+        if let Some(views) = self.circular_table_link() {
+            bail!(ConfigError::CircularTableLink { views })
+        }
         Ok(())
     }
+
+    /// Checks that the view linked by the given column exists and contains the linked column.
+    fn validate_table_link(
+        &self,
+        column: &str,
+        render_columns: &RenderColumnSpec,
+        linked_view: &str,
+    ) -> Result<()> {
+        let link = format!("link-to-table of column {column}");
+        let dataset = self
+            .views
+            .get(linked_view)
+            .and_then(|view| view.dataset.as_ref())
+            .and_then(|dataset| self.datasets.get(dataset));
+        match dataset {
+            Some(dataset) => {
+                let linked_column = render_columns.link_column.as_deref().unwrap_or(column);
+                if !dataset
+                    .reader()?
+                    .headers()?
+                    .iter()
+                    .any(|c| c == linked_column)
+                {
+                    bail!(ConfigError::LinkToMissingColumn {
+                        view: linked_view.to_string(),
+                        column: linked_column.to_string(),
+                        link,
+                    })
+                }
+                Ok(())
+            }
+            None => bail!(LinkToMissingView {
+                view: linked_view.to_string(),
+                link,
+            }),
+        }
+    }
+
+    /// Returns the views forming a cycle of cross-table links between columns, if any.
+    fn circular_table_link(&self) -> Option<Vec<String>> {
+        let mut finished = HashSet::new();
+        self.views
+            .keys()
+            .sorted()
+            .find_map(|view| self.find_table_link_cycle(view, &mut Vec::new(), &mut finished))
+    }
+
+    /// Depth-first search for a cycle of cross-table links reachable from the given view.
+    fn find_table_link_cycle(
+        &self,
+        view: &str,
+        path: &mut Vec<String>,
+        finished: &mut HashSet<String>,
+    ) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|v| v == view) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(view.to_string());
+            return Some(cycle);
+        }
+        if finished.contains(view) {
+            return None;
+        }
+        path.push(view.to_string());
+        if let Some(spec) = self.views.get(view) {
+            for linked_view in spec.linked_views() {
+                if let Some(cycle) = self.find_table_link_cycle(&linked_view, path, finished) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+        finished.insert(view.to_string());
+        None
+    }
 }
 
 fn default_single_page_threshold() -> usize {
     20000_usize
 }
 
-fn default_separator() -> char {
-    char::from_str(",").unwrap()
-}
-
 fn default_page_size() -> usize {
     20
 }
@@ -357,77 +975,753 @@ fn default_header_size() -> usize {
     1_usize
 }
 
+fn default_header() -> bool {
+    true
+}
+
+fn default_double_quote() -> bool {
+    true
+}
+
+fn default_filterable() -> bool {
+    true
+}
+
+fn default_range_filter() -> bool {
+    true
+}
+
+/// Escapes and names that can be given instead of a separator character.
+const SEPARATOR_ALIASES: [(&str, char); 7] = [
+    ("\\t", '\t'),
+    ("\\s", ' '),
+    ("tab", '\t'),
+    ("space", ' '),
+    ("comma", ','),
+    ("semicolon", ';'),
+    ("pipe", '|'),
+];
+
+fn deserialize_separator<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<char>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|separator| parse_separator(&separator).map_err(D::Error::custom))
+        .transpose()
+}
+
+/// Parses a separator given as a single ASCII character, an escape or a name.
+pub(crate) fn parse_separator(separator: &str) -> Result<char, String> {
+    if let Some((_, character)) = SEPARATOR_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(separator))
+    {
+        return Ok(*character);
+    }
+    let mut characters = separator.chars();
+    match (characters.next(), characters.next()) {
+        (Some(character), None) if character.is_ascii() => Ok(character),
+        _ => Err(format!(
+            "invalid separator {separator:?}, expected a single ASCII character, one of the \
+            escapes \\t or \\s or one of the names {}",
+            SEPARATOR_ALIASES
+                .iter()
+                .skip(2)
+                .map(|(name, _)| *name)
+                .join(", ")
+        )),
+    }
+}
+
 fn default_render_table() -> Option<RenderTableSpecs> {
     Some(RenderTableSpecs {
         columns: HashMap::from([]),
         additional_columns: None,
         headers: None,
+        include_columns: None,
+        exclude_columns: vec![],
+        filter: None,
+        computed_columns: IndexMap::new(),
+        correlation_matrix: false,
+        sample: None,
+        seed: None,
+        missing_threshold: default_missing_threshold(),
+        row_color_rules: vec![],
+        embed_full_csv: false,
+        default_sort_column: None,
+        default_sort_direction: SortDirection::Asc,
+        sort_keys: Vec::new(),
+        number_format: None,
+        na_values: None,
+        min_numeric_fraction: None,
+        max_categories: None,
     })
 }
 
+fn default_missing_threshold() -> f64 {
+    0.001
+}
+
 fn default_links() -> Option<HashMap<String, LinkSpec>> {
     Some(HashMap::new())
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct DatasetSpecs {
+    #[serde(default)]
     pub(crate) path: PathBuf,
-    #[serde(default = "default_separator")]
-    pub(crate) separator: char,
+    /// Records of small tables given directly in the config instead of a path.
+    #[serde(default)]
+    pub(crate) data: Option<InlineData>,
+    /// Separator of delimited files, given as a single ASCII character, an escape like `\t`
+    /// or a name like `tab`. Detected from the file if not given.
+    #[serde(default, deserialize_with = "deserialize_separator")]
+    #[schemars(with = "Option<String>")]
+    pub(crate) separator: Option<char>,
     #[serde(default = "default_header_size", rename = "headers")]
     pub(crate) header_rows: usize,
     #[serde(default = "default_links")]
     pub(crate) links: Option<HashMap<String, LinkSpec>>,
     #[serde(default)]
     pub(crate) offer_excel: bool,
-}
-
-impl DatasetSpecs {
-    pub(crate) fn size(&self) -> Result<usize> {
-        Ok(self.reader()?.records()?.count() - (self.header_rows - 1))
-    }
-
-    pub(crate) fn is_empty(&self) -> Result<bool> {
-        Ok(self.size()? == 0)
-    }
-
-    pub(crate) fn reader(&self) -> Result<readervzrd::FileReader> {
-        let path = &self
-            .path
-            .to_str()
-            .ok_or(anyhow!("Failed to create dataset reader."))?;
-        let reader = readervzrd::FileReader::new(path, Some(self.separator))?;
-        Ok(reader)
-    }
-
-    /// Returns a hashmap counting the number of unique values of all columns of the dataset
-    pub(crate) fn unique_column_values(&self) -> Result<HashMap<String, usize>> {
-        let mut reader = self.reader()?;
-        let headers = reader.headers()?.clone();
-        let column_counts: HashMap<String, usize> = headers
-            .iter()
-            .enumerate()
-            .map(|(index, column)| {
-                let mut reader = self.reader().unwrap();
-                (
-                    column.to_string(),
-                    reader
-                        .records()
-                        .unwrap()
-                        .map(|row| row.get(index).unwrap().to_string())
-                        .unique()
-                        .collect_vec()
-                        .len(),
-                )
-            })
-            .collect();
+    #[serde(default)]
+    pub(crate) date_columns: Vec<String>,
+    #[serde(default)]
+    pub(crate) table: Option<String>,
+    #[serde(default)]
+    pub(crate) query: Option<String>,
+    /// Whether the first line of delimited files and spreadsheets contains the column names.
+    /// If not, the columns are named column_1 to column_n unless column names are given.
+    #[serde(default = "default_header")]
+    pub(crate) header: bool,
+    /// Column names of delimited files and spreadsheets without a header row. When given, the
+    /// first line is treated as a record and split by the (given or detected) separator.
+    #[serde(default)]
+    pub(crate) column_names: Option<Vec<String>>,
+    /// Worksheet of spreadsheets to read, given by name or zero-based index. Defaults to the
+    /// first worksheet.
+    #[serde(default)]
+    pub(crate) sheet: Option<Sheet>,
+    /// Number of leading lines of delimited files to skip before the header row.
+    #[serde(default)]
+    pub(crate) skip_rows: usize,
+    /// Lines of delimited files starting with this character are ignored.
+    #[serde(default)]
+    pub(crate) comment_char: Option<char>,
+    /// Whether to display the skipped lines and comments above the table.
+    #[serde(default)]
+    pub(crate) show_skipped_lines: bool,
+    /// Quote character of delimited files. Defaults to `"`.
+    #[serde(default)]
+    pub(crate) quote: Option<String>,
+    /// Escape character for quotes inside quoted fields of delimited files.
+    #[serde(default)]
+    pub(crate) escape: Option<String>,
+    /// Whether two consecutive quote characters inside quoted fields denote a single quote.
+    #[serde(default = "default_double_quote")]
+    pub(crate) double_quote: bool,
+    /// Whether records of delimited files may have missing trailing fields, which are then
+    /// read as empty strings.
+    #[serde(default)]
+    pub(crate) flexible: bool,
+    /// Whether to number repeated column names from their second occurrence on, e.g. as
+    /// `score_2`, instead of failing. Settings of such columns have to refer to them by their
+    /// new names or by index.
+    #[serde(default)]
+    pub(crate) dedup_headers: bool,
+    /// Encoding of delimited files, e.g. latin1 or utf-16le. Defaults to UTF-8.
+    #[serde(default)]
+    pub(crate) encoding: Option<String>,
+    /// Whether to add a column holding the originating file of each record when the path is
+    /// a glob pattern matching multiple tables.
+    #[serde(default)]
+    pub(crate) source_column: bool,
+    /// Number of leading rows from which the types of the columns are inferred, or `all`.
+    /// Defaults to 10000 rows.
+    #[serde(default)]
+    pub(crate) classify_rows: ClassifyRows,
+    /// Number of records with missing trailing fields, counted during preprocessing.
+    #[serde(skip)]
+    pub(crate) padded_rows: usize,
+    /// Columns that readers are restricted to, as selected by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) selected_columns: Option<Vec<String>>,
+    /// Expression that rows have to satisfy, as given by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) row_filter: Option<FilterExpression>,
+    /// Columns appended to each record, as defined by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) computed_columns: Vec<ComputedColumn>,
+    /// Random sample of the records that readers are restricted to, as drawn for rendering the
+    /// pages of a view.
+    #[serde(skip)]
+    pub(crate) sample: Option<RowSample>,
+    /// Number formats of single columns, as configured by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) number_formats: HashMap<String, NumberFormat>,
+    /// Number format of all other columns, as configured by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) default_number_format: Option<NumberFormat>,
+    /// Columns whose values are stripped before parsing them as numbers, as configured by the
+    /// view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) stripped_columns: HashMap<String, StrippedColumn>,
+    /// Column types overriding the inferred ones, as configured by the view rendering the
+    /// dataset.
+    #[serde(skip)]
+    pub(crate) column_types: HashMap<String, ColumnType>,
+    /// Column types reported by the original format of tables converted to csv during
+    /// preprocessing, e.g. the schema of a parquet file.
+    #[serde(skip)]
+    pub(crate) reported_column_types: HashMap<String, ColumnType>,
+    /// Tokens denoting missing values in single columns, as configured by the view rendering
+    /// the dataset.
+    #[serde(skip)]
+    pub(crate) na_values: HashMap<String, Vec<String>>,
+    /// Tokens denoting missing values in all other columns, as configured by the view rendering
+    /// the dataset.
+    #[serde(skip)]
+    pub(crate) default_na_values: Option<Vec<String>>,
+    /// Fractions of numbers above which single columns are numeric despite other values, as
+    /// configured by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) min_numeric_fractions: HashMap<String, f64>,
+    /// Fraction of numbers above which all other columns are numeric despite other values, as
+    /// configured by the view rendering the dataset.
+    #[serde(skip)]
+    pub(crate) default_min_numeric_fraction: Option<f64>,
+}
+
+/// Uniform random sample of the records of a dataset, which is reproducible for a given seed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RowSample {
+    pub(crate) size: usize,
+    pub(crate) seed: u64,
+}
+
+/// Formats the given repeated column names and their indices like `"score" at indices 1 and 3`.
+fn format_duplicate_headers(duplicates: &[(String, Vec<usize>)]) -> String {
+    duplicates
+        .iter()
+        .map(|(column, indices)| {
+            let (last, others) = indices.split_last().unwrap();
+            format!(
+                "{column:?} at indices {} and {last}",
+                others.iter().join(", ")
+            )
+        })
+        .join(", ")
+}
+
+/// Column by which the rows of a table are sorted, and the direction.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(
+    rename_all(serialize = "kebab-case", deserialize = "kebab-case"),
+    deny_unknown_fields
+)]
+pub(crate) struct SortKey {
+    pub(crate) column: String,
+    #[serde(default)]
+    pub(crate) direction: SortDirection,
+    /// Values of the column in the order they are sorted in, e.g. severity levels, as given by
+    /// the nominal sort of the column. Other values follow in alphabetical order.
+    #[serde(skip)]
+    pub(crate) order: Vec<String>,
+    /// Whether values are compared by their numbers, as configured for the column.
+    #[serde(skip)]
+    pub(crate) natural: bool,
+}
+
+/// Direction in which the rows of a table are sorted.
+#[derive(Default, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl DatasetSpecs {
+    pub(crate) fn size(&self) -> Result<usize> {
+        Ok(self.reader()?.records()?.count() - (self.header_rows - 1))
+    }
+
+    pub(crate) fn is_empty(&self) -> Result<bool> {
+        Ok(self.size()? == 0)
+    }
+
+    /// Returns the number format of the given column as configured by the view rendering the
+    /// dataset, if any.
+    pub(crate) fn number_format(&self, column: &str) -> Option<&NumberFormat> {
+        self.number_formats
+            .get(column)
+            .or(self.default_number_format.as_ref())
+    }
+
+    /// Returns the tokens denoting missing values in the given column in addition to empty
+    /// values.
+    pub(crate) fn na_values(&self, column: &str) -> Vec<String> {
+        match self.configured_na_values(column) {
+            Some(na_values) => na_values.to_vec(),
+            None => DEFAULT_NA_VALUES.map(String::from).to_vec(),
+        }
+    }
+
+    /// Returns true if the given value of the given column is empty or one of the tokens
+    /// denoting missing values.
+    pub(crate) fn is_na(&self, column: &str, value: &str) -> bool {
+        value.is_empty()
+            || match self.configured_na_values(column) {
+                Some(na_values) => na_values.iter().any(|na_value| na_value == value),
+                None => DEFAULT_NA_VALUES.contains(&value),
+            }
+    }
+
+    fn configured_na_values(&self, column: &str) -> Option<&[String]> {
+        self.na_values
+            .get(column)
+            .or(self.default_na_values.as_ref())
+            .map(Vec::as_slice)
+    }
+
+    /// Returns the fraction of numbers above which the given column without stripped values is
+    /// numeric despite other values, if configured.
+    pub(crate) fn min_numeric_fraction(&self, column: &str) -> Option<f64> {
+        self.min_numeric_fractions
+            .get(column)
+            .copied()
+            .or(self.default_min_numeric_fraction)
+    }
+
+    /// Returns what to strip from the values of the given column before parsing them as
+    /// numbers, if anything.
+    pub(crate) fn strip(&self, column: &str) -> Option<&Strip> {
+        self.stripped_columns
+            .get(column)
+            .map(|stripped| &stripped.strip)
+    }
+
+    /// Returns the number of rows from which the column types are inferred if the dataset has
+    /// more rows than that.
+    pub(crate) fn classified_rows(&self) -> Result<Option<usize>> {
+        let Some(limit) = self.classify_rows.limit() else {
+            return Ok(None);
+        };
+        let mut reader = self.reader()?;
+        let has_more_rows = reader
+            .records()?
+            .nth(limit + self.header_rows - 1)
+            .is_some();
+        Ok(has_more_rows.then_some(limit))
+    }
+
+    /// Returns true if the given column is not excluded by the view rendering the dataset.
+    pub(crate) fn is_selected(&self, column: &str) -> bool {
+        self.selected_columns
+            .as_ref()
+            .is_none_or(|columns| columns.iter().any(|selected| selected == column))
+    }
+
+    pub(crate) fn reader(&self) -> Result<TableReader> {
+        let mut reader = open_table_reader(self)?;
+        let headers = reader.headers()?;
+        let duplicates = duplicate_headers(&headers);
+        if !duplicates.is_empty() {
+            if !self.dedup_headers {
+                bail!(ConfigError::DuplicateHeader {
+                    duplicates: format_duplicate_headers(&duplicates),
+                    path: self.path.to_owned()
+                })
+            }
+            reader = reader.rename(dedup_headers(&headers));
+        }
+        if !self.computed_columns.is_empty() {
+            // Computed columns are appended first so that they can be filtered and selected.
+            reader = reader.compute(&self.computed_columns, self.header_rows - 1)?;
+        }
+        if let Some(row_filter) = &self.row_filter {
+            let row_filter = row_filter.bind(&reader.headers()?)?;
+            // Additional header rows are never filtered.
+            reader = reader.filter(row_filter, self.header_rows - 1);
+        }
+        if let Some(sample) = &self.sample {
+            reader = reader.sample(sample.size, sample.seed, self.header_rows - 1);
+        }
+        match &self.selected_columns {
+            Some(columns) => reader.select(columns),
+            None => Ok(reader),
+        }
+    }
+
+    /// Returns a dataset reading the records of this one sorted by the given keys from a
+    /// temporary csv file, so that the table is sorted once instead of every time it is read.
+    /// Filters, computed columns, samples and selected columns are applied before sorting.
+    pub(crate) fn sorted(&self, sort_keys: &[SortKey]) -> Result<DatasetSpecs> {
+        let mut reader = self.reader()?;
+        let headers = reader.headers()?;
+        let records = self.sort_records(&headers, reader.records()?, sort_keys)?;
+        let path = write_temp_csv("datavzrd-sorted-", &headers, records.into_iter().map(Ok))
+            .context(format!("Failed to sort table {:?}.", self.path))?;
+        let mut sorted = DatasetSpecs {
+            row_filter: None,
+            computed_columns: Vec::new(),
+            sample: None,
+            selected_columns: None,
+            ..self.clone()
+        };
+        sorted.read_as_plain_csv(path);
+        Ok(sorted)
+    }
+
+    /// Sorts the given records with the given headers by the given keys, keeping additional
+    /// header rows first. Later keys only break ties of earlier ones. Values are compared by
+    /// their position in the order of the key if any, numbers numerically and before other
+    /// values. Missing values come last in both directions and ties of all keys keep their
+    /// order.
+    fn sort_records(
+        &self,
+        headers: &[String],
+        records: impl Iterator<Item = Result<Vec<String>>>,
+        sort_keys: &[SortKey],
+    ) -> Result<Vec<Vec<String>>> {
+        let indices = sort_keys
+            .iter()
+            .map(
+                |key| match headers.iter().position(|header| header == &key.column) {
+                    Some(index) => Ok(index),
+                    None => Err(ConfigError::MissingSortColumn {
+                        column: key.column.to_owned(),
+                        path: self.path.clone(),
+                    }),
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut records = records.collect::<Result<Vec<_>>>()?;
+        let rows = records.split_off((self.header_rows - 1).min(records.len()));
+        let mut keyed = rows
+            .into_iter()
+            .map(|record| {
+                let values = sort_keys
+                    .iter()
+                    .zip(&indices)
+                    .map(|(key, index)| {
+                        let column = &key.column;
+                        let value = record.get(*index).map_or("", String::as_str);
+                        let missing = value.is_empty() || self.is_na(column, value);
+                        // Values are ranked by the order and compared as text beyond it.
+                        if !key.order.is_empty() {
+                            let rank = key.order.iter().position(|ordered| ordered == value);
+                            return (missing, rank.unwrap_or(key.order.len()), None);
+                        }
+                        let number =
+                            parse_number(value, self.number_format(column), self.strip(column));
+                        (missing, 0, number)
+                    })
+                    .collect_vec();
+                (values, record)
+            })
+            .collect_vec();
+        keyed.sort_by(|(values_a, a), (values_b, b)| {
+            sort_keys
+                .iter()
+                .zip(&indices)
+                .zip(values_a.iter().zip(values_b))
+                .map(
+                    |(
+                        (key, index),
+                        ((missing_a, rank_a, number_a), (missing_b, rank_b, number_b)),
+                    )| {
+                        if *missing_a || *missing_b {
+                            return missing_a.cmp(missing_b);
+                        }
+                        let ordering =
+                            rank_a.cmp(rank_b).then_with(|| match (number_a, number_b) {
+                                (Some(a), Some(b)) => a.total_cmp(b),
+                                (Some(_), None) => Ordering::Less,
+                                (None, Some(_)) => Ordering::Greater,
+                                (None, None) if key.natural => {
+                                    natural_compare(&a[*index], &b[*index])
+                                }
+                                (None, None) => a[*index].cmp(&b[*index]),
+                            });
+                        match key.direction {
+                            SortDirection::Asc => ordering,
+                            SortDirection::Desc => ordering.reverse(),
+                        }
+                    },
+                )
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        records.extend(keyed.into_iter().map(|(_, record)| record));
+        Ok(records)
+    }
+
+    /// Returns the number of computed values that are undefined, e.g. due to a division by
+    /// zero, and hence rendered as empty cells.
+    pub(crate) fn invalid_computed_values(&self) -> Result<usize> {
+        if self.computed_columns.is_empty() {
+            return Ok(0);
+        }
+        let mut reader = open_table_reader(self)?;
+        let columns = BoundComputedColumns::bind(&self.computed_columns, &reader.headers()?)?;
+        let invalid_values = reader
+            .records()?
+            .skip(self.header_rows - 1)
+            .map_ok(|mut record| columns.append(&mut record))
+            .sum::<Result<usize>>()?;
+        Ok(invalid_values)
+    }
+
+    /// Returns the configured encoding of the dataset, if it is known.
+    pub(crate) fn encoding(&self) -> Option<&'static Encoding> {
+        self.encoding
+            .as_ref()
+            .and_then(|label| Encoding::for_label(label.as_bytes()))
+    }
+
+    /// Returns the configured separator or detects it from the file if none is given.
+    pub(crate) fn separator(&self) -> Result<char> {
+        match self.separator {
+            Some(separator) => Ok(separator),
+            None => detect_separator(self),
+        }
+    }
+
+    /// Returns a dataset read from the given path with the default settings, e.g. for inspecting
+    /// a table without a config.
+    pub(crate) fn from_path(
+        path: &Path,
+        separator: Option<char>,
+        download_settings: &DownloadSettings,
+    ) -> Result<DatasetSpecs> {
+        let mut dataset: DatasetSpecs = serde_yaml::from_str("{}")?;
+        dataset.path = path.to_path_buf();
+        dataset.separator = separator;
+        dataset.preprocess(&path.to_string_lossy(), download_settings)?;
+        Ok(dataset)
+    }
+
+    /// Buffers datasets read from stdin or downloaded from URLs and detects missing separators
+    /// of delimited files once so that later reads can reuse them.
+    fn preprocess(&mut self, name: &str, download_settings: &DownloadSettings) -> Result<()> {
+        if let Some(data) = &self.data {
+            if !self.path.as_os_str().is_empty() {
+                bail!(ConfigError::PathAndInlineData {
+                    dataset: name.to_string()
+                })
+            }
+            if data.is_empty() {
+                bail!(ConfigError::EmptyInlineData {
+                    dataset: name.to_string()
+                })
+            }
+            self.path = write_inline_table(data)?;
+            self.separator = Some(',');
+            if let InlineData::Records(_) = data {
+                // The column names are written as the first line.
+                self.header = true;
+                self.column_names = None;
+            }
+        } else if self.path.as_os_str().is_empty() {
+            bail!(ConfigError::MissingDatasetPath {
+                dataset: name.to_string()
+            })
+        }
+        if self.path == Path::new(STDIN_PATH) {
+            self.path = buffer_stdin()?;
+        } else if let Some(url) = remote_url(&self.path) {
+            self.path = fetch_dataset(name, &url, download_settings)?;
+        } else if let Some(paths) = glob_paths(&self.path)? {
+            return self.concatenate(name, paths, download_settings);
+        } else if !self.path.exists() {
+            bail!(ConfigError::DatasetPathNotFound {
+                dataset: name.to_string(),
+                path: self.path.to_owned()
+            })
+        }
+        // Decompress once instead of every time the table is read.
+        if let Some(path) = decompress_to_temp_file(&self.path)? {
+            self.path = path;
+        }
+        if self
+            .comment_char
+            .is_some_and(|comment_char| !comment_char.is_ascii())
+        {
+            bail!(ConfigError::NonAsciiCommentChar {
+                dataset: name.to_string(),
+                comment_char: self.comment_char.unwrap()
+            })
+        }
+        self.validate_quoting(name)?;
+        if let (Some(encoding), None) = (&self.encoding, self.encoding()) {
+            bail!(ConfigError::UnknownEncoding {
+                dataset: name.to_string(),
+                encoding: encoding.to_string()
+            })
+        }
+        if self.encoding.is_none() && is_delimited(&self.path) {
+            // Files starting with a UTF-16 byte order mark are decoded without further ado.
+            self.encoding = utf16_encoding(&self.path).map(String::from);
+        }
+        if self.separator.is_none() && is_delimited(&self.path) {
+            self.separator = Some(detect_separator(self)?);
+        }
+        if !self.header && self.column_names.is_none() {
+            // The first line still tells the number of columns.
+            let columns = self.reader()?.headers()?.len();
+            self.column_names = Some((1..=columns).map(|i| format!("column_{i}")).collect());
+        }
+        if is_delimited(&self.path) {
+            self.padded_rows = check_record_lengths(self)?;
+        }
+        if let Some(column_names) = &self.column_names {
+            if let Some(record) = self.reader()?.records()?.next() {
+                let record = record?;
+                if record.len() != column_names.len() {
+                    bail!(ConfigError::ColumnNamesLengthMismatch {
+                        dataset: name.to_string(),
+                        expected: record.len(),
+                        found: column_names.len()
+                    })
+                }
+            }
+        }
+        // Convert other formats once instead of parsing them every time the table is read.
+        if let Some((path, column_types)) = convert_to_temp_file(self)? {
+            self.read_as_plain_csv(path);
+            self.reported_column_types = column_types;
+        }
+        Ok(())
+    }
+
+    /// Replaces the table of the dataset by the given csv file with a header row, resetting all
+    /// options that only apply to reading the original table.
+    fn read_as_plain_csv(&mut self, path: PathBuf) {
+        self.path = path;
+        self.separator = Some(',');
+        self.header = true;
+        self.column_names = None;
+        self.sheet = None;
+        self.table = None;
+        self.query = None;
+        self.skip_rows = 0;
+        self.comment_char = None;
+        self.quote = None;
+        self.escape = None;
+        self.double_quote = true;
+        self.flexible = false;
+        self.encoding = None;
+    }
+
+    /// Preprocesses the tables matched by a glob pattern individually and replaces them by
+    /// their concatenation, which is a plain csv file.
+    fn concatenate(
+        &mut self,
+        name: &str,
+        paths: Vec<PathBuf>,
+        download_settings: &DownloadSettings,
+    ) -> Result<()> {
+        let datasets = paths
+            .into_iter()
+            .map(|path| {
+                let mut dataset = DatasetSpecs {
+                    path,
+                    ..self.clone()
+                };
+                dataset.preprocess(name, download_settings)?;
+                Ok(dataset)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.padded_rows = datasets.iter().map(|dataset| dataset.padded_rows).sum();
+        self.read_as_plain_csv(concatenate_tables(&datasets, self.source_column)?);
+        Ok(())
+    }
+
+    /// Checks that the quote and escape characters are single ASCII characters that differ
+    /// from the separator.
+    fn validate_quoting(&self, name: &str) -> Result<()> {
+        for (option, value) in [("quote", &self.quote), ("escape", &self.escape)] {
+            if let Some(value) = value {
+                if value.chars().count() != 1 || !value.is_ascii() {
+                    bail!(ConfigError::InvalidQuotingCharacter {
+                        dataset: name.to_string(),
+                        option: option.to_string(),
+                        value: value.to_string()
+                    })
+                }
+                if self
+                    .separator
+                    .is_some_and(|separator| value.starts_with(separator))
+                {
+                    bail!(ConfigError::QuotingCharacterIsSeparator {
+                        dataset: name.to_string(),
+                        option: option.to_string()
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a hashmap counting the number of unique values of all columns of the dataset
+    pub(crate) fn unique_column_values(&self) -> Result<HashMap<String, usize>> {
+        let mut reader = self.reader()?;
+        let headers = reader.headers()?.clone();
+        let column_counts: HashMap<String, usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let mut reader = self.reader()?;
+                let values = reader.records()?.process_results(|records| {
+                    records
+                        .map(|row| row.get(index).unwrap().to_string())
+                        .unique()
+                        .count()
+                })?;
+                Ok((column.to_string(), values))
+            })
+            .collect::<Result<_>>()?;
 
         Ok(column_counts)
     }
+
+    /// Returns the distinct values of the given columns in a single pass over the dataset,
+    /// omitting columns with more distinct values than their given maximum.
+    pub(crate) fn distinct_values(
+        &self,
+        max_values: &HashMap<String, usize>,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let mut reader = self.reader()?;
+        let headers = reader.headers()?;
+        let mut values: HashMap<usize, Option<HashSet<String>>> = headers
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| max_values.contains_key(*column))
+            .map(|(index, _)| (index, Some(HashSet::new())))
+            .collect();
+        for record in reader.records()?.skip(self.header_rows - 1) {
+            let record = record?;
+            for (index, column_values) in values.iter_mut() {
+                if let Some(distinct) = column_values {
+                    distinct.insert(record[*index].to_owned());
+                    if distinct.len() > max_values[&headers[*index]] {
+                        *column_values = None;
+                    }
+                }
+            }
+        }
+        Ok(values
+            .into_iter()
+            .filter_map(|(index, distinct)| {
+                distinct.map(|distinct| (headers[index].to_owned(), distinct.into_iter().collect()))
+            })
+            .collect())
+    }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct ItemSpecs {
     #[serde(default)]
@@ -487,7 +1781,7 @@ impl ItemSpecs {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct RenderTableSpecs {
     #[serde(default)]
@@ -496,49 +1790,339 @@ pub(crate) struct RenderTableSpecs {
     pub(crate) additional_columns: Option<HashMap<String, AdditionalColumnSpec>>,
     #[serde(default)]
     pub(crate) headers: Option<HashMap<u32, HeaderSpecs>>,
-}
-
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
-pub(crate) struct AdditionalColumnSpec {
-    #[serde(default = "default_value_function")]
-    pub(crate) value: String,
+    /// Names or glob patterns of the only columns to render.
     #[serde(default)]
-    pub(crate) display_mode: DisplayMode,
+    pub(crate) include_columns: Option<Vec<String>>,
+    /// Names or glob patterns of columns to leave out.
     #[serde(default)]
-    pub(crate) custom_plot: Option<CustomPlot>,
+    pub(crate) exclude_columns: Vec<String>,
+    /// Expression that rows have to satisfy in order to be rendered, e.g.
+    /// `qual >= 30 && filter == "PASS"`.
     #[serde(default)]
-    pub(crate) link_to_url: Option<LinkToUrlSpec>,
-}
-
-fn default_value_function() -> String {
-    String::from("function(row) { return '' }")
-}
-
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
-pub(crate) struct HeaderSpecs {
+    pub(crate) filter: Option<String>,
+    /// Columns computed from the other columns of each record by an expression, e.g.
+    /// `af: alt_count / depth` or `label: concat(chrom, ":", pos)`.
     #[serde(default)]
-    pub(crate) label: Option<String>,
+    pub(crate) computed_columns: IndexMap<String, String>,
+    /// Whether to offer a heatmap of the pairwise correlations of all numeric columns.
     #[serde(default)]
-    pub(crate) plot: Option<PlotSpec>,
+    pub(crate) correlation_matrix: bool,
+    /// Number of randomly sampled rows to render instead of all rows. Plots are still based on
+    /// all rows.
     #[serde(default)]
-    pub(crate) display_mode: HeaderDisplayMode,
+    pub(crate) sample: Option<usize>,
+    /// Seed of the random sampling of rows, making the sample reproducible.
     #[serde(default)]
-    pub(crate) ellipsis: Option<u32>,
+    pub(crate) seed: Option<u64>,
+    /// Fraction of missing values above which a column header shows the percentage of missing
+    /// values.
+    #[serde(default = "default_missing_threshold")]
+    pub(crate) missing_threshold: f64,
+    /// Rules coloring the background of whole rows depending on the value of a column. The
+    /// first matching rule determines the color.
+    #[serde(default)]
+    pub(crate) row_color_rules: Vec<RowColorRule>,
+    /// Whether each page of a multi-page table offers all rows for download as CSV instead of
+    /// only the rows of the page. Note that this embeds the whole table into every page.
+    #[serde(default)]
+    pub(crate) embed_full_csv: bool,
+    /// Column by which the rows are sorted before they are split into pages, e.g. an adjusted
+    /// p-value. Numbers are compared numerically and missing values always come last. By
+    /// default, rows are rendered in the order of the dataset.
+    #[serde(default)]
+    pub(crate) default_sort_column: Option<String>,
+    /// Direction in which the rows are sorted by the default sort column, `asc` or `desc`.
+    #[serde(default)]
+    pub(crate) default_sort_direction: SortDirection,
+    /// Columns by which the rows are sorted before they are split into pages, each with a
+    /// direction, e.g. by p-value and then by gene name. Later keys only break ties of
+    /// earlier ones. A default sort column is a shorthand for a single key.
+    #[serde(default)]
+    pub(crate) sort_keys: Vec<SortKey>,
+    /// Decimal and thousands separators of the numbers in all columns without their own
+    /// number format.
+    #[serde(default)]
+    pub(crate) number_format: Option<NumberFormat>,
+    /// Tokens denoting missing values in all columns without their own ones, replacing the
+    /// default ones like `NA`.
+    #[serde(default)]
+    pub(crate) na_values: Option<Vec<String>>,
+    /// Minimal fraction of non-missing values that have to be numbers in order to treat a
+    /// column as numeric anyway. The other values are plotted as missing. By default, all
+    /// values have to be numbers.
+    #[serde(default)]
+    pub(crate) min_numeric_fraction: Option<f64>,
+    /// Number of values shown in the histograms of all nominal columns without their own
+    /// max-categories, or `all`.
+    #[serde(default)]
+    pub(crate) max_categories: Option<MaxCategories>,
 }
 
-lazy_static! {
-    static ref INDEX_RE: Regex = Regex::new(r"^index\(([0-9]+)\)$").unwrap();
-}
+impl RenderTableSpecs {
+    /// Returns the given dataset restricted to the rows matching the filter expression and to
+    /// the included and not excluded columns, or `None` if the whole dataset is rendered.
+    pub(crate) fn view_dataset(&self, dataset: &DatasetSpecs) -> Result<Option<DatasetSpecs>> {
+        let selects_columns = self.include_columns.is_some() || !self.exclude_columns.is_empty();
+        let number_formats: HashMap<_, _> = self
+            .columns
+            .iter()
+            .filter_map(|(column, spec)| Some((column.to_owned(), spec.number_format?)))
+            .collect();
+        let stripped_columns: HashMap<_, _> = self
+            .columns
+            .iter()
+            .filter_map(|(column, spec)| {
+                let stripped = StrippedColumn {
+                    strip: spec.strip.clone()?,
+                    min_fraction: spec
+                        .min_numeric_fraction
+                        .or(self.min_numeric_fraction)
+                        .unwrap_or(MIN_NUMERIC_FRACTION),
+                };
+                Some((column.to_owned(), stripped))
+            })
+            .collect();
+        let column_types: HashMap<_, _> = self
+            .columns
+            .iter()
+            .filter_map(|(column, spec)| Some((column.to_owned(), spec.column_type?)))
+            .collect();
+        let min_numeric_fractions: HashMap<_, _> = self
+            .columns
+            .iter()
+            .filter(|(_, spec)| spec.strip.is_none())
+            .filter_map(|(column, spec)| Some((column.to_owned(), spec.min_numeric_fraction?)))
+            .collect();
+        let na_values: HashMap<_, _> = self
+            .columns
+            .iter()
+            .filter_map(|(column, spec)| Some((column.to_owned(), spec.na_values.clone()?)))
+            .collect();
+        if self.filter.is_none()
+            && !selects_columns
+            && self.computed_columns.is_empty()
+            && number_formats.is_empty()
+            && self.number_format.is_none()
+            && stripped_columns.is_empty()
+            && column_types.is_empty()
+            && na_values.is_empty()
+            && self.na_values.is_none()
+            && min_numeric_fractions.is_empty()
+            && self.min_numeric_fraction.is_none()
+        {
+            return Ok(None);
+        }
+        let dataset = DatasetSpecs {
+            computed_columns: self.computed_columns()?,
+            number_formats,
+            default_number_format: self.number_format,
+            stripped_columns,
+            column_types,
+            na_values,
+            default_na_values: self.na_values.clone(),
+            min_numeric_fractions,
+            default_min_numeric_fraction: self.min_numeric_fraction,
+            ..dataset.clone()
+        };
+        let matches_any = |filters: &[String], column: &str| {
+            filters
+                .iter()
+                .any(|filter| matches_column_filter(filter, column))
+        };
+        let selected_columns = if selects_columns {
+            Some(
+                dataset
+                    .reader()?
+                    .headers()?
+                    .into_iter()
+                    .filter(|column| {
+                        self.include_columns
+                            .as_ref()
+                            .is_none_or(|include| matches_any(include, column))
+                            && !matches_any(&self.exclude_columns, column)
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        Ok(Some(DatasetSpecs {
+            row_filter: self
+                .filter
+                .as_deref()
+                .map(FilterExpression::from_str)
+                .transpose()?,
+            selected_columns,
+            ..dataset
+        }))
+    }
 
-lazy_static! {
-    static ref REGEX_RE: Regex = Regex::new(r#"^regex\((?:'|")(.+)(?:'|")\)$"#).unwrap();
-}
+    /// Checks that the computed columns of the given view are valid and do not collide with
+    /// existing columns. Returns the dataset of the view including its computed columns.
+    pub(crate) fn computed_dataset(
+        &self,
+        view: &str,
+        dataset: &DatasetSpecs,
+    ) -> Result<DatasetSpecs> {
+        if self.computed_columns.is_empty() {
+            return Ok(dataset.clone());
+        }
+        let headers = dataset.reader()?.headers()?;
+        let additional_columns = self.additional_columns.iter().flatten();
+        for column in self.computed_columns.keys() {
+            if headers.contains(column)
+                || additional_columns.clone().any(|(name, _)| name == column)
+            {
+                bail!(ConfigError::ComputedColumnCollision {
+                    column: column.to_string(),
+                    view: view.to_string()
+                })
+            }
+        }
+        let computed_columns = self.computed_columns().and_then(|computed_columns| {
+            BoundComputedColumns::bind(&computed_columns, &headers)?;
+            Ok(computed_columns)
+        });
+        match computed_columns {
+            Ok(computed_columns) => Ok(DatasetSpecs {
+                computed_columns,
+                ..dataset.clone()
+            }),
+            Err(error) => bail!(ConfigError::InvalidComputedColumn {
+                view: view.to_string(),
+                reason: error.to_string()
+            }),
+        }
+    }
 
-lazy_static! {
-    static ref RANGE_RE: Regex = Regex::new(r"^range\(([0-9]+,[0-9]+)\)$").unwrap();
-}
+    /// Returns the given dataset with the given number of records restricted to the configured
+    /// random sample of rows, or `None` if it has no more records than the sample size.
+    pub(crate) fn sampled_dataset(
+        &self,
+        dataset: &DatasetSpecs,
+        records: usize,
+    ) -> Option<DatasetSpecs> {
+        let size = self.sample.filter(|size| *size < records)?;
+        Some(DatasetSpecs {
+            sample: Some(RowSample {
+                size,
+                // Without a given seed, the sample is still drawn identically by all readers.
+                seed: self.seed.unwrap_or_else(|| fastrand::u64(..)),
+            }),
+            ..dataset.clone()
+        })
+    }
+
+    /// Returns the keys by which the rows are sorted, i.e. the default sort column followed by
+    /// the configured sort keys, with the custom orders of the nominal sorts of their columns.
+    pub(crate) fn sort_keys(&self) -> Vec<SortKey> {
+        self.default_sort_column
+            .iter()
+            .map(|column| SortKey {
+                column: column.to_owned(),
+                direction: self.default_sort_direction,
+                order: Vec::new(),
+                natural: false,
+            })
+            .chain(self.sort_keys.iter().cloned())
+            .map(|key| match self.columns.get(&key.column) {
+                Some(spec) => SortKey {
+                    order: match &spec.nominal_sort {
+                        NominalSort::Custom(order) => order.clone(),
+                        _ => Vec::new(),
+                    },
+                    natural: spec.natural_sort,
+                    ..key
+                },
+                None => key,
+            })
+            .collect()
+    }
+
+    /// Returns the given dataset with its records sorted by the sort keys, or `None` if no such
+    /// keys are configured.
+    pub(crate) fn sorted_dataset(&self, dataset: &DatasetSpecs) -> Result<Option<DatasetSpecs>> {
+        let sort_keys = self.sort_keys();
+        if sort_keys.is_empty() {
+            return Ok(None);
+        }
+        dataset.sorted(&sort_keys).map(Some)
+    }
+
+    /// Parses the expressions of the computed columns.
+    pub(crate) fn computed_columns(&self) -> Result<Vec<ComputedColumn>> {
+        self.computed_columns
+            .iter()
+            .map(|(name, expression)| {
+                Ok(ComputedColumn {
+                    name: name.to_owned(),
+                    expression: ValueExpression::from_str(expression)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the include and exclude filters that do not match any of the given columns.
+    fn unmatched_column_filters<'a>(&'a self, columns: &'a [String]) -> Vec<&'a String> {
+        self.include_columns
+            .iter()
+            .flatten()
+            .chain(&self.exclude_columns)
+            .filter(|filter| {
+                !columns
+                    .iter()
+                    .any(|column| matches_column_filter(filter, column))
+            })
+            .collect()
+    }
+}
+
+/// Returns true if the given column equals the given name or matches it as a glob pattern.
+fn matches_column_filter(filter: &str, column: &str) -> bool {
+    filter == column || glob::Pattern::new(filter).is_ok_and(|pattern| pattern.matches(column))
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct AdditionalColumnSpec {
+    #[serde(default = "default_value_function")]
+    pub(crate) value: String,
+    #[serde(default)]
+    pub(crate) display_mode: DisplayMode,
+    #[serde(default)]
+    pub(crate) custom_plot: Option<CustomPlot>,
+    #[serde(default)]
+    pub(crate) link_to_url: Option<LinkToUrlSpec>,
+}
+
+fn default_value_function() -> String {
+    String::from("function(row) { return '' }")
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct HeaderSpecs {
+    #[serde(default)]
+    pub(crate) label: Option<String>,
+    #[serde(default)]
+    pub(crate) plot: Option<PlotSpec>,
+    #[serde(default)]
+    pub(crate) display_mode: HeaderDisplayMode,
+    #[serde(default)]
+    pub(crate) ellipsis: Option<u32>,
+}
+
+lazy_static! {
+    static ref INDEX_RE: Regex = Regex::new(r"^index\(([0-9]+)\)$").unwrap();
+}
+
+lazy_static! {
+    static ref REGEX_RE: Regex = Regex::new(r#"^regex\((?:'|")(.+)(?:'|")\)$"#).unwrap();
+}
+
+lazy_static! {
+    static ref RANGE_RE: Regex = Regex::new(r"^range\(([0-9]+,[0-9]+)\)$").unwrap();
+}
 
 fn get_first_match_group(regex: &Regex, key: &str) -> String {
     regex
@@ -555,6 +2139,17 @@ fn get_first_match_group(regex: &Regex, key: &str) -> String {
 
 impl ItemSpecs {
     /// Preprocesses columns with index and regex notation
+    /// Returns the views that cells of this view link to via cross-table links.
+    fn linked_views(&self) -> Vec<String> {
+        self.render_table
+            .iter()
+            .flat_map(|render_table| render_table.columns.values())
+            .filter_map(|column| column.link_to_table.clone())
+            .sorted()
+            .dedup()
+            .collect_vec()
+    }
+
     fn preprocess_columns(
         &mut self,
         dataset: &DatasetSpecs,
@@ -568,6 +2163,8 @@ impl ItemSpecs {
             self.page_size = *rows;
         }
         let headers = dataset.reader()?.headers()?;
+        // Repeated column names of datasets numbering them are ambiguous.
+        let ambiguous = duplicate_headers(&open_table_reader(dataset)?.headers()?);
         if let Some(render_table) = self.render_table.borrow_mut() {
             for (title, render_column_specs) in render_table.columns.iter_mut() {
                 render_column_specs.preprocess(dataset, title)?;
@@ -626,6 +2223,11 @@ impl ItemSpecs {
                         })
                     }
                 }
+            } else if ambiguous.iter().any(|(column, _)| column == key) {
+                bail!(ConfigError::AmbiguousColumn {
+                    column: key.to_string(),
+                    table_path: dataset.path.clone(),
+                })
             } else if indexed_keys
                 .insert(key.to_string(), render_column_specs.clone())
                 .is_some()
@@ -638,8 +2240,7 @@ impl ItemSpecs {
         }
         self.render_table = Some(RenderTableSpecs {
             columns: indexed_keys,
-            additional_columns: self.render_table.clone().unwrap().additional_columns,
-            headers: self.render_table.clone().unwrap().headers,
+            ..self.render_table.clone().unwrap()
         });
         let mut rendered_spells = HashMap::new();
         for (key, render_column_specs) in self.render_table.as_ref().unwrap().columns.iter() {
@@ -660,8 +2261,7 @@ impl ItemSpecs {
         }
         self.render_table = Some(RenderTableSpecs {
             columns: rendered_spells,
-            additional_columns: self.render_table.clone().unwrap().additional_columns,
-            headers: self.render_table.clone().unwrap().headers,
+            ..self.render_table.clone().unwrap()
         });
         // Generate default RenderColumnSpecs for columns that are not specified in the config
         for header in headers {
@@ -687,14 +2287,16 @@ fn default_precision() -> u32 {
     2_u32
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct RenderColumnSpec {
     #[serde(default)]
     pub(crate) optional: Option<bool>,
     #[serde(default)]
     pub(crate) precision: Option<u32>,
-    #[serde(default)]
+    /// Name shown in the column header and plot titles instead of the column name. Links,
+    /// filters and expressions still refer to the column name.
+    #[serde(default, alias = "display-name")]
     pub(crate) label: Option<String>,
     #[serde(default)]
     pub(crate) custom: Option<String>,
@@ -713,6 +2315,130 @@ pub(crate) struct RenderColumnSpec {
     #[serde(default)]
     pub(crate) plot_view_legend: Option<bool>,
     #[serde(default)]
+    pub(crate) bins: Option<usize>,
+    #[serde(default)]
+    pub(crate) log_scale: Option<bool>,
+    /// Scale on which the values of this numeric column are binned in its histogram. With
+    /// `log`, bins are evenly spaced orders of magnitude, e.g. for p-values, and values that
+    /// are not positive are counted separately.
+    #[serde(default)]
+    pub(crate) scale: Option<BinScale>,
+    #[serde(default)]
+    pub(crate) date_format: Option<String>,
+    /// Format used to parse the dates of the column, e.g. `%m/%d/%Y` to resolve whether
+    /// `01/08/2023` denotes January or August. By default, all supported formats are tried.
+    #[serde(default)]
+    pub(crate) input_date_format: Option<String>,
+    #[serde(default)]
+    pub(crate) date_granularity: Option<DateGranularity>,
+    /// Number of values shown in the histogram of this nominal column, e.g. the most frequent
+    /// ones, or `all`. The remaining values are summarized as `other`. Defaults to the
+    /// max-categories of the table or 10.
+    #[serde(default)]
+    pub(crate) max_categories: Option<MaxCategories>,
+    /// Whether the histogram of this integer column has a bar per value instead of bins, e.g.
+    /// for chromosomes or flags. By default, integer columns with at most max-categories
+    /// (12 unless configured) distinct values are categorical.
+    #[serde(default)]
+    pub(crate) categorical: Option<bool>,
+    /// Order of the bars of the histogram of this nominal column, either `by-count`,
+    /// `alphabetical`, `numeric` or a list of values followed by the remaining ones in
+    /// alphabetical order. Only the first max-categories values in this order are shown. A list
+    /// of values, e.g. severity levels, also orders the rows when sorting the table by this
+    /// column.
+    #[serde(default)]
+    pub(crate) nominal_sort: NominalSort,
+    /// Rules for the background color of cells. The first matching rule wins.
+    #[serde(default)]
+    pub(crate) color_rules: Vec<ColorRule>,
+    /// View whose row with a matching value the cells of this column link to.
+    #[serde(default)]
+    pub(crate) link_to_table: Option<String>,
+    /// Column of the linked view that is matched against the cell values. Defaults to the
+    /// name of this column.
+    #[serde(default)]
+    pub(crate) link_column: Option<String>,
+    /// Scatter plot of two numeric columns that is shown next to the histogram of this column.
+    #[serde(default)]
+    pub(crate) scatter: Option<ScatterPlotConfig>,
+    /// Whether to render a box plot of this numeric column next to its histogram.
+    #[serde(default)]
+    pub(crate) boxplot: bool,
+    /// Whether to render a violin plot of this numeric column next to its histogram.
+    #[serde(default)]
+    pub(crate) violin: bool,
+    /// Vega-Lite spec replacing the histogram of this column. The plot records of the column
+    /// are injected into its data values, including the share of rows of each record as
+    /// `percentage` between 0 and 100.
+    #[serde(default)]
+    pub(crate) custom_vega_spec: Option<PathBuf>,
+    /// Whether to render the values of this column as links even if it is not detected as a
+    /// URL column.
+    #[serde(default)]
+    pub(crate) url_column: bool,
+    /// Number of characters after which the displayed URLs of this column are shortened, which
+    /// omits their scheme but keeps at least their host. Defaults to 60.
+    #[serde(default)]
+    pub(crate) url_truncate_length: Option<usize>,
+    /// Whether to render the values of this column as image thumbnails even if it is not
+    /// detected as an image URL column. Local images under relative paths are copied into
+    /// the report and no histogram is plotted for image columns.
+    #[serde(default)]
+    pub(crate) image_column: bool,
+    /// Maximal height of image thumbnails in pixels. Defaults to 64.
+    #[serde(default)]
+    pub(crate) thumbnail_height: Option<u32>,
+    /// Maximal width of image thumbnails in pixels. Defaults to 128.
+    #[serde(default)]
+    pub(crate) thumbnail_width: Option<u32>,
+    /// Values denoting true in this column, replacing the default ones.
+    #[serde(default)]
+    pub(crate) true_values: Vec<String>,
+    /// Values denoting false in this column, replacing the default ones.
+    #[serde(default)]
+    pub(crate) false_values: Vec<String>,
+    /// d3-format specifier of numeric values, e.g. `,.2f` for thousands separators and two
+    /// decimals.
+    #[serde(default)]
+    pub(crate) format: Option<String>,
+    /// Whether percentages like `87.5%` are plotted as fractions, i.e. divided by 100.
+    #[serde(default)]
+    pub(crate) percent_as_fraction: bool,
+    /// Whether values of this column are sorted by their numbers, e.g. `sample2` before
+    /// `sample10`, when sorting the rows of the table by it and in its histogram.
+    #[serde(default)]
+    pub(crate) natural_sort: bool,
+    /// Whether the header of this column offers a filter for its values.
+    #[serde(default = "default_filterable")]
+    pub(crate) filterable: bool,
+    /// Whether numeric values of this column are filtered by selecting a range instead of
+    /// typing a text. Has no effect on other columns.
+    #[serde(default = "default_range_filter")]
+    pub(crate) range_filter: bool,
+    /// Decimal and thousands separators of the numbers in this column, overriding the default
+    /// of the table.
+    #[serde(default)]
+    pub(crate) number_format: Option<NumberFormat>,
+    /// Type of the column, overriding the one inferred from its values. Values that do not fit
+    /// the type are treated as missing in plots.
+    #[serde(default, rename = "type")]
+    pub(crate) column_type: Option<ColumnType>,
+    /// Literal prefixes and suffixes like `$` or a regular expression whose matches are removed
+    /// from the values before parsing them as numbers. The cells still show the original text.
+    #[serde(default)]
+    pub(crate) strip: Option<Strip>,
+    /// Minimal fraction of non-missing values that have to be numbers (after stripping) in order
+    /// to treat the column as numeric, overriding the one of the table.
+    #[serde(default)]
+    pub(crate) min_numeric_fraction: Option<f64>,
+    /// Tokens denoting missing values in this column, overriding the ones of the table.
+    #[serde(default)]
+    pub(crate) na_values: Option<Vec<String>>,
+    /// Colors of values of this column, e.g. `PASS: green`, which are used for their bars in
+    /// the histogram and as background of their cells.
+    #[serde(default)]
+    pub(crate) value_colors: Option<IndexMap<String, Color>>,
+    #[serde(default)]
     pub(crate) spell: Option<SpellSpec>,
 }
 
@@ -730,6 +2456,40 @@ impl Default for RenderColumnSpec {
             custom_plot: None,
             ellipsis: None,
             plot_view_legend: Some(false),
+            bins: None,
+            log_scale: Some(false),
+            scale: None,
+            date_format: None,
+            input_date_format: None,
+            date_granularity: None,
+            max_categories: None,
+            categorical: None,
+            nominal_sort: NominalSort::ByCount,
+            color_rules: vec![],
+            link_to_table: None,
+            link_column: None,
+            scatter: None,
+            boxplot: false,
+            violin: false,
+            custom_vega_spec: None,
+            url_column: false,
+            url_truncate_length: None,
+            image_column: false,
+            thumbnail_height: None,
+            thumbnail_width: None,
+            true_values: vec![],
+            false_values: vec![],
+            format: None,
+            percent_as_fraction: false,
+            natural_sort: false,
+            filterable: true,
+            range_filter: true,
+            number_format: None,
+            column_type: None,
+            strip: None,
+            min_numeric_fraction: None,
+            na_values: None,
+            value_colors: None,
             spell: None,
         }
     }
@@ -769,6 +2529,108 @@ impl RenderColumnSpec {
         if let Some(plot_view_legend) = other.plot_view_legend {
             merged.plot_view_legend = Some(plot_view_legend);
         }
+        if let Some(bins) = other.bins {
+            merged.bins = Some(bins);
+        }
+        if let Some(log_scale) = other.log_scale {
+            merged.log_scale = Some(log_scale);
+        }
+        if let Some(scale) = other.scale {
+            merged.scale = Some(scale);
+        }
+        if let Some(date_format) = &other.date_format {
+            merged.date_format = Some(date_format.to_string());
+        }
+        if let Some(input_date_format) = &other.input_date_format {
+            merged.input_date_format = Some(input_date_format.to_string());
+        }
+        if let Some(date_granularity) = other.date_granularity {
+            merged.date_granularity = Some(date_granularity);
+        }
+        if let Some(max_categories) = other.max_categories {
+            merged.max_categories = Some(max_categories);
+        }
+        if let Some(categorical) = other.categorical {
+            merged.categorical = Some(categorical);
+        }
+        if other.nominal_sort != NominalSort::default() {
+            merged.nominal_sort = other.nominal_sort.clone();
+        }
+        if !other.color_rules.is_empty() {
+            merged.color_rules = other.color_rules.clone();
+        }
+        if let Some(link_to_table) = &other.link_to_table {
+            merged.link_to_table = Some(link_to_table.to_string());
+        }
+        if let Some(link_column) = &other.link_column {
+            merged.link_column = Some(link_column.to_string());
+        }
+        if let Some(scatter) = &other.scatter {
+            merged.scatter = Some(scatter.clone());
+        }
+        if other.boxplot {
+            merged.boxplot = true;
+        }
+        if other.violin {
+            merged.violin = true;
+        }
+        if let Some(custom_vega_spec) = &other.custom_vega_spec {
+            merged.custom_vega_spec = Some(custom_vega_spec.clone());
+        }
+        if other.url_column {
+            merged.url_column = true;
+        }
+        if let Some(url_truncate_length) = other.url_truncate_length {
+            merged.url_truncate_length = Some(url_truncate_length);
+        }
+        if other.image_column {
+            merged.image_column = true;
+        }
+        if let Some(thumbnail_height) = other.thumbnail_height {
+            merged.thumbnail_height = Some(thumbnail_height);
+        }
+        if let Some(thumbnail_width) = other.thumbnail_width {
+            merged.thumbnail_width = Some(thumbnail_width);
+        }
+        if !other.true_values.is_empty() {
+            merged.true_values = other.true_values.clone();
+        }
+        if !other.false_values.is_empty() {
+            merged.false_values = other.false_values.clone();
+        }
+        if let Some(format) = &other.format {
+            merged.format = Some(format.to_string());
+        }
+        if other.percent_as_fraction {
+            merged.percent_as_fraction = true;
+        }
+        if other.natural_sort {
+            merged.natural_sort = true;
+        }
+        if !other.filterable {
+            merged.filterable = false;
+        }
+        if !other.range_filter {
+            merged.range_filter = false;
+        }
+        if let Some(number_format) = other.number_format {
+            merged.number_format = Some(number_format);
+        }
+        if let Some(column_type) = other.column_type {
+            merged.column_type = Some(column_type);
+        }
+        if let Some(strip) = &other.strip {
+            merged.strip = Some(strip.clone());
+        }
+        if let Some(min_numeric_fraction) = other.min_numeric_fraction {
+            merged.min_numeric_fraction = Some(min_numeric_fraction);
+        }
+        if let Some(value_colors) = &other.value_colors {
+            merged.value_colors = Some(value_colors.clone());
+        }
+        if let Some(na_values) = &other.na_values {
+            merged.na_values = Some(na_values.clone());
+        }
         Ok(merged)
     }
 
@@ -786,11 +2648,14 @@ impl RenderColumnSpec {
         if self.plot_view_legend.is_none() {
             with_defaults.plot_view_legend = Some(false);
         }
+        if self.log_scale.is_none() {
+            with_defaults.log_scale = Some(false);
+        }
         Ok(with_defaults)
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"))]
 pub(crate) struct LinkToUrlSpec {
     #[serde(flatten)]
@@ -798,7 +2663,7 @@ pub(crate) struct LinkToUrlSpec {
     pub(crate) custom_content: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct LinkToUrlSpecEntry {
     url: String,
@@ -810,7 +2675,7 @@ fn default_new_window() -> bool {
     true
 }
 
-#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum DisplayMode {
     #[default]
@@ -819,127 +2684,672 @@ pub(crate) enum DisplayMode {
     Hidden,
 }
 
-#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
-#[serde(rename_all = "lowercase")]
-pub(crate) enum HeaderDisplayMode {
-    #[default]
-    Normal,
-    Hidden,
+/// Worksheet of a spreadsheet. Numbers are zero-based indices while strings are sheet names,
+/// so that sheets with numeric names can still be selected by quoting them.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum Sheet {
+    Index(usize),
+    Name(String),
 }
 
-impl RenderColumnSpec {
-    fn preprocess(&mut self, dataset: &DatasetSpecs, title: &str) -> Result<()> {
-        if !dataset.is_empty()? {
-            if let Some(plot) = &mut self.plot {
-                if let Some(ticks) = &mut plot.tick_plot {
-                    ticks.preprocess(dataset)?;
-                } else if let Some(heatmap) = &mut plot.heatmap {
-                    heatmap.preprocess(dataset, title)?;
-                } else if let Some(bars) = &mut plot.bar_plot {
-                    bars.preprocess(dataset)?;
-                }
-            }
-        }
-        if let Some(path) = self.custom_path.as_ref() {
-            let mut file = File::open(path)?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
-            self.custom = Some(contents);
+/// Records of a table given in the config. Records can either be maps from column names to
+/// values, where missing keys denote empty values, or rows that are read like the lines of a
+/// delimited file, so that `header` and `column-names` apply to them.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum InlineData {
+    Records(Vec<IndexMap<String, InlineValue>>),
+    Rows(Vec<Vec<InlineValue>>),
+}
+
+impl InlineData {
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            InlineData::Records(records) => records.is_empty(),
+            InlineData::Rows(rows) => rows.is_empty(),
         }
-        Ok(())
     }
 }
 
-impl TickPlot {
-    fn preprocess(&mut self, dataset: &DatasetSpecs) -> Result<()> {
-        if let Some(color_definition) = &mut self.color {
-            color_definition.preprocess()?;
+/// Scalar value of a table given in the config, where null denotes an empty value.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub(crate) enum InlineValue {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Text(String),
+    Missing(()),
+}
+
+impl InlineValue {
+    /// Returns the value as it would be written to a delimited file.
+    pub(crate) fn to_field(&self) -> String {
+        match self {
+            InlineValue::Integer(value) => value.to_string(),
+            InlineValue::Float(value) => value.to_string(),
+            InlineValue::Boolean(value) => value.to_string(),
+            InlineValue::Text(value) => value.to_owned(),
+            InlineValue::Missing(()) => String::new(),
         }
-        self.aux_domain_columns.preprocess(dataset)
     }
 }
 
-impl Heatmap {
-    fn preprocess(&mut self, dataset: &DatasetSpecs, title: &str) -> Result<()> {
-        self.aux_domain_columns.preprocess(dataset)?;
-        self.scale_type.preprocess();
-        match self.vega_type {
-            Some(VegaType::Nominal) | Some(VegaType::Ordinal) => {
-                self.scale_type = ScaleType::Ordinal;
-                self.color_scheme = "category20".to_string();
-            }
-            Some(VegaType::Quantitative) => {
-                self.scale_type = ScaleType::Linear;
-                self.color_scheme = "blues".to_string();
-            }
-            _ => {}
-        }
-        if self.domain.is_none() {
-            let d = get_column_domain(title, dataset, self)?;
-            let domain: Vec<String> = if self.scale_type.is_quantitative() {
-                let floating_domain: Vec<f64> = serde_json::from_str(&d)?;
-                floating_domain.iter().map(|x| x.to_string()).collect()
-            } else {
-                serde_json::from_str(&d)?
-            };
-            self.domain = Some(domain);
-        }
-        if let Some(domain_mid) = &self.domain_mid {
-            let old_domain = self.domain.as_mut().unwrap();
-            old_domain.insert(1, domain_mid.to_string());
-            self.domain = Some(old_domain.to_owned())
-        }
-        if !self.color_range.0.is_empty() {
-            self.color_range.preprocess()?;
-        }
-        Ok(())
+/// Scale on which the values of numeric columns are binned in their histograms.
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BinScale {
+    #[default]
+    Linear,
+    Log,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DateGranularity {
+    #[default]
+    Month,
+    Year,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Theme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Default minimal fraction of values that have to be numbers after stripping in order to treat
+/// a column as numeric.
+const MIN_NUMERIC_FRACTION: f64 = 0.9;
+
+/// Tokens denoting missing values in addition to empty values unless configured otherwise.
+pub(crate) const DEFAULT_NA_VALUES: [&str; 4] = ["NA", "N/A", "NaN", "null"];
+
+/// Number of leading rows from which the types of columns are inferred, given as a number or
+/// as `all`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ClassifyRows {
+    All,
+    First(usize),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum ClassifyRowsSpec {
+    First(usize),
+    Keyword(String),
+}
+
+impl Default for ClassifyRows {
+    fn default() -> Self {
+        ClassifyRows::First(10000)
     }
 }
 
-impl BarPlot {
-    fn preprocess(&mut self, dataset: &DatasetSpecs) -> Result<()> {
-        if let Some(color_definition) = &mut self.color {
-            color_definition.preprocess()?;
+impl<'de> Deserialize<'de> for ClassifyRows {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match ClassifyRowsSpec::deserialize(deserializer)? {
+            ClassifyRowsSpec::First(0) => {
+                Err(D::Error::custom("classify-rows must be at least 1 or all"))
+            }
+            ClassifyRowsSpec::First(rows) => Ok(ClassifyRows::First(rows)),
+            ClassifyRowsSpec::Keyword(keyword) if keyword == "all" => Ok(ClassifyRows::All),
+            ClassifyRowsSpec::Keyword(keyword) => Err(D::Error::custom(format!(
+                "invalid classify-rows {keyword:?}, expected a number of rows or all"
+            ))),
         }
-        self.aux_domain_columns.preprocess(dataset)
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
-pub(crate) struct RenderPlotSpec {
-    #[serde(default, rename = "spec")]
-    pub(crate) schema: Option<String>,
-    #[serde(default, rename = "spec-path")]
-    pub(crate) schema_path: Option<String>,
+impl JsonSchema for ClassifyRows {
+    fn schema_name() -> String {
+        "ClassifyRows".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        ClassifyRowsSpec::json_schema(generator)
+    }
 }
 
-impl RenderPlotSpec {
-    /// Reads schema for RenderPlotSpec from path and saves it under the schema attribute
-    pub(crate) fn read_schema(&mut self) -> Result<()> {
-        if let Some(path) = self.schema_path.as_ref() {
-            let mut file = File::open(path)?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
-            self.schema = Some(contents);
+impl ClassifyRows {
+    /// Returns the maximal number of rows to examine, or `None` for all rows.
+    pub(crate) fn limit(&self) -> Option<usize> {
+        match self {
+            ClassifyRows::All => None,
+            ClassifyRows::First(rows) => Some(*rows),
         }
-        Ok(())
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
-pub(crate) struct RenderHtmlSpec {
-    pub(crate) script_path: String,
+/// Order of the bars of histograms of nominal columns.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) enum NominalSort {
+    /// Descending by the number of occurrences of the values.
+    #[default]
+    ByCount,
+    Alphabetical,
+    /// Ascending by the numeric values, followed by the values that are no numbers.
+    Numeric,
+    /// The given values first, followed by the remaining ones in alphabetical order.
+    Custom(Vec<String>),
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
-pub(crate) struct RenderImgSpec {
-    pub(crate) path: String,
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum NominalSortSpec {
+    Custom(Vec<String>),
+    Keyword(String),
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+impl<'de> Deserialize<'de> for NominalSort {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match NominalSortSpec::deserialize(deserializer)? {
+            NominalSortSpec::Custom(values) => Ok(NominalSort::Custom(values)),
+            NominalSortSpec::Keyword(keyword) if keyword == "by-count" => Ok(NominalSort::ByCount),
+            NominalSortSpec::Keyword(keyword) if keyword == "alphabetical" => {
+                Ok(NominalSort::Alphabetical)
+            }
+            NominalSortSpec::Keyword(keyword) if keyword == "numeric" => Ok(NominalSort::Numeric),
+            NominalSortSpec::Keyword(keyword) => Err(D::Error::custom(format!(
+                "invalid nominal-sort {keyword:?}, expected by-count, alphabetical, numeric or a list of values"
+            ))),
+        }
+    }
+}
+
+impl JsonSchema for NominalSort {
+    fn schema_name() -> String {
+        "NominalSort".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        NominalSortSpec::json_schema(generator)
+    }
+}
+
+/// Number of values shown in histograms of nominal columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MaxCategories {
+    All,
+    Count(usize),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum MaxCategoriesSpec {
+    Count(usize),
+    Keyword(String),
+}
+
+impl<'de> Deserialize<'de> for MaxCategories {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match MaxCategoriesSpec::deserialize(deserializer)? {
+            MaxCategoriesSpec::Count(count) => Ok(MaxCategories::Count(count)),
+            MaxCategoriesSpec::Keyword(keyword) if keyword == "all" => Ok(MaxCategories::All),
+            MaxCategoriesSpec::Keyword(keyword) => Err(D::Error::custom(format!(
+                "invalid max-categories {keyword:?}, expected a number or all"
+            ))),
+        }
+    }
+}
+
+impl JsonSchema for MaxCategories {
+    fn schema_name() -> String {
+        "MaxCategories".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        MaxCategoriesSpec::json_schema(generator)
+    }
+}
+
+/// What to remove from values before parsing them as numbers, given either as a list of
+/// literal prefixes and suffixes or as a regular expression, e.g. `{regex: '[^0-9.]'}`.
+#[derive(Debug, Clone)]
+pub(crate) enum Strip {
+    Affixes(Vec<String>),
+    Regex(Regex),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged, deny_unknown_fields)]
+enum StripSpec {
+    Affixes(Vec<String>),
+    Regex { regex: String },
+}
+
+impl<'de> Deserialize<'de> for Strip {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match StripSpec::deserialize(deserializer)? {
+            StripSpec::Affixes(affixes) => Ok(Strip::Affixes(affixes)),
+            StripSpec::Regex { regex } => Regex::new(&regex).map(Strip::Regex).map_err(|error| {
+                D::Error::custom(format!("invalid strip regex {regex:?}: {error}"))
+            }),
+        }
+    }
+}
+
+impl JsonSchema for Strip {
+    fn schema_name() -> String {
+        "Strip".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        StripSpec::json_schema(generator)
+    }
+}
+
+impl PartialEq for Strip {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Strip::Affixes(affixes), Strip::Affixes(other)) => affixes == other,
+            (Strip::Regex(regex), Strip::Regex(other)) => regex.as_str() == other.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Strip {
+    /// Removes the prefixes and suffixes or the matches of the regular expression from the
+    /// given value, along with surrounding whitespace.
+    pub(crate) fn apply<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match self {
+            Strip::Affixes(affixes) => {
+                let mut value = value.trim();
+                for affix in affixes {
+                    value = value.strip_prefix(affix.as_str()).unwrap_or(value).trim();
+                    value = value.strip_suffix(affix.as_str()).unwrap_or(value).trim();
+                }
+                Cow::Borrowed(value)
+            }
+            Strip::Regex(regex) => match regex.replace_all(value, "") {
+                Cow::Borrowed(value) => Cow::Borrowed(value.trim()),
+                Cow::Owned(value) => Cow::Owned(value.trim().to_owned()),
+            },
+        }
+    }
+}
+
+/// Column whose values are stripped before parsing them as numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct StrippedColumn {
+    pub(crate) strip: Strip,
+    pub(crate) min_fraction: f64,
+}
+
+/// Separators of numbers written in a locale-specific way, e.g. `1.234,56`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct NumberFormat {
+    #[serde(default = "default_decimal_separator")]
+    pub(crate) decimal_separator: char,
+    #[serde(default)]
+    pub(crate) thousands_separator: Option<char>,
+}
+
+fn default_decimal_separator() -> char {
+    '.'
+}
+
+impl NumberFormat {
+    /// Returns the given value with its thousands separators removed and `.` as decimal
+    /// separator, or `None` if the thousands separators do not separate groups of three digits.
+    pub(crate) fn normalize(&self, value: &str) -> Option<String> {
+        let (integer, fraction) = match value.split_once(self.decimal_separator) {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (value, None),
+        };
+        let integer = match self.thousands_separator {
+            Some(separator) if integer.contains(separator) => {
+                let mut groups = integer.split(separator);
+                let leading = groups.next()?.trim_start_matches(['-', '+']);
+                let is_digits = |group: &str| group.chars().all(|c| c.is_ascii_digit());
+                if leading.is_empty()
+                    || leading.len() > 3
+                    || !is_digits(leading)
+                    || !groups.all(|group| group.len() == 3 && is_digits(group))
+                {
+                    return None;
+                }
+                integer.replace(separator, "")
+            }
+            _ => integer.to_owned(),
+        };
+        Some(match fraction {
+            Some(fraction) => format!("{integer}.{fraction}"),
+            None => integer,
+        })
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum HeaderDisplayMode {
+    #[default]
+    Normal,
+    Hidden,
+}
+
+impl RenderColumnSpec {
+    fn preprocess(&mut self, dataset: &DatasetSpecs, title: &str) -> Result<()> {
+        if !dataset.is_empty()? {
+            if let Some(plot) = &mut self.plot {
+                if let Some(ticks) = &mut plot.tick_plot {
+                    ticks.preprocess(dataset)?;
+                } else if let Some(heatmap) = &mut plot.heatmap {
+                    heatmap.preprocess(dataset, title)?;
+                } else if let Some(bars) = &mut plot.bar_plot {
+                    bars.preprocess(dataset)?;
+                }
+            }
+        }
+        if let Some(value_colors) = &mut self.value_colors {
+            value_colors
+                .values_mut()
+                .try_for_each(|color| color.preprocess())?;
+        }
+        if let Some(path) = self.custom_path.as_ref() {
+            let mut file = File::open(path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+            self.custom = Some(contents);
+        }
+        Ok(())
+    }
+
+    /// Returns the label of the given column if one is configured and its name otherwise.
+    pub(crate) fn display_name<'a>(&'a self, column: &'a str) -> &'a str {
+        self.label.as_deref().unwrap_or(column)
+    }
+
+    /// Returns the vocabulary of boolean values if the column is classified as boolean or
+    /// boolean values are configured for it.
+    pub(crate) fn boolean_vocabulary(
+        &self,
+        column_type: Option<&ColumnType>,
+    ) -> Option<BooleanVocabulary> {
+        if column_type == Some(&ColumnType::Boolean)
+            || !self.true_values.is_empty()
+            || !self.false_values.is_empty()
+        {
+            Some(BooleanVocabulary::new(
+                &self.true_values,
+                &self.false_values,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl TickPlot {
+    fn preprocess(&mut self, dataset: &DatasetSpecs) -> Result<()> {
+        if let Some(color_definition) = &mut self.color {
+            color_definition.preprocess()?;
+        }
+        self.aux_domain_columns.preprocess(dataset)
+    }
+}
+
+impl Heatmap {
+    fn preprocess(&mut self, dataset: &DatasetSpecs, title: &str) -> Result<()> {
+        self.aux_domain_columns.preprocess(dataset)?;
+        self.scale_type.preprocess();
+        match self.vega_type {
+            Some(VegaType::Nominal) | Some(VegaType::Ordinal) => {
+                self.scale_type = ScaleType::Ordinal;
+                self.color_scheme = "category20".to_string();
+            }
+            Some(VegaType::Quantitative) => {
+                self.scale_type = ScaleType::Linear;
+                self.color_scheme = "blues".to_string();
+            }
+            _ => {}
+        }
+        if self.domain.is_none() {
+            let d = get_column_domain(title, dataset, self)?;
+            let domain: Vec<String> = if self.scale_type.is_quantitative() {
+                let floating_domain: Vec<f64> = serde_json::from_str(&d)?;
+                floating_domain.iter().map(|x| x.to_string()).collect()
+            } else {
+                serde_json::from_str(&d)?
+            };
+            self.domain = Some(domain);
+        }
+        if let Some(domain_mid) = &self.domain_mid {
+            let old_domain = self.domain.as_mut().unwrap();
+            old_domain.insert(1, domain_mid.to_string());
+            self.domain = Some(old_domain.to_owned())
+        } else if self.scale_type.is_quantitative() && self.color_range.0.len() == 3 {
+            // A middle color is placed halfway between the bounds unless its position is
+            // configured, since otherwise only the first two colors would be used.
+            let domain = self.domain.as_mut().unwrap();
+            if let [Ok(min), Ok(max)] = domain.iter().map(|x| f64::from_str(x)).collect_vec()[..] {
+                domain.insert(1, ((min + max) / 2.0).to_string());
+            }
+        }
+        if !self.color_range.0.is_empty() {
+            self.color_range.preprocess()?;
+        }
+        Ok(())
+    }
+}
+
+impl BarPlot {
+    fn preprocess(&mut self, dataset: &DatasetSpecs) -> Result<()> {
+        if let Some(color_definition) = &mut self.color {
+            color_definition.preprocess()?;
+        }
+        self.aux_domain_columns.preprocess(dataset)
+    }
+}
+
+/// Scatter plot of the numeric columns x-column and y-column with points optionally colored by
+/// the values of color-column.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct ScatterPlotConfig {
+    pub(crate) x_column: String,
+    pub(crate) y_column: String,
+    #[serde(default)]
+    pub(crate) color_column: Option<String>,
+    #[serde(default)]
+    pub(crate) opacity: Option<f32>,
+}
+
+impl ScatterPlotConfig {
+    /// Returns all columns of the dataset that are used by the scatter plot.
+    pub(crate) fn columns(&self) -> impl Iterator<Item = &String> {
+        [&self.x_column, &self.y_column]
+            .into_iter()
+            .chain(self.color_column.as_ref())
+    }
+}
+
+/// Background color of cells whose value lies within the given range (min inclusive, max
+/// exclusive) or equals the given value. Rules without any condition match every cell.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct ColorRule {
+    #[serde(default)]
+    pub(crate) min: Option<f64>,
+    #[serde(default)]
+    pub(crate) max: Option<f64>,
+    #[serde(default)]
+    pub(crate) value: Option<String>,
+    pub(crate) color: String,
+}
+
+impl ColorRule {
+    /// Returns true if the given cell value satisfies this rule.
+    pub(crate) fn matches(&self, value: &str) -> bool {
+        if let Some(expected) = &self.value {
+            return value == expected;
+        }
+        if self.min.is_none() && self.max.is_none() {
+            return true;
+        }
+        match f64::from_str(value) {
+            Ok(value) => {
+                self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value < max)
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Returns the color of the first of the given rules matching the given cell value.
+pub(crate) fn cell_color<'a>(rules: &'a [ColorRule], value: &str) -> Option<&'a str> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(value))
+        .map(|rule| rule.color.as_str())
+}
+
+/// Background color of rows whose value in the given column satisfies the comparison with the
+/// given value.
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct RowColorRule {
+    pub(crate) column: String,
+    pub(crate) operator: ComparisonOp,
+    pub(crate) value: String,
+    pub(crate) color: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub(crate) enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Contains,
+    Regex,
+}
+
+impl RowColorRule {
+    /// Returns the compiled pattern of regex rules.
+    fn pattern(&self) -> Result<Option<Regex>> {
+        match self.operator {
+            ComparisonOp::Regex => match Regex::new(&self.value) {
+                Ok(pattern) => Ok(Some(pattern)),
+                Err(error) => bail!(
+                    "Invalid regular expression {:?} for column {:?}: {error}.",
+                    self.value,
+                    self.column
+                ),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns true if the given cell value satisfies this rule. Values are compared
+    /// numerically if both are numbers. Ordering comparisons never match non-numeric values.
+    fn matches(&self, value: &str, pattern: Option<&Regex>) -> bool {
+        let numbers = f64::from_str(value)
+            .ok()
+            .zip(f64::from_str(&self.value).ok());
+        match (self.operator, numbers) {
+            (ComparisonOp::Eq, Some((value, expected))) => value == expected,
+            (ComparisonOp::Eq, None) => value == self.value,
+            (ComparisonOp::Ne, Some((value, expected))) => value != expected,
+            (ComparisonOp::Ne, None) => value != self.value,
+            (ComparisonOp::Lt, Some((value, expected))) => value < expected,
+            (ComparisonOp::Gt, Some((value, expected))) => value > expected,
+            (ComparisonOp::Le, Some((value, expected))) => value <= expected,
+            (ComparisonOp::Ge, Some((value, expected))) => value >= expected,
+            (ComparisonOp::Lt | ComparisonOp::Gt | ComparisonOp::Le | ComparisonOp::Ge, None) => {
+                false
+            }
+            (ComparisonOp::Contains, _) => value.contains(&self.value),
+            (ComparisonOp::Regex, _) => {
+                pattern.is_some_and(|pattern| pattern.is_match(value).unwrap_or(false))
+            }
+        }
+    }
+}
+
+/// Row color rules whose columns are resolved to the indices of a table and whose patterns are
+/// compiled.
+pub(crate) struct RowColors<'a> {
+    rules: Vec<(&'a RowColorRule, usize, Option<Regex>)>,
+}
+
+impl<'a> RowColors<'a> {
+    pub(crate) fn new(rules: &'a [RowColorRule], headers: &[String]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let index = headers
+                    .iter()
+                    .position(|header| header == &rule.column)
+                    .context(format!(
+                        "Row color rule refers to unknown column {:?}.",
+                        rule.column
+                    ))?;
+                Ok((rule, index, rule.pattern()?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RowColors { rules })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the color of the first rule matching the given row.
+    pub(crate) fn color(&self, row: &[String]) -> Option<&'a str> {
+        self.rules
+            .iter()
+            .find(|(rule, index, pattern)| {
+                row.get(*index)
+                    .is_some_and(|value| rule.matches(value, pattern.as_ref()))
+            })
+            .map(|(rule, _, _)| rule.color.as_str())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct RenderPlotSpec {
+    #[serde(default, rename = "spec")]
+    pub(crate) schema: Option<String>,
+    #[serde(default, rename = "spec-path")]
+    pub(crate) schema_path: Option<String>,
+}
+
+impl RenderPlotSpec {
+    /// Reads schema for RenderPlotSpec from path and saves it under the schema attribute
+    pub(crate) fn read_schema(&mut self) -> Result<()> {
+        if let Some(path) = self.schema_path.as_ref() {
+            let mut file = File::open(path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+            self.schema = Some(contents);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct RenderHtmlSpec {
+    pub(crate) script_path: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
+pub(crate) struct RenderImgSpec {
+    pub(crate) path: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct LinkSpec {
     #[serde(default)]
@@ -952,7 +3362,7 @@ pub(crate) struct LinkSpec {
     pub(crate) optional: bool,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct CustomPlot {
     #[serde(default, rename = "data")]
@@ -978,7 +3388,7 @@ impl CustomPlot {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct PlotSpec {
     #[serde(rename = "ticks")]
@@ -988,7 +3398,7 @@ pub(crate) struct PlotSpec {
     pub(crate) bar_plot: Option<BarPlot>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct TickPlot {
     #[serde(default, rename = "scale")]
@@ -1000,7 +3410,7 @@ pub(crate) struct TickPlot {
     #[serde(default)]
     pub(crate) color: Option<ColorDefinition>,
 }
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct ColorDefinition {
     #[serde(default, rename = "scale")]
@@ -1023,7 +3433,7 @@ fn default_clamp() -> bool {
     true
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct Heatmap {
     #[serde(default, rename = "type")]
@@ -1046,7 +3456,7 @@ pub(crate) struct Heatmap {
     pub(crate) custom_content: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 pub(crate) struct ColorRange(pub(crate) Vec<Color>);
 
 impl ColorRange {
@@ -1055,7 +3465,7 @@ impl ColorRange {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub(crate) struct Color(pub(crate) String);
 
 impl Color {
@@ -1090,7 +3500,7 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct BarPlot {
     #[serde(default, rename = "scale")]
@@ -1103,7 +3513,7 @@ pub(crate) struct BarPlot {
     pub(crate) color: Option<ColorDefinition>,
 }
 
-#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum ScaleType {
     Linear,
@@ -1121,7 +3531,7 @@ pub(crate) enum ScaleType {
     None,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Copy, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum VegaType {
     Nominal,
@@ -1149,7 +3559,7 @@ impl ScaleType {
     }
 }
 
-#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 pub struct AuxDomainColumns(pub(crate) Option<Vec<String>>);
 
 impl AuxDomainColumns {
@@ -1209,6 +3619,8 @@ pub enum ConfigError {
     MissingDatasetProperty { view: String },
     #[error("Could not find dataset named {dataset:?} in given config.")]
     MissingDataset { dataset: String },
+    #[error("Could not find custom CSS file under given path {path:?}.")]
+    MissingCustomCss { path: PathBuf },
     #[error("Could not find default view named {view:?} in given config.")]
     MissingDefaultView { view: String },
     #[error("View {view:?} consists of a configuration with render-plot and render-table present while only one should be present. If you want both please define two separate views.")]
@@ -1261,6 +3673,8 @@ pub enum ConfigError {
     },
     #[error("Could not find column named '{column}' in the dataset that is used by view {view}.")]
     MissingColumn { column: String, view: String },
+    #[error("Could not find column {column:?} to sort the rows of dataset {path:?} by.")]
+    MissingSortColumn { column: String, path: PathBuf },
     #[error(
         "Could not find view named {view:?} in given config that is referred to with {link:?}."
     )]
@@ -1273,19 +3687,109 @@ pub enum ConfigError {
     },
     #[error("Cannot customize the first header row of view {view:?} in given config. Please start customizing additional headers at index 1.")]
     HeadersFirstColumnCustomized { view: String },
+    #[error("Column {column:?} of view {view:?} is used as an axis of a scatter plot but is not numeric.")]
+    NonNumericScatterColumn { column: String, view: String },
+    #[error("Invalid filter expression of view {view:?}. {reason}")]
+    InvalidRowFilter { view: String, reason: String },
+    #[error("Invalid row color rule of view {view:?}. {reason}")]
+    InvalidRowColorRule { view: String, reason: String },
+    #[error("Sample size of view {view:?} must be greater than zero.")]
+    ZeroSampleSize { view: String },
+    #[error("Missing threshold {threshold} of view {view:?} must be between 0 and 1.")]
+    InvalidMissingThreshold { view: String, threshold: f64 },
+    #[error("Invalid computed column of view {view:?}. {reason}")]
+    InvalidComputedColumn { view: String, reason: String },
+    #[error(
+        "Computed column {column:?} of view {view:?} has the same name as an existing column."
+    )]
+    ComputedColumnCollision { column: String, view: String },
+    #[error("Column filter {filter:?} of view {view:?} does not match any column of its dataset.")]
+    UnmatchedColumnFilter { filter: String, view: String },
+    #[error("Box and violin plots of column {column:?} of view {view:?} require the column to be numeric.")]
+    NonNumericDistributionPlotColumn { column: String, view: String },
+    #[error("Given scatter plot opacity for column {column:?} of view {view:?} must be between 0 and 1.")]
+    InvalidScatterOpacity { column: String, view: String },
+    #[error("Given minimal numeric fraction for column {column:?} of view {view:?} must be between 0 and 1.")]
+    InvalidMinNumericFraction { column: String, view: String },
+    #[error("Given minimal numeric fraction of view {view:?} must be between 0 and 1.")]
+    InvalidTableMinNumericFraction { view: String },
+    #[error("Given number of histogram bins for column {column:?} of view {view:?} must be greater than 0.")]
+    ZeroHistogramBins { column: String, view: String },
+    #[error("Given default number of histogram bins must be greater than 0.")]
+    ZeroDefaultBins,
+    #[error("Dataset {dataset:?} has {expected} columns but {found} column-names were given.")]
+    ColumnNamesLengthMismatch {
+        dataset: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Given max-categories for column {column:?} of view {view:?} must be greater than 0.")]
+    ZeroMaxCategories { column: String, view: String },
+    #[error("Given max-categories of view {view:?} must be greater than 0.")]
+    ZeroDefaultMaxCategories { view: String },
+    #[error("Value {value:?} is listed multiple times in the nominal sort of column {column:?} of view {view:?}.")]
+    DuplicateSortValue {
+        value: String,
+        column: String,
+        view: String,
+    },
+    #[error("Color rules for column {column:?} of view {view:?} must either define a value or a min/max range.")]
+    ConflictingColorRule { column: String, view: String },
+    #[error("Given encoding {encoding:?} of dataset {dataset:?} is unknown.")]
+    UnknownEncoding { dataset: String, encoding: String },
+    #[error("Cross-table links of columns form a cycle between views {views:?}.")]
+    CircularTableLink { views: Vec<String> },
+    #[error(
+        "Given comment-char {comment_char:?} of dataset {dataset:?} must be an ASCII character."
+    )]
+    NonAsciiCommentChar { dataset: String, comment_char: char },
+    #[error("Given {option} {value:?} of dataset {dataset:?} must be a single ASCII character.")]
+    InvalidQuotingCharacter {
+        dataset: String,
+        option: String,
+        value: String,
+    },
+    #[error("Given {option} of dataset {dataset:?} must differ from its separator.")]
+    QuotingCharacterIsSeparator { dataset: String, option: String },
+    #[error("Dataset {dataset:?} must either be given a path or inline data but not both.")]
+    PathAndInlineData { dataset: String },
+    #[error("Dataset {dataset:?} must be given a path or inline data.")]
+    MissingDatasetPath { dataset: String },
+    #[error("Could not find dataset {dataset:?} under given path {path:?}.")]
+    DatasetPathNotFound { dataset: String, path: PathBuf },
+    #[error("Could not find file {path:?} referenced by view {view:?}.")]
+    MissingViewFile { view: String, path: String },
+    #[error("Inline data of dataset {dataset:?} must contain at least one record.")]
+    EmptyInlineData { dataset: String },
+    #[error("Environment variable '{name}' referenced in config is not set")]
+    UndefinedEnvVar { name: String },
+    #[error("Column names occur multiple times in the header of {path:?}: {duplicates}. Set dedup-headers: true for the dataset to number the repeated ones.")]
+    DuplicateHeader { duplicates: String, path: PathBuf },
+    #[error("Column {column:?} configured in {table_path:?} is ambiguous since the dataset contains it multiple times. Please refer to it by index or by its numbered name instead.")]
+    AmbiguousColumn { column: String, table_path: PathBuf },
+    #[error("Configs extending each other form a cycle between {configs:?}.")]
+    CircularExtends { configs: Vec<PathBuf> },
 }
 
 #[cfg(test)]
 mod tests {
     use crate::spec::{
-        default_links, default_page_size, default_precision, default_render_table,
-        default_single_page_threshold, AuxDomainColumns, ColorRange, DatasetSpecs, DisplayMode,
-        HeaderDisplayMode, HeaderSpecs, Heatmap, ItemSpecs, ItemsSpec, LinkSpec, LinkToUrlSpec,
-        LinkToUrlSpecEntry, PlotSpec, RenderColumnSpec, RenderHtmlSpec, RenderPlotSpec,
-        RenderTableSpecs, ScaleType, TickPlot,
+        cell_color, config_schema, default_links, default_page_size, default_precision,
+        default_render_table, default_single_page_threshold, AuxDomainColumns, ClassifyRows,
+        ColorRange, ColorRule, DatasetSpecs, DisplayMode, HeaderDisplayMode, HeaderSpecs, Heatmap,
+        ItemSpecs, ItemsSpec, LinkSpec, LinkToUrlSpec, LinkToUrlSpecEntry, MaxCategories,
+        NominalSort, PlotSpec, RenderColumnSpec, RenderHtmlSpec, RenderPlotSpec, RenderTableSpecs,
+        RowColorRule, RowColors, ScaleType, SortDirection, SortKey, Strip, Theme, TickPlot,
     };
+    use crate::spec::{extended_configs, substitute_env_vars};
+    use crate::utils::column_type::{classify_table, ColumnType};
+    use crate::utils::remote::DownloadSettings;
+    use indexmap::IndexMap;
+    use itertools::Itertools;
     use std::collections::HashMap;
-    use std::path::PathBuf;
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_table_config_deserialization() {
@@ -1309,16 +3813,51 @@ mod tests {
             custom_plot: None,
             ellipsis: None,
             plot_view_legend: None,
+            bins: None,
+            log_scale: None,
+            scale: None,
+            date_format: None,
+            input_date_format: None,
+            date_granularity: None,
+            max_categories: None,
+            categorical: None,
+            nominal_sort: NominalSort::ByCount,
+            color_rules: vec![],
+            link_to_table: None,
+            link_column: None,
+            scatter: None,
+            boxplot: false,
+            violin: false,
+            custom_vega_spec: None,
+            url_column: false,
+            url_truncate_length: None,
+            image_column: false,
+            thumbnail_height: None,
+            thumbnail_width: None,
+            true_values: vec![],
+            false_values: vec![],
+            format: None,
+            percent_as_fraction: false,
+            natural_sort: false,
+            filterable: true,
+            range_filter: true,
+            number_format: None,
+            column_type: None,
+            strip: None,
+            min_numeric_fraction: None,
+            na_values: None,
+            value_colors: None,
             label: None,
             spell: None,
         };
 
         let expected_dataset_spec = DatasetSpecs {
             path: PathBuf::from("test.tsv"),
-            separator: ',',
             header_rows: 1,
             links: default_links(),
-            offer_excel: false,
+            header: true,
+            double_quote: true,
+            ..Default::default()
         };
 
         let expected_table_spec = ItemSpecs {
@@ -1332,6 +3871,23 @@ mod tests {
                 columns: HashMap::from([("x".to_string(), expected_render_columns)]),
                 additional_columns: None,
                 headers: None,
+                include_columns: None,
+                exclude_columns: vec![],
+                filter: None,
+                computed_columns: IndexMap::new(),
+                correlation_matrix: false,
+                sample: None,
+                seed: None,
+                missing_threshold: 0.001,
+                row_color_rules: vec![],
+                embed_full_csv: false,
+                default_sort_column: None,
+                default_sort_direction: SortDirection::Asc,
+                sort_keys: Vec::new(),
+                number_format: None,
+                na_values: None,
+                min_numeric_fraction: None,
+                max_categories: None,
             }),
             render_plot: None,
             render_html: None,
@@ -1348,6 +3904,11 @@ mod tests {
             report_name: "my_report".to_string(),
             aux_libraries: None,
             webview_controls: false,
+            single_file: false,
+            theme: Theme::Light,
+            custom_css: vec![],
+            default_bins: None,
+            extends: None,
         };
 
         let raw_config = r#"
@@ -1392,10 +3953,11 @@ mod tests {
 
         let expected_dataset_spec = DatasetSpecs {
             path: PathBuf::from("test.tsv"),
-            separator: ',',
             header_rows: 1,
             links: Some(expected_links),
-            offer_excel: false,
+            header: true,
+            double_quote: true,
+            ..Default::default()
         };
 
         let expected_item_spec = ItemSpecs {
@@ -1421,6 +3983,11 @@ mod tests {
             report_name: "".to_string(),
             aux_libraries: None,
             webview_controls: false,
+            single_file: false,
+            theme: Theme::Light,
+            custom_css: vec![],
+            default_bins: None,
+            extends: None,
         };
 
         let raw_config = r#"
@@ -1453,10 +4020,11 @@ mod tests {
 
         let expected_dataset_spec = DatasetSpecs {
             path: PathBuf::from("test.tsv"),
-            separator: ',',
             header_rows: 1,
             links: Some(HashMap::from([])),
-            offer_excel: false,
+            header: true,
+            double_quote: true,
+            ..Default::default()
         };
 
         let expected_item_spec = ItemSpecs {
@@ -1482,6 +4050,11 @@ mod tests {
             report_name: "".to_string(),
             aux_libraries: Some(Vec::from(["https://cdnjs.org/d3.js".to_string()])),
             webview_controls: false,
+            single_file: false,
+            theme: Theme::Light,
+            custom_css: vec![],
+            default_bins: None,
+            extends: None,
         };
 
         let raw_config = r#"
@@ -1537,6 +4110,23 @@ mod tests {
                         ellipsis: None,
                     },
                 )])),
+                include_columns: None,
+                exclude_columns: vec![],
+                filter: None,
+                computed_columns: IndexMap::new(),
+                correlation_matrix: false,
+                sample: None,
+                seed: None,
+                missing_threshold: 0.001,
+                row_color_rules: vec![],
+                embed_full_csv: false,
+                default_sort_column: None,
+                default_sort_direction: SortDirection::Asc,
+                sort_keys: Vec::new(),
+                number_format: None,
+                na_values: None,
+                min_numeric_fraction: None,
+                max_categories: None,
             }),
             render_plot: None,
             render_html: None,
@@ -1550,10 +4140,11 @@ mod tests {
                 "table-a".to_string(),
                 DatasetSpecs {
                     path: PathBuf::from("test.tsv"),
-                    separator: ',',
                     header_rows: 2,
                     links: Some(HashMap::from([])),
-                    offer_excel: false,
+                    header: true,
+                    double_quote: true,
+                    ..Default::default()
                 },
             )]),
             default_view: None,
@@ -1562,6 +4153,11 @@ mod tests {
             report_name: "".to_string(),
             aux_libraries: None,
             webview_controls: false,
+            single_file: false,
+            theme: Theme::Light,
+            custom_css: vec![],
+            default_bins: None,
+            extends: None,
         };
 
         let raw_config = r#"
@@ -1582,94 +4178,1160 @@ mod tests {
                                         color-scheme: category20
             "#;
 
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert_eq!(config, expected_config);
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert_eq!(config, expected_config);
+    }
+
+    #[test]
+    fn test_valid_config_validation() {
+        let config = ItemsSpec::from_file(
+            ".examples/example-config.yaml",
+            &DownloadSettings::default(),
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_missing_dataset_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                plot-b:
+                    dataset: table-b
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_missing_default_view_config_validation() {
+        let raw_config = r#"
+            default-view: table-b
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                table-a:
+                    dataset: table-a
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_value_outside_domain_config_validation() {
+        let raw_config = r#"
+            datasets:
+                oscars:
+                    path: ".examples/data/oscars.csv"
+            views:
+                oscars:
+                    dataset: oscars
+                    render-table:
+                        columns:
+                            age:
+                                plot:
+                                    ticks:
+                                        scale: linear
+                                        domain:
+                                            - 50
+                                            - 60
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_log_scale_includes_zero_config_validation() {
+        let raw_config = r#"
+            datasets:
+                oscars:
+                    path: ".examples/data/oscars.csv"
+            views:
+                oscars:
+                    dataset: oscars
+                    render-table:
+                        columns:
+                            age:
+                                plot:
+                                    ticks:
+                                        scale: log
+                                        domain:
+                                            - 0
+                                            - 100
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_customize_first_header_row_view_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                table-a:
+                    dataset: table-a
+                    render-table:
+                        headers:
+                            0:
+                                plot:
+                                    heatmap:
+                                        scale: ordinal
+                                        color-scheme: category20
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_missing_view_in_dataset_link_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: .examples/data/oscars.csv
+                    links:
+                        link to non existing view:
+                            column: age
+                            table-row: some-non-existent-view/some-column
+            views:
+                table-a:
+                    dataset: table-a
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_missing_column_in_dataset_link_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: .examples/data/oscars.csv
+                    links:
+                        link from non existing column:
+                            column: non-existing-column
+                            view: other-table-a
+            views:
+                table-a:
+                    dataset: table-a
+                other-table-a:
+                    dataset: table-a
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_missing_column_in_linked_table_row_link_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: .examples/data/oscars.csv
+                    links:
+                        link to non existing column:
+                            column: age
+                            table-row: "other-table-a/non existing column"
+            views:
+                table-a:
+                    dataset: table-a
+                other-table-a:
+                    dataset: table-a
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_circular_table_link_config_validation() {
+        let raw_config = r#"
+            datasets:
+                variants:
+                    path: tests/data/variants.csv
+                genes:
+                    path: tests/data/genes.csv
+            views:
+                variants:
+                    dataset: variants
+                    render-table:
+                        columns:
+                            gene:
+                                link-to-table: genes
+                                link-column: symbol
+                genes:
+                    dataset: genes
+                    render-table:
+                        columns:
+                            symbol:
+                                link-to-table: variants
+                                link-column: gene
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        let error = config.validate().err().unwrap().to_string();
+        assert!(error.contains(r#"["genes", "variants", "genes"]"#));
+    }
+
+    #[test]
+    fn test_conflicting_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                table-a:
+                    dataset: table-a
+                    render-table:
+                        columns:
+                            some-column:
+                                optional: false
+                                plot:
+                                    ticks:
+                                        scale: linear
+                                ellipsis: 25
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_zero_histogram_bins_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                table-a:
+                    dataset: table-a
+                    render-table:
+                        columns:
+                            price:
+                                bins: 0
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+
+        let raw_config = raw_config.replace("bins: 0", "bins: 10");
+        let mut config: ItemsSpec = serde_yaml::from_str(&raw_config).unwrap();
+        assert!(config.validate().is_ok());
+        config.default_bins = Some(0);
+        assert_eq!(
+            config.validate().unwrap_err().to_string(),
+            "Given default number of histogram bins must be greater than 0."
+        );
+    }
+
+    #[test]
+    fn test_numeric_color_rules() {
+        let rules: Vec<ColorRule> = serde_yaml::from_str(
+            r#"
+- max: 0.05
+  color: red
+- min: 0.05
+  max: 0.95
+  color: white
+- min: 0.95
+  color: lime
+"#,
+        )
+        .unwrap();
+        assert_eq!(cell_color(&rules, "0.01"), Some("red"));
+        assert_eq!(cell_color(&rules, "0.05"), Some("white"));
+        assert_eq!(cell_color(&rules, "0.5"), Some("white"));
+        assert_eq!(cell_color(&rules, "0.95"), Some("lime"));
+        assert_eq!(cell_color(&rules, "1e3"), Some("lime"));
+        assert_eq!(cell_color(&rules, "NA"), None);
+    }
+
+    #[test]
+    fn test_nominal_color_rules() {
+        let rules: Vec<ColorRule> = serde_yaml::from_str(
+            r#"
+- value: pathogenic
+  color: red
+- value: benign
+  color: green
+"#,
+        )
+        .unwrap();
+        assert_eq!(cell_color(&rules, "pathogenic"), Some("red"));
+        assert_eq!(cell_color(&rules, "benign"), Some("green"));
+        assert_eq!(cell_color(&rules, "Benign"), None);
+        assert_eq!(cell_color(&rules, ""), None);
+    }
+
+    #[test]
+    fn test_color_rules_fallback() {
+        let rules = vec![
+            ColorRule {
+                min: Some(10.0),
+                max: None,
+                value: None,
+                color: String::from("orange"),
+            },
+            ColorRule {
+                min: None,
+                max: None,
+                value: None,
+                color: String::from("white"),
+            },
+        ];
+        assert_eq!(cell_color(&rules, "12"), Some("orange"));
+        assert_eq!(cell_color(&rules, "2"), Some("white"));
+        assert_eq!(cell_color(&rules, "unknown"), Some("white"));
+    }
+
+    #[test]
+    fn test_row_color_rules() {
+        let headers = ["gene", "qual"].map(String::from);
+        let color = |operator: &str, value: &str, row: [&str; 2]| {
+            let rules: Vec<RowColorRule> = serde_yaml::from_str(&format!(
+                "[{{column: qual, operator: {operator}, value: '{value}', color: red}}]"
+            ))
+            .unwrap();
+            RowColors::new(&rules, &headers)
+                .unwrap()
+                .color(&row.map(String::from))
+                .map(String::from)
+        };
+        let red = Some(String::from("red"));
+        assert_eq!(color("eq", "30", ["BRCA1", "30.0"]), red);
+        assert_eq!(color("eq", "PASS", ["BRCA1", "PASS"]), red);
+        assert_eq!(color("ne", "30", ["BRCA1", "30.0"]), None);
+        assert_eq!(color("ne", "30", ["BRCA1", "NA"]), red);
+        // Numbers are compared numerically, not lexicographically.
+        assert_eq!(color("lt", "30", ["BRCA1", "4"]), red);
+        assert_eq!(color("lt", "30", ["BRCA1", "NA"]), None);
+        assert_eq!(color("gt", "30", ["BRCA1", "100"]), red);
+        assert_eq!(color("gt", "30", ["BRCA1", "30"]), None);
+        assert_eq!(color("le", "30", ["BRCA1", "30"]), red);
+        assert_eq!(color("ge", "1e2", ["BRCA1", "99"]), None);
+        assert_eq!(color("contains", "Low", ["BRCA1", "LowQual"]), red);
+        assert_eq!(color("contains", "Low", ["BRCA1", "PASS"]), None);
+        assert_eq!(color("regex", "^Low[A-Z]", ["BRCA1", "LowQual"]), red);
+        assert_eq!(color("regex", "^Low[A-Z]", ["BRCA1", "Lowqual"]), None);
+
+        let rules: Vec<RowColorRule> = serde_yaml::from_str(
+            r#"
+- {column: gene, operator: eq, value: TP53, color: orange}
+- {column: qual, operator: ge, value: "0", color: white}
+"#,
+        )
+        .unwrap();
+        let row_colors = RowColors::new(&rules, &headers).unwrap();
+        assert_eq!(
+            row_colors.color(&["TP53", "3"].map(String::from)),
+            Some("orange")
+        );
+        assert_eq!(
+            row_colors.color(&["BRCA1", "3"].map(String::from)),
+            Some("white")
+        );
+    }
+
+    #[test]
+    fn test_invalid_row_color_rule_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                table-a:
+                    dataset: table-a
+                    render-table:
+                        row-color-rules:
+                            - column: first
+                              operator: regex
+                              value: "(Joh"
+                              color: red
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().starts_with(
+            r#"Invalid row color rule of view "table-a". Invalid regular expression "(Joh""#
+        ));
+
+        let config: ItemsSpec =
+            serde_yaml::from_str(&raw_config.replace("first", "unknown")).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_quote_character() {
+        let mut dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/escaped_quotes.csv"),
+            header_rows: 1,
+            header: true,
+            quote: Some(String::from("''")),
+            double_quote: true,
+            ..Default::default()
+        };
+        let error = dataset
+            .preprocess("quotes", &DownloadSettings::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("\"quotes\""));
+        dataset.quote = Some(String::from(","));
+        dataset.separator = Some(',');
+        assert!(dataset
+            .preprocess("quotes", &DownloadSettings::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_column_names_length_mismatch() {
+        let mut dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/headerless.csv"),
+            header_rows: 1,
+            header: true,
+            column_names: Some(vec![String::from("first"), String::from("last")]),
+            double_quote: true,
+            ..Default::default()
+        };
+        let error = dataset
+            .preprocess("headerless", &DownloadSettings::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("3 columns"));
+    }
+
+    #[test]
+    fn test_generated_column_names() {
+        let mut dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/headerless.tsv"),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        dataset
+            .preprocess("headerless", &DownloadSettings::default())
+            .unwrap();
+        assert_eq!(
+            dataset.reader().unwrap().headers().unwrap(),
+            vec!["column_1", "column_2", "column_3"]
+        );
+        assert_eq!(dataset.size().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_concatenated_dataset() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/samples/*.tsv\nsource-column: true").unwrap();
+        dataset
+            .preprocess("samples", &DownloadSettings::default())
+            .unwrap();
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["gene", "count", "__source__"]
+        );
+        assert_eq!(
+            reader
+                .records()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>(),
+            vec![
+                vec!["BRCA1", "3", "tests/data/samples/sample_a.tsv"],
+                vec!["TP53", "5", "tests/data/samples/sample_a.tsv"],
+                vec!["KRAS", "7", "tests/data/samples/sample_b.tsv"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concatenated_dataset_with_header_mismatch() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/mismatching_samples/*.csv").unwrap();
+        let error = dataset
+            .preprocess("samples", &DownloadSettings::default())
+            .err()
+            .unwrap();
+        assert!(error
+            .to_string()
+            .contains(r#"missing columns ["count"], unexpected columns ["reads"]"#));
+    }
+
+    #[test]
+    fn test_zero_max_categories_config_validation() {
+        let raw_config = r#"
+            datasets:
+                table-a:
+                    path: tests/data/uniform_datatypes.csv
+            views:
+                table-a:
+                    dataset: table-a
+                    render-table:
+                        columns:
+                            first:
+                                max-categories: 0
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        let error = config.validate().err().unwrap();
+        assert!(error.to_string().contains("max-categories"));
+
+        let raw_config = raw_config.replace("max-categories: 0", "max-categories: all");
+        let mut config: ItemsSpec = serde_yaml::from_str(&raw_config).unwrap();
+        assert!(config.validate().is_ok());
+        let render_table = config.views.get_mut("table-a").unwrap();
+        render_table.render_table.as_mut().unwrap().max_categories = Some(MaxCategories::Count(0));
+        assert!(config.validate().is_err());
+        assert!(serde_yaml::from_str::<MaxCategories>("some").is_err());
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/uniform_datatypes.csv").unwrap();
+        let render_table: RenderTableSpecs = serde_yaml::from_str(
+            r#"
+            include-columns: ["*st", "price"]
+            exclude-columns: [last]
+            "#,
+        )
+        .unwrap();
+        let selected = render_table.view_dataset(&dataset).unwrap().unwrap();
+        let mut reader = selected.reader().unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["first", "price"]);
+        assert_eq!(
+            reader.records().unwrap().next().unwrap().unwrap(),
+            vec!["Delia", "213541234881.3312"]
+        );
+        let column_types = classify_table(&selected).unwrap();
+        assert_eq!(
+            column_types.keys().sorted().collect_vec(),
+            vec!["first", "price"]
+        );
+
+        let render_table: RenderTableSpecs = serde_yaml::from_str("columns: {}").unwrap();
+        assert!(render_table.view_dataset(&dataset).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_number_format() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/locale_numbers.csv, separator: ';'}").unwrap();
+        let render_table: RenderTableSpecs = serde_yaml::from_str(
+            r#"
+            number-format:
+              decimal-separator: ","
+              thousands-separator: "."
+            columns:
+              us:
+                number-format:
+                  thousands-separator: ","
+            "#,
+        )
+        .unwrap();
+        let formatted = render_table.view_dataset(&dataset).unwrap().unwrap();
+        let column_types = classify_table(&formatted).unwrap();
+        assert_eq!(column_types.get("eu"), Some(&ColumnType::Float));
+        assert_eq!(column_types.get("us"), Some(&ColumnType::Float));
+        assert_eq!(
+            formatted
+                .number_format("us")
+                .map(|format| format.decimal_separator),
+            Some('.')
+        );
+
+        let european = formatted.number_format("eu").unwrap();
+        assert_eq!(
+            european.normalize("-1.234.567,8").as_deref(),
+            Some("-1234567.8")
+        );
+        assert_eq!(european.normalize("1.23,4"), None);
+        assert_eq!(european.normalize(".234"), None);
+    }
+
+    #[test]
+    fn test_missing_custom_css() {
+        let config_path = std::env::temp_dir().join("datavzrd-test-missing-custom-css.yaml");
+        fs::write(
+            &config_path,
+            "custom-css: [tests/style/missing.css]\ndatasets: {}\nviews: {}\n",
+        )
+        .unwrap();
+        let error = ItemsSpec::from_file(&config_path, &DownloadSettings::default()).unwrap_err();
+        fs::remove_file(&config_path).unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Could not find custom CSS file under given path "tests/style/missing.css"."#
+        );
+    }
+
+    #[test]
+    fn test_strip_units() {
+        let config = |min_numeric_fraction: f64| {
+            serde_yaml::from_str::<ItemsSpec>(&format!(
+                r#"
+                datasets:
+                  units:
+                    path: tests/data/units.csv
+                views:
+                  units:
+                    dataset: units
+                    render-table:
+                      columns:
+                        price:
+                          strip: ["$"]
+                        concentration:
+                          strip: {{regex: '\s*ng/µl$'}}
+                          min-numeric-fraction: {min_numeric_fraction}
+                "#
+            ))
+            .unwrap()
+        };
+        let specs = config(0.9);
+        specs.validate().unwrap();
+        let dataset = specs.views["units"]
+            .render_table
+            .as_ref()
+            .unwrap()
+            .view_dataset(&specs.datasets["units"])
+            .unwrap()
+            .unwrap();
+        let column_types = classify_table(&dataset).unwrap();
+        assert_eq!(column_types.get("price"), Some(&ColumnType::Float));
+        // Only three of the five values are numbers in ng/µl.
+        assert_eq!(column_types.get("concentration"), Some(&ColumnType::String));
+        assert_eq!(
+            specs.non_numeric_stripped_columns().unwrap(),
+            vec![("units".to_string(), "concentration".to_string(), 0.6)]
+        );
+
+        let specs = config(0.5);
+        assert!(specs.non_numeric_stripped_columns().unwrap().is_empty());
+        let error = config(1.5).validate().unwrap_err();
+        assert!(error.to_string().contains("min"));
+
+        let strip: Strip = serde_yaml::from_str(r#"["$", "USD"]"#).unwrap();
+        assert_eq!(strip.apply(" $ 12 USD"), "12");
+        assert!(serde_yaml::from_str::<Strip>("regex: '('").is_err());
+    }
+
+    #[test]
+    fn test_na_values() {
+        let config = |render_table: &str| {
+            serde_yaml::from_str::<ItemsSpec>(&format!(
+                r#"
+                datasets:
+                  samples:
+                    path: tests/data/na_tokens.csv
+                views:
+                  samples:
+                    dataset: samples
+                    render-table:
+                      {render_table}
+                "#
+            ))
+            .unwrap()
+        };
+        let specs = config("columns: {}");
+        let column_types = classify_table(&specs.datasets["samples"]).unwrap();
+        // Dots and dashes are no missing values by default.
+        assert_eq!(column_types.get("depth"), Some(&ColumnType::String));
+
+        let specs = config(
+            r#"na-values: ["NA", ".", "-"]
+                      columns:
+                        status:
+                          na-values: ["-"]"#,
+        );
+        specs.validate().unwrap();
+        let dataset = specs.views["samples"]
+            .render_table
+            .as_ref()
+            .unwrap()
+            .view_dataset(&specs.datasets["samples"])
+            .unwrap()
+            .unwrap();
+        let column_types = classify_table(&dataset).unwrap();
+        assert_eq!(column_types.get("depth"), Some(&ColumnType::Integer));
+        assert!(dataset.is_na("depth", "."));
+        assert!(dataset.is_na("status", "-"));
+        assert!(!dataset.is_na("status", "."));
+        assert!(!dataset.is_na("status", "NA"));
+        assert!(specs.datasets["samples"].is_na("status", "N/A"));
+    }
+
+    #[test]
+    fn test_tolerated_non_numeric_values() {
+        let config = |render_table: &str| {
+            serde_yaml::from_str::<ItemsSpec>(&format!(
+                r#"
+                datasets:
+                  scores:
+                    path: tests/data/mostly_numbers.csv
+                views:
+                  scores:
+                    dataset: scores
+                    render-table:
+                      {render_table}
+                "#
+            ))
+            .unwrap()
+        };
+        let column_type = |specs: &ItemsSpec| {
+            let dataset = specs.views["scores"]
+                .render_table
+                .as_ref()
+                .unwrap()
+                .view_dataset(&specs.datasets["scores"])
+                .unwrap()
+                .unwrap();
+            classify_table(&dataset).unwrap()["score"]
+        };
+        // Eight of the ten values are numbers.
+        let specs = config("min-numeric-fraction: 0.75");
+        specs.validate().unwrap();
+        assert_eq!(column_type(&specs), ColumnType::Float);
+        assert_eq!(
+            specs.tolerated_non_numeric_values().unwrap(),
+            vec![(
+                "scores".to_string(),
+                "score".to_string(),
+                2,
+                vec![(3, "oops".to_string()), (8, "n.d.".to_string())]
+            )]
+        );
+
+        let specs = config(
+            r#"min-numeric-fraction: 0.75
+                      columns:
+                        score:
+                          min-numeric-fraction: 0.9"#,
+        );
+        assert_eq!(column_type(&specs), ColumnType::String);
+        assert!(specs.tolerated_non_numeric_values().unwrap().is_empty());
+
+        let error = config("min-numeric-fraction: 1.5").validate().unwrap_err();
+        assert!(error.to_string().contains("must be between 0 and 1"));
+    }
+
+    #[test]
+    fn test_extends_config() {
+        let specs =
+            ItemsSpec::from_file("tests/extends/samples.yaml", &DownloadSettings::default())
+                .unwrap();
+        // The most derived config wins over its parent and grandparent.
+        assert_eq!(specs.report_name, "Sample report");
+        assert_eq!(
+            specs.views["scores"].description.as_deref(),
+            Some("Scores of all samples.")
+        );
+        assert_eq!(specs.max_in_memory_rows, 1000);
+        // Settings and views only given by ancestors are kept.
+        assert_eq!(specs.theme, Theme::Dark);
+        assert!(specs.views["groups"].hidden);
+        assert!(specs.datasets.contains_key("scores"));
+        assert_eq!(specs.extends, None);
+        assert_eq!(
+            extended_configs(Path::new("tests/extends/samples.yaml")).unwrap(),
+            vec![
+                fs::canonicalize("tests/extends/analysis.yaml").unwrap(),
+                fs::canonicalize("tests/extends/base.yaml").unwrap(),
+            ]
+        );
+
+        let error =
+            ItemsSpec::from_file("tests/extends/cycle_a.yaml", &DownloadSettings::default())
+                .unwrap_err()
+                .to_string();
+        assert!(error.starts_with("Configs extending each other form a cycle"));
+        assert!(error.contains("cycle_a.yaml") && error.contains("cycle_b.yaml"));
+    }
+
+    #[test]
+    fn test_substitute_env_vars() {
+        env::set_var("DATAVZRD_TEST_BASE", "tests");
+        env::set_var("DATAVZRD_TEST_SUBDIR", "data");
+        env::set_var(
+            "DATAVZRD_TEST_DESCRIPTION",
+            "p-values: <0.05 # [adjusted] {'all'} \"x\"",
+        );
+        let config = substitute_env_vars(
+            r#"
+            datasets:
+              scores:
+                path: ${DATAVZRD_TEST_BASE}/${DATAVZRD_TEST_SUBDIR}/stats.csv
+            views:
+              scores:
+                dataset: scores
+                desc: ${DATAVZRD_TEST_DESCRIPTION}
+            "#,
+        )
+        .unwrap();
+        let specs: ItemsSpec = serde_yaml::from_str(&config).unwrap();
+        assert_eq!(
+            specs.datasets["scores"].path,
+            PathBuf::from("tests/data/stats.csv")
+        );
+        assert_eq!(
+            specs.views["scores"].description.as_deref(),
+            Some("p-values: <0.05 # [adjusted] {'all'} \"x\"")
+        );
+
+        // Configs without references are kept as they are.
+        let config = "views: {}  # no ${} references\n";
+        assert_eq!(substitute_env_vars(config).unwrap(), config);
+
+        let error = substitute_env_vars("name: ${DATAVZRD_TEST_UNDEFINED}/report").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Environment variable 'DATAVZRD_TEST_UNDEFINED' referenced in config is not set"
+        );
+    }
+
+    #[test]
+    fn test_classify_rows_deserialization() {
+        let dataset = |classify_rows: &str| {
+            serde_yaml::from_str::<DatasetSpecs>(&format!(
+                "{{path: tests/data/stats.csv, classify-rows: {classify_rows}}}"
+            ))
+        };
+        assert_eq!(dataset("all").unwrap().classify_rows, ClassifyRows::All);
+        assert_eq!(
+            dataset("500").unwrap().classify_rows,
+            ClassifyRows::First(500)
+        );
+        assert!(dataset("0").is_err());
+        assert!(dataset("some").is_err());
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/stats.csv").unwrap();
+        assert_eq!(dataset.classify_rows.limit(), Some(10000));
+    }
+
+    #[test]
+    fn test_config_schema() {
+        let schema: serde_json::Value = serde_json::from_str(&config_schema().unwrap()).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        for path in [
+            "tests/display_name.yaml",
+            "tests/dark.yaml",
+            "tests/csv_download.yaml",
+            "tests/cross-links.yaml",
+            "tests/images.yaml",
+        ] {
+            let config: serde_json::Value =
+                serde_yaml::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+            assert!(
+                validator.is_valid(&config),
+                "{path} does not match the schema"
+            );
+        }
+        let config = serde_json::json!({"datasets": {}, "views": {}, "unknown-keyword": true});
+        assert!(!validator.is_valid(&config));
+        // Field descriptions are taken from the doc comments.
+        assert!(schema
+            .to_string()
+            .contains("Color theme of the rendered pages and plots."));
+    }
+
+    #[test]
+    fn test_heatmap_with_middle_color() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/type_overrides.csv").unwrap();
+        let mut heatmap: Heatmap =
+            serde_yaml::from_str("{scale: linear, range: [white, orange, red]}").unwrap();
+        heatmap.preprocess(&dataset, "id").unwrap();
+        // The middle color is placed halfway between the minimum and the maximum.
+        assert_eq!(heatmap.domain.unwrap(), vec!["123", "456", "789"]);
+
+        let mut heatmap: Heatmap =
+            serde_yaml::from_str("{scale: linear, range: [white, orange, red], domain-mid: 200}")
+                .unwrap();
+        heatmap.preprocess(&dataset, "id").unwrap();
+        assert_eq!(heatmap.domain.unwrap(), vec!["123", "200", "789"]);
+    }
+
+    #[test]
+    fn test_column_type_overrides() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/type_overrides.csv").unwrap();
+        let column_types = classify_table(&dataset).unwrap();
+        assert_eq!(column_types.get("id"), Some(&ColumnType::Integer));
+        assert_eq!(column_types.get("count"), Some(&ColumnType::String));
+
+        let render_table: RenderTableSpecs = serde_yaml::from_str(
+            r#"
+            columns:
+              id:
+                type: string
+              count:
+                type: integer
+            "#,
+        )
+        .unwrap();
+        let overridden = render_table.view_dataset(&dataset).unwrap().unwrap();
+        let column_types = classify_table(&overridden).unwrap();
+        assert_eq!(column_types.get("id"), Some(&ColumnType::String));
+        assert_eq!(column_types.get("count"), Some(&ColumnType::Integer));
+        assert_eq!(column_types.get("label"), Some(&ColumnType::String));
+
+        let error =
+            serde_yaml::from_str::<RenderTableSpecs>("columns: {id: {type: text}}").unwrap_err();
+        assert!(error.to_string().contains("unknown variant `text`"));
+        assert!(serde_yaml::from_str::<RenderTableSpecs>("columns: {id: {type: none}}").is_err());
+    }
+
+    #[test]
+    fn test_empty_columns() {
+        let specs: ItemsSpec = serde_yaml::from_str(
+            r#"
+            datasets:
+              samples:
+                path: tests/data/empty_column.csv
+            views:
+              samples:
+                dataset: samples
+                render-table:
+                  columns: {}
+            "#,
+        )
+        .unwrap();
+        let dataset = &specs.datasets["samples"];
+        assert_eq!(
+            classify_table(dataset).unwrap()["comment"],
+            ColumnType::None
+        );
+        assert_eq!(
+            specs
+                .empty_columns(&specs.column_diagnostics().unwrap())
+                .unwrap(),
+            vec![("samples".to_string(), "comment".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_missing_images() {
+        let specs =
+            ItemsSpec::from_file("tests/local_images.yaml", &DownloadSettings::default()).unwrap();
+        assert_eq!(
+            specs.missing_images().unwrap(),
+            vec![(
+                "local-images".to_string(),
+                "image".to_string(),
+                vec![PathBuf::from("tests/images/missing.png")]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_numeric_type_override_with_other_values() {
+        let specs: ItemsSpec = serde_yaml::from_str(
+            r#"
+            datasets:
+              samples:
+                path: tests/data/type_overrides.csv
+            views:
+              samples:
+                dataset: samples
+                render-table:
+                  columns:
+                    id:
+                      type: string
+                    count:
+                      type: float
+            "#,
+        )
+        .unwrap();
+        let dataset = specs.views["samples"]
+            .render_table
+            .as_ref()
+            .unwrap()
+            .view_dataset(&specs.datasets["samples"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            classify_table(&dataset).unwrap()["count"],
+            ColumnType::Float
+        );
+        // The value that is no number is reported since it is plotted as missing.
+        assert_eq!(
+            specs.tolerated_non_numeric_values().unwrap(),
+            vec![(
+                "samples".to_string(),
+                "count".to_string(),
+                1,
+                vec![(2, "unknown".to_string())]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_filter_rows() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/uniform_datatypes.csv").unwrap();
+        let render_table: RenderTableSpecs = serde_yaml::from_str(
+            r#"
+            filter: price > 0 && first != "Winnie"
+            exclude-columns: [price]
+            "#,
+        )
+        .unwrap();
+        let filtered = render_table.view_dataset(&dataset).unwrap().unwrap();
+        assert_eq!(filtered.size().unwrap(), 2);
+        assert_eq!(
+            filtered
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect_vec(),
+            vec![
+                vec!["Delia", "Arnold", "201413583792611"],
+                vec!["George", "Evans", "344869145219971"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample_rows() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/many_categories.csv").unwrap();
+        let records = dataset.size().unwrap();
+        let render_table: RenderTableSpecs = serde_yaml::from_str("sample: 10\nseed: 42").unwrap();
+        let sampled = render_table.sampled_dataset(&dataset, records).unwrap();
+        let sample = sampled
+            .reader()
+            .unwrap()
+            .records()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect_vec();
+        assert_eq!(sample.len(), 10);
+        // The sample is reproducible and retains the order of the original records.
+        assert_eq!(
+            sample,
+            sampled
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect_vec()
+        );
+        let all = dataset
+            .reader()
+            .unwrap()
+            .records()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect_vec();
+        let positions = sample
+            .iter()
+            .map(|record| all.iter().position(|other| other == record).unwrap())
+            .collect_vec();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Datasets that are not larger than the sample are rendered completely.
+        let render_table: RenderTableSpecs =
+            serde_yaml::from_str(&format!("sample: {records}")).unwrap();
+        assert!(render_table.sampled_dataset(&dataset, records).is_none());
     }
 
     #[test]
-    fn test_valid_config_validation() {
-        let config = ItemsSpec::from_file(".examples/example-config.yaml").unwrap();
-        assert!(config.validate().is_ok());
+    fn test_separator_deserialization() {
+        let separator = |config: &str| {
+            serde_yaml::from_str::<DatasetSpecs>(&format!("path: table.tsv\n{config}"))
+                .map(|dataset| dataset.separator)
+        };
+        assert_eq!(separator("separator: ';'").unwrap(), Some(';'));
+        assert_eq!(separator("separator: \"\\t\"").unwrap(), Some('\t'));
+        assert_eq!(separator(r"separator: \t").unwrap(), Some('\t'));
+        assert_eq!(separator(r"separator: \s").unwrap(), Some(' '));
+        assert_eq!(separator("separator: Tab").unwrap(), Some('\t'));
+        assert_eq!(separator("separator: comma").unwrap(), Some(','));
+        assert_eq!(separator("separator: semicolon").unwrap(), Some(';'));
+        assert_eq!(separator("headers: 1").unwrap(), None);
+        for invalid in ["'::'", "§", "tabs"] {
+            let error = separator(&format!("separator: {invalid}")).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("expected a single ASCII character"));
+        }
     }
 
     #[test]
-    fn test_missing_dataset_config_validation() {
-        let raw_config = r#"
-            datasets:
-                table-a:
-                    path: tests/data/uniform_datatypes.csv
-            views:
-                plot-b:
-                    dataset: table-b
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
+    fn test_detect_utf16_encoding() {
+        let mut dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/utf16.csv").unwrap();
+        dataset
+            .preprocess("cities", &DownloadSettings::default())
+            .unwrap();
+        assert_eq!(dataset.encoding.as_deref(), Some("utf-16le"));
+        assert_eq!(dataset.separator, Some(','));
+        assert_eq!(
+            dataset.reader().unwrap().headers().unwrap(),
+            vec!["name", "city", "count"]
+        );
     }
 
     #[test]
-    fn test_missing_default_view_config_validation() {
-        let raw_config = r#"
-            default-view: table-b
-            datasets:
-                table-a:
-                    path: tests/data/uniform_datatypes.csv
-            views:
-                table-a:
-                    dataset: table-a
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
-    }
+    fn test_inline_data() {
+        let preprocessed = |config: &str| {
+            let mut dataset: DatasetSpecs = serde_yaml::from_str(config).unwrap();
+            dataset
+                .preprocess("thresholds", &DownloadSettings::default())
+                .map(|_| dataset)
+        };
+        let records = preprocessed(
+            r#"
+            data:
+                - {gene: TP53, threshold: 0.5}
+                - {gene: BRCA1, threshold: 2, note: "germline"}
+                - {gene: KRAS, threshold: null}
+            "#,
+        )
+        .unwrap();
+        let mut reader = records.reader().unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["gene", "threshold", "note"]);
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![
+                vec!["TP53", "0.5", ""],
+                vec!["BRCA1", "2", "germline"],
+                vec!["KRAS", "", ""]
+            ]
+        );
+        assert_eq!(
+            classify_table(&records).unwrap().get("threshold"),
+            Some(&ColumnType::Float)
+        );
 
-    #[test]
-    fn test_value_outside_domain_config_validation() {
-        let raw_config = r#"
-            datasets:
-                oscars:
-                    path: ".examples/data/oscars.csv"
-            views:
-                oscars:
-                    dataset: oscars
-                    render-table:
-                        columns:
-                            age:
-                                plot:
-                                    ticks:
-                                        scale: linear
-                                        domain:
-                                            - 50
-                                            - 60
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
-    }
+        // Rows are read like the lines of a delimited file.
+        let rows = preprocessed(
+            r#"
+            column-names: [gene, threshold]
+            data:
+                - [TP53, 0.5]
+                - [BRCA1, 2]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rows.size().unwrap(), 2);
+        assert_eq!(
+            classify_table(&rows).unwrap().get("threshold"),
+            Some(&ColumnType::Float)
+        );
 
-    #[test]
-    fn test_log_scale_includes_zero_config_validation() {
-        let raw_config = r#"
-            datasets:
-                oscars:
-                    path: ".examples/data/oscars.csv"
-            views:
-                oscars:
-                    dataset: oscars
-                    render-table:
-                        columns:
-                            age:
-                                plot:
-                                    ticks:
-                                        scale: log
-                                        domain:
-                                            - 0
-                                            - 100
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
+        let error =
+            preprocessed("path: tests/data/uniform_datatypes.csv\ndata: [[a], [1]]").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Dataset "thresholds" must either be given a path or inline data but not both."#
+        );
+        assert!(preprocessed("separator: ','").is_err());
+        assert!(preprocessed("data: []").is_err());
     }
 
     #[test]
-    fn test_customize_first_header_row_view_config_validation() {
-        let raw_config = r#"
+    fn test_invalid_filter_config_validation() {
+        let validate = |filter: &str| {
+            let raw_config = format!(
+                r#"
             datasets:
                 table-a:
                     path: tests/data/uniform_datatypes.csv
@@ -1677,77 +5339,99 @@ mod tests {
                 table-a:
                     dataset: table-a
                     render-table:
-                        headers:
-                            0:
-                                plot:
-                                    heatmap:
-                                        scale: ordinal
-                                        color-scheme: category20
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
-    }
-
-    #[test]
-    fn test_missing_view_in_dataset_link_config_validation() {
-        let raw_config = r#"
-            datasets:
-                table-a:
-                    path: .examples/data/oscars.csv
-                    links:
-                        link to non existing view:
-                            column: age
-                            table-row: some-non-existent-view/some-column
-            views:
-                table-a:
-                    dataset: table-a
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
+                        filter: '{filter}'
+            "#
+            );
+            let config: ItemsSpec = serde_yaml::from_str(&raw_config).unwrap();
+            config.validate().err().unwrap().to_string()
+        };
+        assert_eq!(
+            validate("depth > 10"),
+            r#"Invalid filter expression of view "table-a". Filter expression refers to unknown column "depth"."#
+        );
+        assert!(validate("price >")
+            .starts_with(r#"Invalid filter expression of view "table-a". Could not parse"#));
     }
 
     #[test]
-    fn test_missing_column_in_dataset_link_config_validation() {
-        let raw_config = r#"
-            datasets:
-                table-a:
-                    path: .examples/data/oscars.csv
-                    links:
-                        link from non existing column:
-                            column: non-existing-column
-                            view: other-table-a
-            views:
-                table-a:
-                    dataset: table-a
-                other-table-a:
-                    dataset: table-a
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
+    fn test_computed_columns() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/uniform_datatypes.csv").unwrap();
+        let render_table: RenderTableSpecs = serde_yaml::from_str(
+            r#"
+            computed-columns:
+                name: concat(last, ", ", first)
+                undefined: price / (price - price)
+                loss: 0 - `price`
+            filter: loss > 0 || first == "Delia"
+            include-columns: [name, undefined]
+            "#,
+        )
+        .unwrap();
+        let computed = render_table.view_dataset(&dataset).unwrap().unwrap();
+        assert_eq!(
+            computed.reader().unwrap().headers().unwrap(),
+            vec!["name", "undefined"]
+        );
+        assert_eq!(
+            computed
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect_vec(),
+            vec![vec!["Arnold, Delia", ""], vec!["Castro, George", ""]]
+        );
+        // Each of the four records has an undefined value due to the division by zero.
+        assert_eq!(computed.invalid_computed_values().unwrap(), 4);
+        let column_types = classify_table(&computed).unwrap();
+        assert_eq!(column_types.get("name"), Some(&ColumnType::String));
     }
 
     #[test]
-    fn test_missing_column_in_linked_table_row_link_config_validation() {
-        let raw_config = r#"
+    fn test_computed_column_config_validation() {
+        let validate = |name: &str, expression: &str| {
+            let raw_config = format!(
+                r#"
             datasets:
                 table-a:
-                    path: .examples/data/oscars.csv
-                    links:
-                        link to non existing column:
-                            column: age
-                            table-row: "other-table-a/non existing column"
+                    path: tests/data/uniform_datatypes.csv
             views:
                 table-a:
                     dataset: table-a
-                other-table-a:
-                    dataset: table-a
-            "#;
-        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
+                    render-table:
+                        computed-columns:
+                            {name}: '{expression}'
+                        columns:
+                            {name}:
+                                plot:
+                                    ticks:
+                                        scale: linear
+            "#
+            );
+            let config: ItemsSpec = serde_yaml::from_str(&raw_config).unwrap();
+            config.validate().err().map(|error| error.to_string())
+        };
+        assert_eq!(validate("cents", "price * 100"), None);
+        assert_eq!(
+            validate("price", "price * 100"),
+            Some(
+                r#"Computed column "price" of view "table-a" has the same name as an existing column."#
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            validate("cents", "cost * 100"),
+            Some(
+                r#"Invalid computed column of view "table-a". Expression refers to unknown column "cost"."#
+                    .to_string()
+            )
+        );
     }
 
     #[test]
-    fn test_conflicting_config_validation() {
+    fn test_unmatched_column_filter_config_validation() {
         let raw_config = r#"
             datasets:
                 table-a:
@@ -1756,16 +5440,14 @@ mod tests {
                 table-a:
                     dataset: table-a
                     render-table:
-                        columns:
-                            some-column:
-                                optional: false
-                                plot:
-                                    ticks:
-                                        scale: linear
-                                ellipsis: 25
+                        exclude-columns: [last, "zip*"]
             "#;
         let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
-        assert!(config.validate().is_err());
+        let error = config.validate().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Column filter "zip*" of view "table-a" does not match any column of its dataset."#
+        );
     }
 
     #[test]
@@ -1822,12 +5504,16 @@ mod tests {
                     dataset: table-a
             "#;
         let err = serde_yaml::from_str::<ItemsSpec>(raw_config).unwrap_err();
-        assert_eq!(err.to_string(), "unknown field `non-existing-keyword`, expected one of `name`, `datasets`, `default-view`, `max-in-memory-rows`, `views`, `aux-libraries`, `webview-controls` at line 5 column 13");
+        assert_eq!(err.to_string(), "unknown field `non-existing-keyword`, expected one of `name`, `datasets`, `default-view`, `max-in-memory-rows`, `views`, `aux-libraries`, `webview-controls`, `single-file`, `theme`, `custom-css`, `default-bins`, `extends` at line 5 column 13");
     }
 
     #[test]
     fn test_config_preprocessing() {
-        let config = ItemsSpec::from_file(".examples/example-config.yaml").unwrap();
+        let config = ItemsSpec::from_file(
+            ".examples/example-config.yaml",
+            &DownloadSettings::default(),
+        )
+        .unwrap();
         let oscar_config = &config
             .views
             .get("oscars")
@@ -1847,6 +5533,40 @@ mod tests {
             custom_plot: None,
             ellipsis: None,
             plot_view_legend: Some(false),
+            bins: None,
+            log_scale: Some(false),
+            scale: None,
+            date_format: None,
+            input_date_format: None,
+            date_granularity: None,
+            max_categories: None,
+            categorical: None,
+            nominal_sort: NominalSort::ByCount,
+            color_rules: vec![],
+            link_to_table: None,
+            link_column: None,
+            scatter: None,
+            boxplot: false,
+            violin: false,
+            custom_vega_spec: None,
+            url_column: false,
+            url_truncate_length: None,
+            image_column: false,
+            thumbnail_height: None,
+            thumbnail_width: None,
+            true_values: vec![],
+            false_values: vec![],
+            format: None,
+            percent_as_fraction: false,
+            natural_sort: false,
+            filterable: true,
+            range_filter: true,
+            number_format: None,
+            column_type: None,
+            strip: None,
+            min_numeric_fraction: None,
+            na_values: None,
+            value_colors: None,
             label: None,
             spell: None,
         };
@@ -1861,6 +5581,40 @@ mod tests {
             custom_plot: None,
             ellipsis: None,
             plot_view_legend: Some(false),
+            bins: None,
+            log_scale: Some(false),
+            scale: None,
+            date_format: None,
+            input_date_format: None,
+            date_granularity: None,
+            max_categories: None,
+            categorical: None,
+            nominal_sort: NominalSort::ByCount,
+            color_rules: vec![],
+            link_to_table: None,
+            link_column: None,
+            scatter: None,
+            boxplot: false,
+            violin: false,
+            custom_vega_spec: None,
+            url_column: false,
+            url_truncate_length: None,
+            image_column: false,
+            thumbnail_height: None,
+            thumbnail_width: None,
+            true_values: vec![],
+            false_values: vec![],
+            format: None,
+            percent_as_fraction: false,
+            natural_sort: false,
+            filterable: true,
+            range_filter: true,
+            number_format: None,
+            column_type: None,
+            strip: None,
+            min_numeric_fraction: None,
+            na_values: None,
+            value_colors: None,
             label: None,
             spell: None,
         };
@@ -1935,6 +5689,40 @@ mod tests {
             custom_plot: None,
             ellipsis: None,
             plot_view_legend: Some(false),
+            bins: None,
+            log_scale: Some(false),
+            scale: None,
+            date_format: None,
+            input_date_format: None,
+            date_granularity: None,
+            max_categories: None,
+            categorical: None,
+            nominal_sort: NominalSort::ByCount,
+            color_rules: vec![],
+            link_to_table: None,
+            link_column: None,
+            scatter: None,
+            boxplot: false,
+            violin: false,
+            custom_vega_spec: None,
+            url_column: false,
+            url_truncate_length: None,
+            image_column: false,
+            thumbnail_height: None,
+            thumbnail_width: None,
+            true_values: vec![],
+            false_values: vec![],
+            format: None,
+            percent_as_fraction: false,
+            natural_sort: false,
+            filterable: true,
+            range_filter: true,
+            number_format: None,
+            column_type: None,
+            strip: None,
+            min_numeric_fraction: None,
+            na_values: None,
+            value_colors: None,
             spell: None,
         };
         let expected_item_specs = ItemSpecs {
@@ -1964,6 +5752,23 @@ mod tests {
                 ]),
                 additional_columns: None,
                 headers: None,
+                include_columns: None,
+                exclude_columns: vec![],
+                filter: None,
+                computed_columns: IndexMap::new(),
+                correlation_matrix: false,
+                sample: None,
+                seed: None,
+                missing_threshold: 0.001,
+                row_color_rules: vec![],
+                embed_full_csv: false,
+                default_sort_column: None,
+                default_sort_direction: SortDirection::Asc,
+                sort_keys: Vec::new(),
+                number_format: None,
+                na_values: None,
+                min_numeric_fraction: None,
+                max_categories: None,
             }),
             render_plot: None,
             render_html: None,
@@ -1977,7 +5782,11 @@ mod tests {
 
     #[test]
     fn test_dataset_size() {
-        let config = ItemsSpec::from_file(".examples/example-config.yaml").unwrap();
+        let config = ItemsSpec::from_file(
+            ".examples/example-config.yaml",
+            &DownloadSettings::default(),
+        )
+        .unwrap();
         assert_eq!(config.datasets.get("movies").unwrap().size().unwrap(), 184);
     }
 
@@ -1985,10 +5794,10 @@ mod tests {
     fn test_dataset_size_with_json() {
         let dataset = DatasetSpecs {
             path: PathBuf::from("tests/data/uniform_datatypes.json"),
-            separator: ',',
+            separator: Some(','),
             header_rows: 1,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
         assert_eq!(dataset.size().unwrap(), 4);
     }
@@ -1997,17 +5806,21 @@ mod tests {
     fn test_dataset_empty() {
         let empty_dataset = DatasetSpecs {
             path: PathBuf::from("tests/data/empty_table.csv"),
-            separator: ',',
+            separator: Some(','),
             header_rows: 4,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
         assert!(empty_dataset.is_empty().unwrap());
     }
 
     #[test]
     fn test_dataset_unique_column_values() {
-        let config = ItemsSpec::from_file(".examples/example-config.yaml").unwrap();
+        let config = ItemsSpec::from_file(
+            ".examples/example-config.yaml",
+            &DownloadSettings::default(),
+        )
+        .unwrap();
         let unique_column_values = config
             .datasets
             .get("oscars")
@@ -2017,4 +5830,212 @@ mod tests {
         assert_eq!(unique_column_values.get("oscar_yr").unwrap(), &91_usize);
         assert_eq!(unique_column_values.get("award").unwrap(), &2_usize);
     }
+
+    #[test]
+    fn test_sorted_dataset() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/stats.csv").unwrap();
+        let names = |render_table: &RenderTableSpecs| {
+            let sorted = render_table.sorted_dataset(&dataset).unwrap().unwrap();
+            sorted
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(|record| record.unwrap()[0].to_owned())
+                .collect_vec()
+        };
+        let mut render_table: RenderTableSpecs =
+            serde_yaml::from_str("default-sort-column: score").unwrap();
+        // Missing values come last and ties keep their order.
+        assert_eq!(
+            names(&render_table),
+            vec!["B", "A", "F", "E", "D", "C", "G"]
+        );
+        render_table.default_sort_direction = SortDirection::Desc;
+        assert_eq!(
+            names(&render_table),
+            vec!["D", "E", "A", "F", "B", "C", "G"]
+        );
+
+        // The sorted records are read from a plain csv file instead of sorting them again.
+        let sorted = render_table.sorted_dataset(&dataset).unwrap().unwrap();
+        assert_ne!(sorted.path, dataset.path);
+        assert_eq!(sorted.separator, Some(','));
+
+        render_table.default_sort_column = None;
+        assert!(render_table.sorted_dataset(&dataset).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/ranked_genes.csv").unwrap();
+        let render_table: RenderTableSpecs =
+            serde_yaml::from_str("sort-keys: [{column: pvalue}, {column: gene, direction: desc}]")
+                .unwrap();
+        let sorted = render_table.sorted_dataset(&dataset).unwrap().unwrap();
+        let genes = sorted
+            .reader()
+            .unwrap()
+            .records()
+            .unwrap()
+            .map(|record| record.unwrap()[0].to_owned())
+            .collect_vec();
+        // Genes break ties of p-values, including those of the missing ones, which come last.
+        assert_eq!(
+            genes,
+            vec![
+                "MYC", "KIT", "BRAF", "ALK", "TP53", "KRAS", "JAK2", "BRCA1", "ATM", "PTEN",
+                "NRAS", "NF1", "ERBB2", "EGFR", "BRCA2", "RB1", "IDH1", "CDH1", "VHL", "APC"
+            ]
+        );
+
+        // A default sort column is the first key.
+        let render_table: RenderTableSpecs =
+            serde_yaml::from_str("{default-sort-column: pvalue, sort-keys: [{column: gene}]}")
+                .unwrap();
+        assert_eq!(
+            render_table.sort_keys(),
+            vec![
+                SortKey {
+                    column: "pvalue".to_string(),
+                    direction: SortDirection::Asc,
+                    order: Vec::new(),
+                    natural: false
+                },
+                SortKey {
+                    column: "gene".to_string(),
+                    direction: SortDirection::Asc,
+                    order: Vec::new(),
+                    natural: false
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_sort_order() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/severities.csv").unwrap();
+        let render_table: RenderTableSpecs = serde_yaml::from_str(
+            "
+            default-sort-column: severity
+            columns:
+                severity:
+                    nominal-sort: [Low, Medium, High, Critical]
+            ",
+        )
+        .unwrap();
+        let sorted = render_table.sorted_dataset(&dataset).unwrap().unwrap();
+        let findings = sorted
+            .reader()
+            .unwrap()
+            .records()
+            .unwrap()
+            .map(|record| record.unwrap()[0].to_owned())
+            .collect_vec();
+        // Values missing from the order follow it, and missing values come last.
+        assert_eq!(
+            findings,
+            vec!["f3", "f10", "f1", "f4", "f7", "f2", "f9", "f5", "f8", "f6"]
+        );
+
+        let raw_config = r#"
+            datasets:
+                findings:
+                    path: tests/data/severities.csv
+            views:
+                findings:
+                    dataset: findings
+                    render-table:
+                        columns:
+                            severity:
+                                nominal-sort: [Low, High, Low]
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert_eq!(
+            config.validate().unwrap_err().to_string(),
+            r#"Value "Low" is listed multiple times in the nominal sort of column "severity" of view "findings"."#
+        );
+    }
+
+    #[test]
+    fn test_natural_sort() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/sample_ids.csv").unwrap();
+        let samples = |natural_sort: bool| {
+            let render_table: RenderTableSpecs = serde_yaml::from_str(&format!(
+                "{{default-sort-column: sample, columns: {{sample: {{natural-sort: {natural_sort}}}}}}}"
+            ))
+            .unwrap();
+            let sorted = render_table.sorted_dataset(&dataset).unwrap().unwrap();
+            sorted
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(|record| record.unwrap()[0].to_owned())
+                .collect_vec()
+        };
+        assert_eq!(
+            samples(true),
+            vec!["sample1", "Sample2", "sample2", "sample3", "sample10", "sample21"]
+        );
+        assert_eq!(
+            samples(false),
+            vec!["Sample2", "sample1", "sample10", "sample2", "sample21", "sample3"]
+        );
+    }
+
+    #[test]
+    fn test_missing_sort_column_config_validation() {
+        let raw_config = r#"
+            datasets:
+                scores:
+                    path: tests/data/stats.csv
+            views:
+                scores:
+                    dataset: scores
+                    render-table:
+                        default-sort-column: p-value
+            "#;
+        let config: ItemsSpec = serde_yaml::from_str(raw_config).unwrap();
+        assert!(config.validate().is_err());
+
+        let raw_config = raw_config.replace(
+            "default-sort-column: p-value",
+            "sort-keys: [{column: score}, {column: p-value}]",
+        );
+        let config: ItemsSpec = serde_yaml::from_str(&raw_config).unwrap();
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("'p-value'"));
+    }
+
+    #[test]
+    fn test_ambiguous_column_config() {
+        let config_path = std::env::temp_dir().join("datavzrd-test-ambiguous-column.yaml");
+        let read_config = |column: &str| {
+            fs::write(
+                &config_path,
+                format!(
+                    "datasets:\n  samples:\n    path: tests/data/duplicate_header.csv\n    \
+                     separator: ','\n    dedup-headers: true\nviews:\n  samples:\n    \
+                     dataset: samples\n    render-table:\n      columns:\n        \
+                     {column}:\n          display-mode: detail\n"
+                ),
+            )
+            .unwrap();
+            ItemsSpec::from_file(&config_path, &DownloadSettings::default())
+        };
+        let error = read_config("sample").unwrap_err();
+        assert!(error.to_string().contains("ambiguous"));
+        let config = read_config("sample_2").unwrap();
+        fs::remove_file(&config_path).unwrap();
+        assert!(config.views["samples"]
+            .render_table
+            .as_ref()
+            .unwrap()
+            .columns
+            .contains_key("sample_2"));
+    }
 }
@@ -6,12 +6,13 @@ use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
 use pyo3::types::PyModule;
 use reqwest::blocking::get;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all(deserialize = "kebab-case"), deny_unknown_fields)]
 pub(crate) struct SpellSpec {
     pub(crate) url: String,
@@ -118,7 +119,7 @@ fn call_process_yaml(template: &str, variables: HashMap<String, String>) -> Resu
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::spec::{Color, ColorRange, Heatmap, PlotSpec, ScaleType};
+    use crate::spec::{Color, ColorRange, Heatmap, NominalSort, PlotSpec, ScaleType};
 
     #[test]
     fn call_process_yaml_valid_input() {
@@ -191,6 +192,40 @@ mod tests {
             custom_plot: None,
             ellipsis: None,
             plot_view_legend: None,
+            bins: None,
+            log_scale: None,
+            scale: None,
+            date_format: None,
+            input_date_format: None,
+            date_granularity: None,
+            max_categories: None,
+            categorical: None,
+            nominal_sort: NominalSort::ByCount,
+            color_rules: vec![],
+            link_to_table: None,
+            link_column: None,
+            scatter: None,
+            boxplot: false,
+            violin: false,
+            custom_vega_spec: None,
+            url_column: false,
+            url_truncate_length: None,
+            image_column: false,
+            thumbnail_height: None,
+            thumbnail_width: None,
+            true_values: vec![],
+            false_values: vec![],
+            format: None,
+            percent_as_fraction: false,
+            natural_sort: false,
+            filterable: true,
+            range_filter: true,
+            number_format: None,
+            column_type: None,
+            strip: None,
+            min_numeric_fraction: None,
+            na_values: None,
+            value_colors: None,
             spell: None,
         };
         assert_eq!(result, expected);
@@ -0,0 +1,168 @@
+use crate::utils::column_type::ColumnType;
+use crate::utils::reader::{TableReader, TableReaderError};
+use anyhow::{bail, Context, Result};
+use arrow_array::RecordBatch;
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_ipc::reader::{FileReader, StreamReader};
+use arrow_schema::{ArrowError, DataType, SchemaRef};
+use itertools::Itertools;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Format of timestamps, matching the one used for parquet files.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Reads all record batches of the given Arrow IPC file, which is also the format of Feather
+/// files. Column types are taken from the schema and nested columns are rejected.
+pub(crate) fn read_arrow(path: &Path) -> Result<TableReader> {
+    let open = || File::open(path).context(format!("Could not open table {path:?}."));
+    type Batches = Box<dyn Iterator<Item = Result<RecordBatch, ArrowError>>>;
+    // Files written in the streaming format lack the footer of the file format.
+    let (schema, batches): (SchemaRef, Batches) = match FileReader::try_new(open()?, None) {
+        Ok(reader) => (reader.schema(), Box::new(reader)),
+        Err(_) => {
+            let reader = StreamReader::try_new(BufReader::new(open()?), None)
+                .context(format!("Failed to read Arrow file {path:?}."))?;
+            (reader.schema(), Box::new(reader))
+        }
+    };
+    if let Some(field) = schema
+        .fields()
+        .iter()
+        .find(|field| field.data_type().is_nested())
+    {
+        bail!(TableReaderError::NestedArrowColumn {
+            path: path.to_owned(),
+            column: field.name().to_owned(),
+            data_type: field.data_type().to_string(),
+        });
+    }
+    let options = FormatOptions::default()
+        .with_null("")
+        .with_timestamp_format(Some(TIMESTAMP_FORMAT))
+        .with_timestamp_tz_format(Some(TIMESTAMP_FORMAT));
+    let mut records = Vec::new();
+    for batch in batches {
+        let batch = batch.context(format!("Failed to read record batch of {path:?}."))?;
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<Result<Vec<_>, _>>()?;
+        for row in 0..batch.num_rows() {
+            records.push(
+                formatters
+                    .iter()
+                    .map(|formatter| formatter.value(row).to_string())
+                    .collect_vec(),
+            );
+        }
+    }
+    Ok(TableReader::Records {
+        headers: schema
+            .fields()
+            .iter()
+            .map(|field| field.name().to_owned())
+            .collect(),
+        records,
+        column_types: schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().to_owned(), column_type(field.data_type())))
+            .collect(),
+    })
+}
+
+/// Derives the column type from the given Arrow data type.
+fn column_type(data_type: &DataType) -> ColumnType {
+    match data_type {
+        DataType::Null => ColumnType::None,
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => ColumnType::Integer,
+        DataType::Float16
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Decimal128(..)
+        | DataType::Decimal256(..) => ColumnType::Float,
+        DataType::Date32 | DataType::Date64 | DataType::Timestamp(..) => ColumnType::Date,
+        DataType::Boolean => ColumnType::Boolean,
+        // Categorical columns are dictionary encoded.
+        DataType::Dictionary(_, values) => column_type(values),
+        _ => ColumnType::String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spec::DatasetSpecs;
+    use crate::utils::column_type::{classify_table, ColumnType};
+    use arrow_array::builder::{ListBuilder, StringBuilder};
+    use arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray};
+    use arrow_ipc::writer::FileWriter;
+    use itertools::Itertools;
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    fn write_feather(path: &Path, columns: Vec<(&str, ArrayRef)>) {
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let mut writer = FileWriter::try_new(File::create(path).unwrap(), &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_read_feather() {
+        let path = std::env::temp_dir().join("datavzrd-test-table.feather");
+        write_feather(
+            &path,
+            vec![
+                ("gene", Arc::new(StringArray::from(vec!["TP53", "BRCA1"]))),
+                ("count", Arc::new(Int64Array::from(vec![Some(3), None]))),
+                ("score", Arc::new(Float64Array::from(vec![0.5, 2.0]))),
+            ],
+        );
+        let dataset = DatasetSpecs {
+            path: path.clone(),
+            header_rows: 1,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["gene", "count", "score"]);
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![vec!["TP53", "3", "0.5"], vec!["BRCA1", "", "2.0"]]
+        );
+        let column_types = classify_table(&dataset).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(column_types.get("count"), Some(&ColumnType::Integer));
+        assert_eq!(column_types.get("score"), Some(&ColumnType::Float));
+        assert_eq!(column_types.get("gene"), Some(&ColumnType::String));
+    }
+
+    #[test]
+    fn test_reject_nested_arrow_column() {
+        let path = std::env::temp_dir().join("datavzrd-test-nested.arrow");
+        let mut tags = ListBuilder::new(StringBuilder::new());
+        tags.values().append_value("a");
+        tags.append(true);
+        write_feather(&path, vec![("tags", Arc::new(tags.finish()))]);
+        let dataset = DatasetSpecs {
+            path: path.clone(),
+            header_rows: 1,
+            ..Default::default()
+        };
+        let error = dataset.reader().err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error
+            .to_string()
+            .starts_with(r#"Column "tags" of Arrow file"#));
+    }
+}
@@ -20,6 +20,7 @@ impl ColumnIndex {
         let mut index = HashMap::new();
         let address_factory = RowAddressFactory::new(page_size);
         for (i, result) in reader.records()?.skip(dataset.header_rows - 1).enumerate() {
+            let result = result?;
             index.insert(
                 result.get(column_index).unwrap().to_owned(),
                 address_factory.get(i),
@@ -60,10 +61,10 @@ mod tests {
                 .to_string()
                 .parse()
                 .unwrap(),
-            separator: char::from_str(",").unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
             header_rows: 1,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
         let column_index = ColumnIndex::new(&dataset, "first", 3).unwrap();
         let expected_column_index = ColumnIndex {
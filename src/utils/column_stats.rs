@@ -0,0 +1,206 @@
+use crate::spec::DatasetSpecs;
+use crate::utils::column_type::ColumnType;
+use anyhow::Result;
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Summary statistics of a single column. Numeric columns provide min, max, mean and standard
+/// deviation while nominal columns provide the number of distinct values and the mode.
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+pub(crate) struct ColumnStats {
+    pub(crate) nulls: usize,
+    pub(crate) min: Option<f64>,
+    pub(crate) max: Option<f64>,
+    pub(crate) mean: Option<f64>,
+    pub(crate) stddev: Option<f64>,
+    pub(crate) distinct: Option<usize>,
+    pub(crate) mode: Option<String>,
+}
+
+/// Running statistics of a column that are updated one value at a time.
+enum Accumulator {
+    /// Welford's online algorithm for mean and variance.
+    Numeric {
+        count: usize,
+        mean: f64,
+        m2: f64,
+        min: f64,
+        max: f64,
+    },
+    Nominal {
+        counts: HashMap<String, usize>,
+    },
+}
+
+impl Accumulator {
+    fn new(column_type: ColumnType) -> Self {
+        if column_type.is_numeric() {
+            Accumulator::Numeric {
+                count: 0,
+                mean: 0.0,
+                m2: 0.0,
+                min: f64::INFINITY,
+                max: f64::NEG_INFINITY,
+            }
+        } else {
+            Accumulator::Nominal {
+                counts: HashMap::new(),
+            }
+        }
+    }
+
+    fn update(&mut self, value: &str) {
+        match self {
+            Accumulator::Numeric {
+                count,
+                mean,
+                m2,
+                min,
+                max,
+            } => {
+                if let Ok(value) = f64::from_str(value) {
+                    *count += 1;
+                    let delta = value - *mean;
+                    *mean += delta / *count as f64;
+                    *m2 += delta * (value - *mean);
+                    *min = min.min(value);
+                    *max = max.max(value);
+                }
+            }
+            Accumulator::Nominal { counts } => {
+                *counts.entry(value.to_owned()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    fn finish(self, nulls: usize) -> ColumnStats {
+        match self {
+            Accumulator::Numeric {
+                count,
+                mean,
+                m2,
+                min,
+                max,
+            } => ColumnStats {
+                nulls,
+                min: (count > 0).then_some(min),
+                max: (count > 0).then_some(max),
+                mean: (count > 0).then_some(mean),
+                // Sample standard deviation, which is undefined for less than two values.
+                stddev: (count > 1).then(|| (m2 / (count - 1) as f64).sqrt()),
+                ..Default::default()
+            },
+            Accumulator::Nominal { counts } => ColumnStats {
+                nulls,
+                distinct: Some(counts.len()),
+                // Ties are broken by the smallest value to keep the output deterministic.
+                mode: counts
+                    .iter()
+                    .sorted_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)))
+                    .map(|(value, _)| value.to_owned())
+                    .next(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Values that are counted as missing in addition to empty ones, compared case-insensitively.
+const MISSING_VALUES: [&str; 4] = ["na", "n/a", "null", "nan"];
+
+fn is_missing(value: &str) -> bool {
+    value.is_empty()
+        || MISSING_VALUES
+            .iter()
+            .any(|missing| value.eq_ignore_ascii_case(missing))
+}
+
+/// Computes the fraction of missing values of all columns of the given dataset.
+pub(crate) fn compute_missing_fractions(dataset: &DatasetSpecs) -> Result<HashMap<String, f64>> {
+    let mut reader = dataset.reader()?;
+    let headers = reader.headers()?;
+    let mut missing = vec![0; headers.len()];
+    let mut records = 0;
+    for record in reader.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        records += 1;
+        for (index, value) in record.iter().enumerate().take(headers.len()) {
+            if is_missing(value.trim()) || dataset.is_na(&headers[index], value) {
+                missing[index] += 1;
+            }
+        }
+    }
+    Ok(headers
+        .into_iter()
+        .zip(missing)
+        .map(|(column, missing)| {
+            let fraction = if records > 0 {
+                missing as f64 / records as f64
+            } else {
+                0.0
+            };
+            (column, fraction)
+        })
+        .collect())
+}
+
+/// Computes the summary statistics of all columns of the given dataset in a single pass.
+pub(crate) fn compute_column_stats(
+    dataset: &DatasetSpecs,
+    column_types: &HashMap<String, ColumnType>,
+) -> Result<HashMap<String, ColumnStats>> {
+    let mut reader = dataset.reader()?;
+    let headers = reader.headers()?;
+    let mut accumulators = headers
+        .iter()
+        .map(|column| Accumulator::new(column_types.get(column).copied().unwrap_or_default()))
+        .collect_vec();
+    let mut nulls = vec![0; headers.len()];
+    for record in reader.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        for (index, value) in record.iter().enumerate().take(headers.len()) {
+            if dataset.is_na(&headers[index], value) {
+                nulls[index] += 1;
+            } else {
+                accumulators[index].update(value);
+            }
+        }
+    }
+    Ok(headers
+        .into_iter()
+        .zip(accumulators.into_iter().zip(nulls))
+        .map(|(column, (accumulator, nulls))| (column, accumulator.finish(nulls)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::column_stats::{compute_column_stats, ColumnStats};
+    use crate::utils::column_type::classify_table;
+
+    #[test]
+    fn test_column_stats() {
+        let dataset = serde_yaml::from_str("path: tests/data/stats.csv").unwrap();
+        let column_types = classify_table(&dataset).unwrap();
+        let stats = compute_column_stats(&dataset, &column_types).unwrap();
+        let score = stats.get("score").unwrap();
+        assert_eq!(score.nulls, 2);
+        assert_eq!(score.min, Some(2.0));
+        assert_eq!(score.max, Some(9.0));
+        assert_eq!(score.mean, Some(5.0));
+        // Sample variance of 2, 4, 4, 6 and 9 is 28 / 4.
+        assert!((score.stddev.unwrap() - 7_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(score.distinct, None);
+        assert_eq!(
+            stats.get("group").unwrap(),
+            &ColumnStats {
+                nulls: 1,
+                distinct: Some(3),
+                mode: Some(String::from("b")),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -1,60 +1,815 @@
-use crate::spec::DatasetSpecs;
+use crate::spec::{DatasetSpecs, NumberFormat, Strip, DEFAULT_NA_VALUES};
 use anyhow::Result;
-use std::collections::HashMap;
+use chrono::{NaiveDate, NaiveDateTime};
+use itertools::Itertools;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::iter::FromIterator;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
 pub(crate) enum ColumnType {
+    /// Type of columns without any values.
     #[default]
+    #[serde(skip_deserializing)]
     None,
     String,
     Integer,
     Float,
+    Date,
+    Url,
+    ImageUrl,
+    Boolean,
+    Percentage,
+    /// Durations like `1h 32m` or `00:05:12`, which are plotted and sorted by their length in
+    /// seconds.
+    Duration,
 }
 
+/// Minimal fraction of non-missing values of a column that have to be URLs in order to
+/// classify it as a URL column.
+const URL_FRACTION: f64 = 0.95;
+/// Minimal fraction of non-missing values of a column that have to point to images in order
+/// to classify it as an image URL column.
+const IMAGE_URL_FRACTION: f64 = 0.9;
+/// Extensions of image files that can be shown by browsers.
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "svg", "webp"];
+
+/// Values that denote true in boolean columns unless configured otherwise, compared
+/// case-insensitively.
+const TRUE_VALUES: [&str; 5] = ["true", "t", "yes", "1", "pass"];
+/// Values that denote false in boolean columns unless configured otherwise, compared
+/// case-insensitively.
+const FALSE_VALUES: [&str; 5] = ["false", "f", "no", "0", "fail"];
+
+/// Formats that are probed in order when checking whether a value is a date.
+const DATE_FORMATS: [&str; 6] = [
+    "%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y", "%d/%m/%Y", "%m/%d/%Y", "%d %b %Y",
+];
+const DATETIME_FORMATS: [&str; 7] = [
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%d.%m.%Y %H:%M",
+    "%d/%m/%Y %H:%M",
+    "%m/%d/%Y %H:%M",
+];
+
+/// Units of durations like `1h 32m` with their length in seconds, ordered by length.
+const DURATION_UNITS: [(&str, f64); 6] = [
+    ("d", 86400.0),
+    ("h", 3600.0),
+    ("min", 60.0),
+    ("m", 60.0),
+    ("sec", 1.0),
+    ("s", 1.0),
+];
+
+/// Number format assumed for columns without a configured one, which only removes unambiguous
+/// thousands separators like in `1,234,567.8`.
+const GROUPED_NUMBERS: NumberFormat = NumberFormat {
+    decimal_separator: '.',
+    thousands_separator: Some(','),
+};
+
 impl ColumnType {
-    fn update(&mut self, value: &str) -> Result<()> {
-        if !value.is_na() {
-            *self = match (
-                f64::from_str(value).is_ok(),
-                i64::from_str(value).is_ok(),
-                &self,
-            ) {
-                (true, true, ColumnType::None) | (true, true, ColumnType::Integer) => {
-                    ColumnType::Integer
-                }
-                (true, false, ColumnType::None)
-                | (true, _, ColumnType::Float)
-                | (true, false, ColumnType::Integer) => ColumnType::Float,
-                (false, false, _) | (_, _, ColumnType::String) => ColumnType::String,
-                (false, true, _) => unreachable!(),
+    fn update(&mut self, value: &str, number_format: Option<&NumberFormat>) -> Result<()> {
+        if !value.is_empty() {
+            let value_type = ColumnType::of_value(value, number_format);
+            *self = match (&self, value_type) {
+                (ColumnType::None, value_type) => value_type,
+                (ColumnType::Integer, ColumnType::Integer) => ColumnType::Integer,
+                (ColumnType::Integer, ColumnType::Float)
+                | (ColumnType::Float, ColumnType::Integer)
+                | (ColumnType::Float, ColumnType::Float) => ColumnType::Float,
+                (ColumnType::Date, ColumnType::Date) => ColumnType::Date,
+                (ColumnType::Percentage, ColumnType::Percentage) => ColumnType::Percentage,
+                (ColumnType::Duration, ColumnType::Duration) => ColumnType::Duration,
+                _ => ColumnType::String,
             };
         }
         Ok(())
     }
 
+    /// Returns the type of the given non-empty value.
+    fn of_value(value: &str, number_format: Option<&NumberFormat>) -> ColumnType {
+        let number = normalize_number(value, number_format);
+        if i64::from_str(&number).is_ok() {
+            ColumnType::Integer
+        } else if f64::from_str(&number).is_ok() {
+            ColumnType::Float
+        } else if parse_percentage(&number).is_some() {
+            ColumnType::Percentage
+        } else if parse_date(value).is_some() {
+            ColumnType::Date
+        } else if parse_duration(value).is_some() {
+            ColumnType::Duration
+        } else {
+            ColumnType::String
+        }
+    }
+
     pub(crate) fn is_numeric(&self) -> bool {
         self == &ColumnType::Integer || self == &ColumnType::Float
     }
 }
 
-/// Classifies table columns as String, Integer or Float
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColumnType::None => "none",
+            ColumnType::String => "string",
+            ColumnType::Integer => "integer",
+            ColumnType::Float => "float",
+            ColumnType::Date => "date",
+            ColumnType::Url => "url",
+            ColumnType::ImageUrl => "image-url",
+            ColumnType::Boolean => "boolean",
+            ColumnType::Percentage => "percentage",
+            ColumnType::Duration => "duration",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Parses the given value as a percentage like `87.5%`, returning the number without the
+/// percent sign.
+pub(crate) fn parse_percentage(value: &str) -> Option<f64> {
+    f64::from_str(value.strip_suffix('%')?.trim_end()).ok()
+}
+
+/// Parses the given value as a duration, returning its length in seconds. Supported are
+/// clock times like `00:05:12` or `1:02:03.5` with hours, minutes and seconds as well as
+/// amounts of units like `1h 32m`, `2d 3h` or `1.5 min`, which have to be ordered from days to
+/// seconds.
+pub(crate) fn parse_duration(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some((hours, rest)) = value.split_once(':') {
+        let (minutes, seconds) = rest.split_once(':')?;
+        let is_digits =
+            |value: &str| !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+        let whole_seconds = seconds.split_once('.').map_or(seconds, |(whole, _)| whole);
+        if !is_digits(hours) || minutes.len() != 2 || whole_seconds.len() != 2 {
+            return None;
+        }
+        let (hours, minutes, seconds) = (
+            u64::from_str(hours).ok()?,
+            u8::from_str(minutes).ok()?,
+            f64::from_str(seconds).ok()?,
+        );
+        if !is_digits(whole_seconds) || minutes >= 60 || seconds >= 60.0 {
+            return None;
+        }
+        return Some(hours as f64 * 3600.0 + f64::from(minutes) * 60.0 + seconds);
+    }
+
+    let mut rest = value;
+    let mut seconds = 0.0;
+    let mut previous_length = f64::INFINITY;
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let amount = f64::from_str(&rest[..number_end]).ok()?;
+        rest = rest[number_end..].trim_start();
+        let unit_end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let (_, length) = DURATION_UNITS
+            .iter()
+            .find(|(unit, _)| *unit == &rest[..unit_end])?;
+        // Each unit may only occur once, after all longer ones.
+        if *length >= previous_length {
+            return None;
+        }
+        previous_length = *length;
+        seconds += amount * length;
+        rest = rest[unit_end..].trim_start();
+    }
+    previous_length.is_finite().then_some(seconds)
+}
+
+/// Parses the given value as a number written in the given format after stripping the given
+/// prefixes and suffixes, ignoring the percent sign of percentages.
+pub(crate) fn parse_number(
+    value: &str,
+    number_format: Option<&NumberFormat>,
+    strip: Option<&Strip>,
+) -> Option<f64> {
+    let value = strip.map_or(Cow::Borrowed(value), |strip| strip.apply(value));
+    let value = normalize_number(&value, number_format);
+    f64::from_str(&value)
+        .ok()
+        .or_else(|| parse_percentage(&value))
+}
+
+/// Returns the given value with thousands separators removed and `.` as decimal separator
+/// according to the given format. Without a format, only unambiguous thousands separators are
+/// removed. Values that are not numbers in the format are returned unchanged.
+pub(crate) fn normalize_number<'a>(
+    value: &'a str,
+    number_format: Option<&NumberFormat>,
+) -> Cow<'a, str> {
+    match number_format.unwrap_or(&GROUPED_NUMBERS).normalize(value) {
+        Some(normalized) if normalized != value => Cow::Owned(normalized),
+        _ => Cow::Borrowed(value),
+    }
+}
+
+/// Parses the given value as a date or datetime, trying all supported formats.
+pub(crate) fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .or_else(|| {
+            DATETIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        })
+}
+
+/// Parses the given value as a date or datetime with the given format, or by trying all
+/// supported formats if none is given.
+pub(crate) fn parse_date_with_format(value: &str, format: Option<&str>) -> Option<NaiveDateTime> {
+    match format {
+        Some(format) => NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()?
+                    .and_hms_opt(0, 0, 0)
+            }),
+        None => parse_date(value),
+    }
+}
+
+/// Returns true if the given value is an HTTP or HTTPS URL.
+pub(crate) fn is_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+/// Returns true if the given value points to an image file, ignoring any query or fragment.
+pub(crate) fn is_image_url(value: &str) -> bool {
+    let path = value.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit_once('.').is_some_and(|(_, extension)| {
+        IMAGE_EXTENSIONS
+            .iter()
+            .any(|image_extension| extension.eq_ignore_ascii_case(image_extension))
+    })
+}
+
+/// Values denoting true and false in boolean columns, which are compared case-insensitively.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BooleanVocabulary {
+    pub(crate) true_values: Vec<String>,
+    pub(crate) false_values: Vec<String>,
+}
+
+impl Default for BooleanVocabulary {
+    fn default() -> Self {
+        BooleanVocabulary::new(&[], &[])
+    }
+}
+
+impl BooleanVocabulary {
+    /// Creates a vocabulary from the given values, falling back to the default ones if either
+    /// of them is empty.
+    pub(crate) fn new(true_values: &[String], false_values: &[String]) -> Self {
+        let lowercase = |values: &[String], default: &[&str]| {
+            if values.is_empty() {
+                default.iter().map(|value| value.to_string()).collect()
+            } else {
+                values.iter().map(|value| value.to_lowercase()).collect()
+            }
+        };
+        BooleanVocabulary {
+            true_values: lowercase(true_values, &TRUE_VALUES),
+            false_values: lowercase(false_values, &FALSE_VALUES),
+        }
+    }
+
+    /// Returns the boolean denoted by the given value, if any.
+    pub(crate) fn parse(&self, value: &str) -> Option<bool> {
+        let value = value.to_lowercase();
+        if self.true_values.contains(&value) {
+            Some(true)
+        } else if self.false_values.contains(&value) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Classifies table columns as String, Integer, Float, Date, Url, ImageUrl or Boolean
 pub(crate) fn classify_table(dataset: &DatasetSpecs) -> Result<HashMap<String, ColumnType>> {
+    Ok(inspect_table(dataset)?.0)
+}
+
+/// Columns of a dataset whose values hint at a surprising type.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ColumnDiagnostics {
+    /// Text columns whose values are mostly but not all boolean values, which hints at typos or
+    /// unconfigured boolean values.
+    pub(crate) mixed_boolean_columns: Vec<String>,
+    /// Text columns whose values are numbers of which only some are percentages, along with the
+    /// first data row (1-based) whose value lacks a percent sign.
+    pub(crate) mixed_percentage_columns: Vec<(String, usize)>,
+    /// Columns that consist of integers of which some exceed the range of 64 bit integers, so
+    /// that they are treated as floats with limited precision.
+    pub(crate) overflowing_integer_columns: Vec<String>,
+    /// Columns without any values, sorted by name.
+    pub(crate) empty_columns: Vec<String>,
+}
+
+/// Returns the diagnostics of the columns of the given dataset, gathered in the same pass over
+/// its records that infers the column types.
+pub(crate) fn diagnose_columns(dataset: &DatasetSpecs) -> Result<ColumnDiagnostics> {
+    let (classification, observations) = inspect_table(dataset)?;
+    let mut diagnostics = ColumnDiagnostics::default();
+    for (title, observed) in observations {
+        let column_type = classification.get(&title);
+        if column_type == Some(&ColumnType::String)
+            && observed.booleans < observed.values
+            && 2 * observed.booleans > observed.values
+        {
+            diagnostics.mixed_boolean_columns.push(title.clone());
+        }
+        if column_type == Some(&ColumnType::String) && !observed.non_numeric && observed.percentages
+        {
+            if let Some(row) = observed.first_plain_number {
+                diagnostics
+                    .mixed_percentage_columns
+                    .push((title.clone(), row));
+            }
+        }
+        if column_type == Some(&ColumnType::Float)
+            && !observed.non_integers
+            && observed.overflowing_integers
+        {
+            diagnostics.overflowing_integer_columns.push(title);
+        }
+    }
+    diagnostics.empty_columns = classification
+        .into_iter()
+        .filter(|(_, column_type)| column_type == &ColumnType::None)
+        .map(|(title, _)| title)
+        .sorted()
+        .collect();
+    Ok(diagnostics)
+}
+
+/// Observations on the values of a column made while inferring its type.
+#[derive(Debug, Clone, Copy, Default)]
+struct ValueObservations {
+    /// Number of non-missing values.
+    values: usize,
+    /// Number of boolean values.
+    booleans: usize,
+    /// Whether any value is neither a number nor a percentage.
+    non_numeric: bool,
+    /// Whether any value is a percentage.
+    percentages: bool,
+    /// First data row (1-based) of a number without a percent sign.
+    first_plain_number: Option<usize>,
+    /// Whether any value is no integer.
+    non_integers: bool,
+    /// Whether any integer exceeds the range of 64 bit integers.
+    overflowing_integers: bool,
+}
+
+impl ValueObservations {
+    /// Records the given non-missing value of the given data row (1-based).
+    fn update(
+        &mut self,
+        value: &str,
+        row: usize,
+        number_format: Option<&NumberFormat>,
+        vocabulary: &BooleanVocabulary,
+    ) {
+        self.values += 1;
+        if vocabulary.parse(value).is_some() {
+            self.booleans += 1;
+        }
+        if parse_percentage(value).is_some() {
+            self.percentages = true;
+        } else if f64::from_str(value).is_ok() {
+            self.first_plain_number.get_or_insert(row);
+        } else {
+            self.non_numeric = true;
+        }
+        let number = normalize_number(value, number_format);
+        let digits = number.strip_prefix(['+', '-']).unwrap_or(&number);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            self.non_integers = true;
+        } else if i64::from_str(&number).is_err() {
+            self.overflowing_integers = true;
+        }
+    }
+}
+
+/// Classifies the columns of the given dataset along with the observations on their values, in
+/// the order of the headers. Observations are only made for inferred column types.
+#[allow(clippy::type_complexity)]
+fn inspect_table(
+    dataset: &DatasetSpecs,
+) -> Result<(
+    HashMap<String, ColumnType>,
+    Vec<(String, ValueObservations)>,
+)> {
+    let mut reader = dataset.reader()?;
+    let headers = reader.headers()?;
+    // Column types reported by the file format take precedence over inferred ones.
+    let mut reported = reader.column_types();
+    reported.extend(
+        dataset
+            .reported_column_types
+            .iter()
+            .filter(|(column, _)| headers.contains(column))
+            .map(|(column, column_type)| (column.to_owned(), *column_type)),
+    );
+    let (mut classification, observations) =
+        if headers.iter().all(|title| reported.contains_key(title)) {
+            (reported, Vec::new())
+        } else {
+            let (mut classification, observations) = infer_column_types(dataset)?;
+            classification.extend(reported);
+            (classification, observations)
+        };
+    // Explicitly annotated date columns override the detected type.
+    for column in &dataset.date_columns {
+        if let Some(column_type) = classification.get_mut(column) {
+            *column_type = ColumnType::Date;
+        }
+    }
+    // Types configured by the view rendering the dataset take precedence over all others.
+    for (column, configured) in &dataset.column_types {
+        if let Some(column_type) = classification.get_mut(column) {
+            *column_type = *configured;
+        }
+    }
+
+    Ok((classification, observations))
+}
+
+/// Infers the column types from the string values of all records
+#[allow(clippy::type_complexity)]
+fn infer_column_types(
+    dataset: &DatasetSpecs,
+) -> Result<(
+    HashMap<String, ColumnType>,
+    Vec<(String, ValueObservations)>,
+)> {
     let headers = dataset.reader()?.headers()?.clone();
     let mut classification = HashMap::from_iter(
         headers
             .iter()
             .map(|f| (f.to_owned(), ColumnType::default())),
     );
-    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
-        for (title, value) in headers.iter().zip(record.iter()) {
+    let vocabulary = BooleanVocabulary::default();
+    // Number of URLs, image URLs and numbers (after stripping) of each column.
+    let mut counts = vec![(0, 0, 0); headers.len()];
+    let mut observations = vec![ValueObservations::default(); headers.len()];
+    // Types of the values that are numbers of columns tolerating other values.
+    let mut numeric_types: HashMap<&String, ColumnType> = HashMap::new();
+    for (row, record) in dataset
+        .reader()?
+        .records()?
+        .skip(dataset.header_rows - 1)
+        .take(row_limit(dataset))
+        .enumerate()
+    {
+        let record = record?;
+        for (((title, value), counts), observed) in headers
+            .iter()
+            .zip(record.iter())
+            .zip(counts.iter_mut())
+            .zip(observations.iter_mut())
+        {
+            if dataset.is_na(title, value) {
+                continue;
+            }
+            let number_format = dataset.number_format(title);
+            observed.update(value, row + 1, number_format, &vocabulary);
             let column_type = classification.get_mut(title).unwrap();
-            column_type.update(value)?;
+            match dataset.strip(title) {
+                // Stripped columns are classified by their values that are numbers, the others
+                // are shown as they are.
+                Some(strip) => {
+                    let stripped = strip.apply(value);
+                    if parse_number(&stripped, number_format, None).is_some() {
+                        column_type.update(&stripped, number_format)?;
+                        counts.2 += 1;
+                    }
+                }
+                None => {
+                    column_type.update(value, number_format)?;
+                    if dataset.min_numeric_fraction(title).is_some()
+                        && parse_number(value, number_format, None).is_some()
+                    {
+                        numeric_types
+                            .entry(title)
+                            .or_default()
+                            .update(value, number_format)?;
+                        counts.2 += 1;
+                    }
+                }
+            }
+            if is_url(value) {
+                counts.0 += 1;
+            }
+            if is_image_url(value) {
+                counts.1 += 1;
+            }
+        }
+    }
+    for ((title, (urls, image_urls, numbers)), observed) in
+        headers.iter().zip(counts).zip(&observations)
+    {
+        let (values, booleans) = (observed.values, observed.booleans);
+        let column_type = classification.get_mut(title).unwrap();
+        if let Some(stripped) = dataset.stripped_columns.get(title) {
+            if (numbers as f64) < stripped.min_fraction * values as f64 {
+                *column_type = ColumnType::String;
+            }
+            continue;
+        }
+        if let Some(min_fraction) = dataset.min_numeric_fraction(title) {
+            let numeric_type = numeric_types.get(title).copied().unwrap_or_default();
+            if !column_type.is_numeric()
+                && numeric_type.is_numeric()
+                && numbers as f64 >= min_fraction * values as f64
+            {
+                *column_type = numeric_type;
+                continue;
+            }
+        }
+        if matches!(column_type, ColumnType::String | ColumnType::Integer) && booleans == values {
+            *column_type = ColumnType::Boolean;
+        } else if column_type == &ColumnType::String {
+            if image_urls as f64 >= IMAGE_URL_FRACTION * values as f64 {
+                *column_type = ColumnType::ImageUrl;
+            } else if urls as f64 >= URL_FRACTION * values as f64 {
+                *column_type = ColumnType::Url;
+            }
+        }
+    }
+
+    Ok((
+        classification,
+        headers.into_iter().zip(observations).collect(),
+    ))
+}
+
+/// Returns the columns of the given dataset that are rendered as text because too few of their
+/// values are numbers after stripping, along with the fraction of numbers.
+pub(crate) fn non_numeric_stripped_columns(dataset: &DatasetSpecs) -> Result<Vec<(String, f64)>> {
+    let classification = classify_table(dataset)?;
+    let headers = dataset.reader()?.headers()?;
+    let mut non_numeric = Vec::new();
+    for (index, column) in headers.iter().enumerate() {
+        if dataset.strip(column).is_none()
+            || classification.get(column) != Some(&ColumnType::String)
+        {
+            continue;
+        }
+        let (number_format, strip) = (dataset.number_format(column), dataset.strip(column));
+        // Number of non-missing values and numbers after stripping.
+        let mut counts = (0, 0);
+        for record in dataset
+            .reader()?
+            .records()?
+            .skip(dataset.header_rows - 1)
+            .take(row_limit(dataset))
+        {
+            let record = record?;
+            let value = record.get(index).unwrap().as_str();
+            if !dataset.is_na(column, value) {
+                counts.0 += 1;
+                if parse_number(value, number_format, strip).is_some() {
+                    counts.1 += 1;
+                }
+            }
+        }
+        if counts.0 > 0 {
+            non_numeric.push((column.to_owned(), counts.1 as f64 / counts.0 as f64));
+        }
+    }
+    Ok(non_numeric)
+}
+
+/// Maximal number of values that are no numbers listed per column treated as numeric.
+const MAX_LISTED_NON_NUMERIC_VALUES: usize = 5;
+
+/// Returns the columns of the given dataset that are treated as numeric although some of their
+/// values are no numbers, either because of a minimal numeric fraction or a configured numeric
+/// type, along with the number of such values and the first of them with their data rows
+/// (1-based).
+#[allow(clippy::type_complexity)]
+pub(crate) fn tolerated_non_numeric_values(
+    dataset: &DatasetSpecs,
+) -> Result<Vec<(String, usize, Vec<(usize, String)>)>> {
+    let classification = classify_table(dataset)?;
+    let headers = dataset.reader()?.headers()?;
+    let mut tolerated = Vec::new();
+    for (index, column) in headers.iter().enumerate() {
+        if (dataset.min_numeric_fraction(column).is_none()
+            && !dataset.column_types.contains_key(column))
+            || dataset.strip(column).is_some()
+            || !classification
+                .get(column)
+                .is_some_and(ColumnType::is_numeric)
+        {
+            continue;
+        }
+        let number_format = dataset.number_format(column);
+        let mut count = 0;
+        let mut examples = Vec::new();
+        for (row, record) in dataset
+            .reader()?
+            .records()?
+            .skip(dataset.header_rows - 1)
+            .enumerate()
+        {
+            let record = record?;
+            let value = record.get(index).unwrap().as_str();
+            if !dataset.is_na(column, value) && parse_number(value, number_format, None).is_none() {
+                count += 1;
+                if examples.len() < MAX_LISTED_NON_NUMERIC_VALUES {
+                    examples.push((row + 1, value.to_owned()));
+                }
+            }
+        }
+        if count > 0 {
+            tolerated.push((column.to_owned(), count, examples));
+        }
+    }
+    Ok(tolerated)
+}
+
+/// Inferred type and statistics of the values of a column.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct ColumnSummary {
+    pub(crate) column: String,
+    #[serde(rename = "type")]
+    pub(crate) column_type: ColumnType,
+    /// Number of empty values, including tokens denoting missing values like `NA`.
+    pub(crate) empty: usize,
+    /// Number of non-empty values that are no numbers, only given for columns containing
+    /// numbers.
+    pub(crate) unparseable: Option<usize>,
+    /// Number of distinct non-empty values.
+    pub(crate) distinct: usize,
+}
+
+/// Returns the inferred type and statistics of the values of each column of the given dataset
+/// in the order of its header.
+pub(crate) fn summarize_columns(dataset: &DatasetSpecs) -> Result<Vec<ColumnSummary>> {
+    let headers = dataset.reader()?.headers()?.clone();
+    let classification = classify_table(dataset)?;
+    // Number of empty values, numbers and other values and the distinct values of each column.
+    let mut counts = vec![(0, 0, 0, HashSet::new()); headers.len()];
+    for record in dataset.reader()?.records()?.skip(dataset.header_rows - 1) {
+        let record = record?;
+        for ((title, value), counts) in headers.iter().zip(record.iter()).zip(counts.iter_mut()) {
+            if dataset.is_na(title, value) {
+                counts.0 += 1;
+                continue;
+            }
+            if parse_number(value, dataset.number_format(title), None).is_some() {
+                counts.1 += 1;
+            } else {
+                counts.2 += 1;
+            }
+            if !counts.3.contains(value.as_str()) {
+                counts.3.insert(value.to_string());
+            }
+        }
+    }
+    Ok(headers
+        .iter()
+        .zip(counts)
+        .map(
+            |(column, (empty, numbers, others, distinct))| ColumnSummary {
+                column: column.to_owned(),
+                column_type: classification.get(column).copied().unwrap_or_default(),
+                empty,
+                unparseable: (numbers > 0).then_some(others),
+                distinct: distinct.len(),
+            },
+        )
+        .collect())
+}
+
+/// Type of a column along with the observations it has been inferred from.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct ColumnTypeReport {
+    pub(crate) column: String,
+    #[serde(rename = "type")]
+    pub(crate) column_type: ColumnType,
+    pub(crate) observations: TypeObservations,
+    /// Whether the type is configured or annotated instead of inferred.
+    pub(crate) overridden: bool,
+}
+
+/// Numbers of values of each kind among the rows the type of a column is inferred from.
+/// Values that are neither integers nor floats, e.g. dates or percentages, count as strings.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct TypeObservations {
+    pub(crate) integer: usize,
+    pub(crate) float: usize,
+    pub(crate) string: usize,
+    /// Number of empty values, including tokens denoting missing values like `NA`.
+    pub(crate) empty: usize,
+}
+
+/// Returns the type of each column of the given dataset in the order of its header along with
+/// the observations that drove its inference.
+pub(crate) fn column_type_report(dataset: &DatasetSpecs) -> Result<Vec<ColumnTypeReport>> {
+    let headers = dataset.reader()?.headers()?.clone();
+    let classification = classify_table(dataset)?;
+    let mut observations = vec![TypeObservations::default(); headers.len()];
+    for record in dataset
+        .reader()?
+        .records()?
+        .skip(dataset.header_rows - 1)
+        .take(row_limit(dataset))
+    {
+        let record = record?;
+        for ((title, value), observations) in headers
+            .iter()
+            .zip(record.iter())
+            .zip(observations.iter_mut())
+        {
+            if dataset.is_na(title, value) {
+                observations.empty += 1;
+                continue;
+            }
+            let value = match dataset.strip(title) {
+                Some(strip) => strip.apply(value),
+                None => Cow::Borrowed(value.as_str()),
+            };
+            match ColumnType::of_value(&value, dataset.number_format(title)) {
+                ColumnType::Integer => observations.integer += 1,
+                ColumnType::Float => observations.float += 1,
+                _ => observations.string += 1,
+            }
         }
     }
+    Ok(headers
+        .iter()
+        .zip(observations)
+        .map(|(column, observations)| ColumnTypeReport {
+            column: column.to_owned(),
+            column_type: classification.get(column).copied().unwrap_or_default(),
+            observations,
+            overridden: dataset.column_types.contains_key(column)
+                || dataset.date_columns.contains(column),
+        })
+        .collect())
+}
 
-    Ok(classification)
+/// Formats the given column summaries as a table with aligned columns.
+pub(crate) fn format_column_summaries(summaries: &[ColumnSummary]) -> String {
+    let rows =
+        std::iter::once(["column", "type", "empty", "unparseable", "distinct"].map(String::from))
+            .chain(summaries.iter().map(|summary| {
+                [
+                    summary.column.to_owned(),
+                    summary.column_type.to_string(),
+                    summary.empty.to_string(),
+                    summary
+                        .unparseable
+                        .map_or("-".to_string(), |count| count.to_string()),
+                    summary.distinct.to_string(),
+                ]
+            }))
+            .collect_vec();
+    let widths: Vec<_> = (0..5)
+        .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap())
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Returns the number of leading rows from which the column types of the given dataset are
+/// inferred.
+fn row_limit(dataset: &DatasetSpecs) -> usize {
+    dataset.classify_rows.limit().unwrap_or(usize::MAX)
 }
 
 pub(crate) trait IsNa {
@@ -63,15 +818,24 @@ pub(crate) trait IsNa {
 
 impl IsNa for &str {
     fn is_na(&self) -> bool {
-        self.is_empty() || self == &"NA"
+        self.is_empty() || DEFAULT_NA_VALUES.contains(self)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::spec::DatasetSpecs;
-    use crate::utils::column_type::{classify_table, ColumnType};
+    use crate::spec::{ClassifyRows, DatasetSpecs, NumberFormat, RenderTableSpecs};
+    use crate::utils::column_type::{
+        classify_table, column_type_report, diagnose_columns, format_column_summaries,
+        is_image_url, normalize_number, parse_date_with_format, parse_duration, parse_number,
+        parse_percentage, summarize_columns, BooleanVocabulary, ColumnSummary, ColumnType,
+        TypeObservations, GROUPED_NUMBERS,
+    };
+    use crate::utils::remote::DownloadSettings;
+    use chrono::NaiveDate;
+    use itertools::Itertools;
     use std::collections::HashMap;
+    use std::path::Path;
     use std::str::FromStr;
 
     #[test]
@@ -81,10 +845,10 @@ mod tests {
                 .to_string()
                 .parse()
                 .unwrap(),
-            separator: char::from_str(",").unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
             header_rows: 1,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
         let classification = classify_table(&dataset).unwrap();
         let expected = HashMap::from([
@@ -103,10 +867,10 @@ mod tests {
                 .to_string()
                 .parse()
                 .unwrap(),
-            separator: char::from_str(",").unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
             header_rows: 1,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
         let classification = classify_table(&dataset).unwrap();
         let expected = HashMap::from([
@@ -118,14 +882,99 @@ mod tests {
         assert_eq!(classification, expected);
     }
 
+    #[test]
+    fn test_column_type_report() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/type_overrides.csv").unwrap();
+        let render_table: RenderTableSpecs =
+            serde_yaml::from_str("columns: {count: {type: float}}").unwrap();
+        let dataset = render_table.view_dataset(&dataset).unwrap().unwrap();
+        let report = column_type_report(&dataset).unwrap();
+        assert_eq!(
+            report.iter().map(|column| &column.column).collect_vec(),
+            vec!["id", "count", "label"]
+        );
+        assert_eq!(report[0].column_type, ColumnType::Integer);
+        assert!(!report[0].overridden);
+        assert_eq!(report[1].column_type, ColumnType::Float);
+        assert!(report[1].overridden);
+        assert_eq!(
+            report[1].observations,
+            TypeObservations {
+                integer: 2,
+                float: 0,
+                string: 1,
+                empty: 0
+            }
+        );
+        assert_eq!(report[2].observations.string, 3);
+    }
+
+    #[test]
+    fn test_summarize_columns() {
+        let dataset = DatasetSpecs::from_path(
+            Path::new("tests/data/stats.csv"),
+            None,
+            &DownloadSettings::default(),
+        )
+        .unwrap();
+        let summaries = summarize_columns(&dataset).unwrap();
+        assert_eq!(
+            summaries[2],
+            ColumnSummary {
+                column: "score".to_string(),
+                column_type: ColumnType::Integer,
+                empty: 2,
+                unparseable: Some(0),
+                distinct: 4,
+            }
+        );
+        assert_eq!(summaries[1].unparseable, None);
+        assert_eq!(
+            format_column_summaries(&summaries),
+            "column  type     empty  unparseable  distinct\n\
+             name    string   0      -            7\n\
+             group   string   1      -            3\n\
+             score   integer  2      0            4\n"
+        );
+
+        let dataset = DatasetSpecs::from_path(
+            Path::new("tests/data/duplicate_header.csv"),
+            None,
+            &DownloadSettings::default(),
+        )
+        .unwrap();
+        assert!(summarize_columns(&dataset).is_err());
+    }
+
+    #[test]
+    fn test_classify_row_limit() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/late_strings.csv").unwrap();
+        assert_eq!(
+            classify_table(&dataset).unwrap().get("value"),
+            Some(&ColumnType::String)
+        );
+        assert_eq!(dataset.classified_rows().unwrap(), None);
+        // The text in the last row is not examined.
+        dataset.classify_rows = ClassifyRows::First(5);
+        assert_eq!(
+            classify_table(&dataset).unwrap().get("value"),
+            Some(&ColumnType::Integer)
+        );
+        assert_eq!(dataset.classified_rows().unwrap(), Some(5));
+        dataset.classify_rows = ClassifyRows::First(6);
+        assert_eq!(dataset.classified_rows().unwrap(), None);
+    }
+
     #[test]
     fn test_empty_column() {
         let dataset = DatasetSpecs {
             path: "tests/data/empty_table.csv".to_string().parse().unwrap(),
-            separator: char::from_str(",").unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
             header_rows: 1,
-            links: None,
-            offer_excel: false,
+            double_quote: true,
+            ..Default::default()
         };
         let classification = classify_table(&dataset).unwrap();
         for column_type in classification.values() {
@@ -133,6 +982,135 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_classify_date_columns() {
+        let dataset = DatasetSpecs {
+            path: "tests/data/dates.csv".to_string().parse().unwrap(),
+            separator: Some(char::from_str(",").unwrap()),
+            header_rows: 1,
+            date_columns: vec![String::from("ambiguous")],
+            double_quote: true,
+            ..Default::default()
+        };
+        let classification = classify_table(&dataset).unwrap();
+        let expected = HashMap::from([
+            (String::from("name"), ColumnType::String),
+            (String::from("joined"), ColumnType::Date),
+            (String::from("updated"), ColumnType::Date),
+            (String::from("ambiguous"), ColumnType::Date),
+        ]);
+        assert_eq!(classification, expected);
+    }
+
+    #[test]
+    fn test_classify_datetime_columns() {
+        let dataset = serde_yaml::from_str("path: tests/data/ambiguous_dates.csv").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("visit"), Some(&ColumnType::Date));
+        assert_eq!(
+            parse_date_with_format("01/08/2023", Some("%m/%d/%Y")),
+            NaiveDate::from_ymd_opt(2023, 1, 8)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(parse_date_with_format("13/08/2023", Some("%m/%d/%Y")), None);
+    }
+
+    #[test]
+    fn test_mixed_boolean_columns() {
+        let dataset = serde_yaml::from_str("path: tests/data/mixed_booleans.csv").unwrap();
+        assert_eq!(
+            classify_table(&dataset).unwrap().get("verdict"),
+            Some(&ColumnType::String)
+        );
+        assert_eq!(
+            diagnose_columns(&dataset).unwrap().mixed_boolean_columns,
+            vec!["verdict"]
+        );
+
+        let dataset = serde_yaml::from_str("path: tests/data/booleans.csv").unwrap();
+        assert!(diagnose_columns(&dataset)
+            .unwrap()
+            .mixed_boolean_columns
+            .is_empty());
+        assert_eq!(BooleanVocabulary::default().parse("FAIL"), Some(false));
+    }
+
+    #[test]
+    fn test_classify_percentage_columns() {
+        let dataset = serde_yaml::from_str("path: tests/data/percentages.csv").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("purity"), Some(&ColumnType::Percentage));
+        assert_eq!(classification.get("coverage"), Some(&ColumnType::Integer));
+        assert_eq!(classification.get("mixed"), Some(&ColumnType::String));
+        assert_eq!(
+            diagnose_columns(&dataset).unwrap().mixed_percentage_columns,
+            vec![(String::from("mixed"), 3)]
+        );
+        assert_eq!(parse_percentage("100 %"), Some(100.0));
+        assert_eq!(parse_percentage("100"), None);
+        assert_eq!(parse_percentage("%"), None);
+    }
+
+    #[test]
+    fn test_classify_duration_columns() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/durations.csv").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("runtime"), Some(&ColumnType::Duration));
+        assert_eq!(classification.get("walltime"), Some(&ColumnType::String));
+        dataset
+            .column_types
+            .insert("walltime".to_string(), ColumnType::Duration);
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("walltime"), Some(&ColumnType::Duration));
+
+        assert_eq!(parse_duration("1h 32m"), Some(5520.0));
+        assert_eq!(parse_duration("2d3h"), Some(183600.0));
+        assert_eq!(parse_duration("1.5 min"), Some(90.0));
+        assert_eq!(parse_duration("00:05:12"), Some(312.0));
+        assert_eq!(parse_duration("100:00:00.5"), Some(360000.5));
+        assert_eq!(parse_duration("00:60:00"), None);
+        assert_eq!(parse_duration("12:30"), None);
+        assert_eq!(parse_duration("32m 1h"), None);
+        assert_eq!(parse_duration("1h 1h"), None);
+        assert_eq!(parse_duration("12"), None);
+        assert_eq!(parse_duration("h"), None);
+    }
+
+    #[test]
+    fn test_classify_locale_numbers() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/locale_numbers.csv, separator: ';'}").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        // Unambiguous thousands separators are removed without a configured format.
+        assert_eq!(classification.get("us"), Some(&ColumnType::Float));
+        assert_eq!(classification.get("eu"), Some(&ColumnType::String));
+
+        let european = NumberFormat {
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+        };
+        dataset.default_number_format = Some(european);
+        dataset
+            .number_formats
+            .insert("us".to_string(), GROUPED_NUMBERS);
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("eu"), Some(&ColumnType::Float));
+        assert_eq!(classification.get("us"), Some(&ColumnType::Float));
+        assert_eq!(
+            parse_number("1.234,56", Some(&european), None),
+            Some(1234.56)
+        );
+        assert_eq!(parse_number("12,5 %", Some(&european), None), Some(12.5));
+        assert_eq!(parse_number("1,234", None, None), Some(1234.0));
+        assert_eq!(parse_number("1,23", None, None), None);
+        assert_eq!(
+            normalize_number("01.02.2020", Some(&european)),
+            "01.02.2020"
+        );
+    }
+
     #[test]
     fn test_is_numeric() {
         let integer = ColumnType::Integer;
@@ -144,4 +1122,42 @@ mod tests {
         assert!(!string.is_numeric());
         assert!(!none.is_numeric())
     }
+
+    #[test]
+    fn test_classify_url_columns() {
+        let dataset = serde_yaml::from_str("path: tests/data/urls.csv").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("issue"), Some(&ColumnType::Url));
+        // At least 95% of the values have to be URLs.
+        assert_eq!(classification.get("almost"), Some(&ColumnType::Url));
+        assert_eq!(classification.get("mixed"), Some(&ColumnType::String));
+    }
+
+    #[test]
+    fn test_classify_image_url_columns() {
+        let dataset = serde_yaml::from_str("path: tests/data/images.csv").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("thumbnail"), Some(&ColumnType::ImageUrl));
+        // At least 90% of the values have to point to images, which may also be relative paths.
+        assert_eq!(classification.get("logo"), Some(&ColumnType::ImageUrl));
+        assert_eq!(classification.get("homepage"), Some(&ColumnType::Url));
+        assert!(is_image_url("https://example.com/a.JPEG#top"));
+        assert!(!is_image_url("https://example.com/png"));
+    }
+
+    #[test]
+    fn test_classify_boolean_columns() {
+        let dataset = serde_yaml::from_str("path: tests/data/booleans.csv").unwrap();
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("passed"), Some(&ColumnType::Boolean));
+        // Values of the vocabulary may be mixed and are compared case-insensitively.
+        assert_eq!(classification.get("mixed_case"), Some(&ColumnType::Boolean));
+        assert_eq!(classification.get("status"), Some(&ColumnType::String));
+        assert_eq!(classification.get("count"), Some(&ColumnType::Integer));
+
+        let vocabulary = BooleanVocabulary::new(&["Done".to_string()], &[]);
+        assert_eq!(vocabulary.parse("DONE"), Some(true));
+        assert_eq!(vocabulary.parse("true"), None);
+        assert_eq!(vocabulary.parse("No"), Some(false));
+    }
 }
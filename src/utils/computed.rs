@@ -0,0 +1,411 @@
+use crate::utils::column_type::IsNa;
+use crate::utils::lexer::{quoted, scan_identifier, scan_number, Parser};
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Column whose values are computed from the other columns of each record.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ComputedColumn {
+    pub(crate) name: String,
+    pub(crate) expression: ValueExpression,
+}
+
+/// Arithmetic or string expression over the columns of a record, e.g. `alt_count / depth` or
+/// `concat(chrom, ":", pos)`. Column names that are not plain identifiers can be quoted with
+/// backticks.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ValueExpression {
+    Column(String),
+    Number(f64),
+    Text(String),
+    Negate(Box<ValueExpression>),
+    Arithmetic {
+        left: Box<ValueExpression>,
+        operator: ArithmeticOperator,
+        right: Box<ValueExpression>,
+    },
+    Concat(Vec<ValueExpression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ArithmeticOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl ArithmeticOperator {
+    fn apply(&self, left: f64, right: f64) -> Option<f64> {
+        let result = match self {
+            ArithmeticOperator::Add => left + right,
+            ArithmeticOperator::Subtract => left - right,
+            ArithmeticOperator::Multiply => left * right,
+            ArithmeticOperator::Divide if right == 0.0 => return None,
+            ArithmeticOperator::Divide => left / right,
+        };
+        result.is_finite().then_some(result)
+    }
+}
+
+/// Outcome of evaluating an expression for a single record.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Evaluation {
+    Value(String),
+    /// One of the referenced values is missing, which is propagated silently.
+    Missing,
+    /// The expression is undefined for the record, e.g. due to a division by zero or a
+    /// non-numeric operand.
+    Invalid,
+}
+
+impl Evaluation {
+    fn number(self) -> Result<f64, Evaluation> {
+        match self {
+            Evaluation::Value(value) => f64::from_str(&value).map_err(|_| Evaluation::Invalid),
+            other => Err(other),
+        }
+    }
+}
+
+impl FromStr for ValueExpression {
+    type Err = anyhow::Error;
+
+    fn from_str(expression: &str) -> Result<Self> {
+        let parse_error = |reason: String| ComputedColumnError::Parse {
+            expression: expression.to_string(),
+            reason,
+        };
+        let tokens = tokenize(expression).map_err(parse_error)?;
+        let mut parser = Parser::new(&tokens);
+        let parsed = parser.parse_sum().map_err(parse_error)?;
+        if let Some(token) = parser.peek() {
+            bail!(parse_error(format!("unexpected {token:?}")))
+        }
+        Ok(parsed)
+    }
+}
+
+impl ValueExpression {
+    /// Returns the names of all columns referenced by the expression.
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        match self {
+            ValueExpression::Column(column) => vec![column.as_str()],
+            ValueExpression::Number(_) | ValueExpression::Text(_) => vec![],
+            ValueExpression::Negate(inner) => inner.columns(),
+            ValueExpression::Arithmetic { left, right, .. } => {
+                let mut columns = left.columns();
+                columns.extend(right.columns());
+                columns
+            }
+            ValueExpression::Concat(arguments) => arguments
+                .iter()
+                .flat_map(|argument| argument.columns())
+                .collect(),
+        }
+    }
+
+    fn evaluate(&self, record: &[String], indices: &HashMap<String, usize>) -> Evaluation {
+        match self {
+            ValueExpression::Column(column) => {
+                let value = record
+                    .get(indices[column])
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                if value.is_na() {
+                    Evaluation::Missing
+                } else {
+                    Evaluation::Value(value.to_owned())
+                }
+            }
+            ValueExpression::Number(number) => Evaluation::Value(number.to_string()),
+            ValueExpression::Text(text) => Evaluation::Value(text.to_owned()),
+            ValueExpression::Negate(inner) => match inner.evaluate(record, indices).number() {
+                Ok(number) => Evaluation::Value((-number).to_string()),
+                Err(evaluation) => evaluation,
+            },
+            ValueExpression::Arithmetic {
+                left,
+                operator,
+                right,
+            } => {
+                let left = left.evaluate(record, indices).number();
+                let right = right.evaluate(record, indices).number();
+                match (left, right) {
+                    (Ok(left), Ok(right)) => operator
+                        .apply(left, right)
+                        .map_or(Evaluation::Invalid, |result| {
+                            Evaluation::Value(result.to_string())
+                        }),
+                    (Err(Evaluation::Invalid), _) | (_, Err(Evaluation::Invalid)) => {
+                        Evaluation::Invalid
+                    }
+                    _ => Evaluation::Missing,
+                }
+            }
+            ValueExpression::Concat(arguments) => {
+                let mut concatenated = String::new();
+                let mut missing = false;
+                for argument in arguments {
+                    match argument.evaluate(record, indices) {
+                        Evaluation::Value(value) => concatenated.push_str(&value),
+                        Evaluation::Missing => missing = true,
+                        Evaluation::Invalid => return Evaluation::Invalid,
+                    }
+                }
+                if missing {
+                    Evaluation::Missing
+                } else {
+                    Evaluation::Value(concatenated)
+                }
+            }
+        }
+    }
+}
+
+/// Computed columns whose referenced columns are resolved to the indices of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BoundComputedColumns {
+    expressions: Vec<(ValueExpression, HashMap<String, usize>)>,
+}
+
+impl BoundComputedColumns {
+    /// Resolves the columns referenced by the given computed columns against the given headers.
+    /// Computed columns may refer to the computed columns preceding them.
+    pub(crate) fn bind(columns: &[ComputedColumn], headers: &[String]) -> Result<Self> {
+        let mut headers = headers.to_vec();
+        let mut expressions = Vec::new();
+        for column in columns {
+            let mut indices = HashMap::new();
+            for referenced in column.expression.columns() {
+                match headers.iter().position(|header| header == referenced) {
+                    Some(index) => indices.insert(referenced.to_string(), index),
+                    None => bail!(ComputedColumnError::UnknownColumn {
+                        column: referenced.to_string()
+                    }),
+                };
+            }
+            expressions.push((column.expression.clone(), indices));
+            headers.push(column.name.clone());
+        }
+        Ok(BoundComputedColumns { expressions })
+    }
+
+    /// Appends the computed values to the given record, leaving invalid values empty.
+    /// Returns the number of invalid values.
+    pub(crate) fn append(&self, record: &mut Vec<String>) -> usize {
+        let mut invalid = 0;
+        for (expression, indices) in &self.expressions {
+            let value = match expression.evaluate(record, indices) {
+                Evaluation::Value(value) => value,
+                Evaluation::Missing => String::new(),
+                Evaluation::Invalid => {
+                    invalid += 1;
+                    String::new()
+                }
+            };
+            record.push(value);
+        }
+        invalid
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.expressions.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Number(f64),
+    Text(String),
+    Operator(ArithmeticOperator),
+    Comma,
+    LeftParenthesis,
+    RightParenthesis,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            ',' => Token::Comma,
+            '+' => Token::Operator(ArithmeticOperator::Add),
+            '-' => Token::Operator(ArithmeticOperator::Subtract),
+            '*' => Token::Operator(ArithmeticOperator::Multiply),
+            '/' => Token::Operator(ArithmeticOperator::Divide),
+            '"' | '\'' => Token::Text(quoted(&mut chars, c)?),
+            '`' => Token::Identifier(quoted(&mut chars, c)?),
+            c if c.is_ascii_digit() || c == '.' => {
+                let number = scan_number(&mut chars, c);
+                Token::Number(
+                    f64::from_str(&number).map_err(|_| format!("invalid number {number}"))?,
+                )
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                Token::Identifier(scan_identifier(&mut chars, c, "_."))
+            }
+            c => return Err(format!("unexpected character {c:?}")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+impl Parser<'_, Token> {
+    fn parse_sum(&mut self) -> Result<ValueExpression, String> {
+        let mut expression = self.parse_product()?;
+        while let Some(Token::Operator(
+            operator @ (ArithmeticOperator::Add | ArithmeticOperator::Subtract),
+        )) = self.peek()
+        {
+            let operator = *operator;
+            self.next();
+            expression = ValueExpression::Arithmetic {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(self.parse_product()?),
+            };
+        }
+        Ok(expression)
+    }
+
+    fn parse_product(&mut self) -> Result<ValueExpression, String> {
+        let mut expression = self.parse_unary()?;
+        while let Some(Token::Operator(
+            operator @ (ArithmeticOperator::Multiply | ArithmeticOperator::Divide),
+        )) = self.peek()
+        {
+            let operator = *operator;
+            self.next();
+            expression = ValueExpression::Arithmetic {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(self.parse_unary()?),
+            };
+        }
+        Ok(expression)
+    }
+
+    fn parse_unary(&mut self) -> Result<ValueExpression, String> {
+        match self.next() {
+            Some(Token::Operator(ArithmeticOperator::Subtract)) => {
+                Ok(ValueExpression::Negate(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::LeftParenthesis) => {
+                let expression = self.parse_sum()?;
+                match self.next() {
+                    Some(Token::RightParenthesis) => Ok(expression),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            Some(Token::Number(number)) => Ok(ValueExpression::Number(*number)),
+            Some(Token::Text(text)) => Ok(ValueExpression::Text(text.to_owned())),
+            Some(Token::Identifier(identifier)) => {
+                let identifier = identifier.to_owned();
+                if self.peek() == Some(&Token::LeftParenthesis) {
+                    self.next();
+                    self.parse_function(&identifier)
+                } else {
+                    Ok(ValueExpression::Column(identifier))
+                }
+            }
+            Some(token) => Err(format!("expected column or value, found {token:?}")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+
+    fn parse_function(&mut self, name: &str) -> Result<ValueExpression, String> {
+        if name != "concat" {
+            return Err(format!("unknown function {name}"));
+        }
+        let mut arguments = vec![self.parse_sum()?];
+        loop {
+            match self.next() {
+                Some(Token::Comma) => arguments.push(self.parse_sum()?),
+                Some(Token::RightParenthesis) => return Ok(ValueExpression::Concat(arguments)),
+                _ => return Err("missing closing parenthesis".to_string()),
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ComputedColumnError {
+    #[error("Could not parse expression {expression:?}: {reason}.")]
+    Parse { expression: String, reason: String },
+    #[error("Expression refers to unknown column {column:?}.")]
+    UnknownColumn { column: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::computed::{BoundComputedColumns, ComputedColumn, ValueExpression};
+    use std::str::FromStr;
+
+    fn compute(expressions: &[(&str, &str)], record: &[&str]) -> (Vec<String>, usize) {
+        let headers = ["chrom", "pos", "alt_count", "depth"].map(String::from);
+        let columns = expressions
+            .iter()
+            .map(|(name, expression)| ComputedColumn {
+                name: name.to_string(),
+                expression: ValueExpression::from_str(expression).unwrap(),
+            })
+            .collect::<Vec<_>>();
+        let bound = BoundComputedColumns::bind(&columns, &headers).unwrap();
+        let mut record = record.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let invalid = bound.append(&mut record);
+        (record.split_off(headers.len()), invalid)
+    }
+
+    #[test]
+    fn test_computed_columns() {
+        let expressions = [
+            ("af", "alt_count / depth"),
+            ("label", r#"concat(chrom, ":", pos)"#),
+            ("percent", "af * 100"),
+            ("shifted", "-(pos - 1) + 2 * 3"),
+        ];
+        assert_eq!(
+            compute(&expressions, &["chr1", "10", "5", "20"]),
+            (
+                vec!["0.25", "chr1:10", "25", "-3"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                0
+            )
+        );
+        // Division by zero and non-numeric operands yield empty cells.
+        let (values, invalid) = compute(&expressions, &["chr1", "x", "5", "0"]);
+        assert_eq!(values, vec!["", "chr1:x", "", ""]);
+        assert_eq!(invalid, 2);
+        // Missing values are propagated without being counted as invalid.
+        let (values, invalid) = compute(&expressions, &["chr1", "NA", "", "20"]);
+        assert_eq!(values, vec!["", "", "", ""]);
+        assert_eq!(invalid, 0);
+    }
+
+    #[test]
+    fn test_invalid_computed_column() {
+        for expression in ["depth /", "concat(chrom", "log(depth)", "`depth", "(depth"] {
+            let error = ValueExpression::from_str(expression).unwrap_err();
+            assert!(error.to_string().starts_with("Could not parse expression"));
+        }
+        let columns = [ComputedColumn {
+            name: "af".to_string(),
+            expression: ValueExpression::from_str("alt / depth").unwrap(),
+        }];
+        let error = BoundComputedColumns::bind(&columns, &["depth".to_string()]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Expression refers to unknown column "alt"."#
+        );
+    }
+}
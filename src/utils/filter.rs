@@ -0,0 +1,331 @@
+use crate::utils::lexer::{quoted, scan_identifier, scan_number, Parser};
+use anyhow::{bail, Result};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Boolean expression over the columns of a record, e.g. `qual >= 30 && filter == "PASS"`.
+/// Column names that are not plain identifiers can be quoted with backticks.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FilterExpression {
+    And(Box<FilterExpression>, Box<FilterExpression>),
+    Or(Box<FilterExpression>, Box<FilterExpression>),
+    Not(Box<FilterExpression>),
+    Comparison {
+        left: Operand,
+        operator: Operator,
+        right: Operand,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Operand {
+    Column(String),
+    Literal(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Operator {
+    fn holds(&self, ordering: Option<Ordering>) -> bool {
+        match (self, ordering) {
+            (Operator::Ne, None) => true,
+            (_, None) => false,
+            (Operator::Eq, Some(ordering)) => ordering.is_eq(),
+            (Operator::Ne, Some(ordering)) => ordering.is_ne(),
+            (Operator::Lt, Some(ordering)) => ordering.is_lt(),
+            (Operator::Le, Some(ordering)) => ordering.is_le(),
+            (Operator::Gt, Some(ordering)) => ordering.is_gt(),
+            (Operator::Ge, Some(ordering)) => ordering.is_ge(),
+        }
+    }
+}
+
+impl FromStr for FilterExpression {
+    type Err = anyhow::Error;
+
+    fn from_str(expression: &str) -> Result<Self> {
+        let parse_error = |reason: String| FilterError::Parse {
+            expression: expression.to_string(),
+            reason,
+        };
+        let tokens = tokenize(expression).map_err(parse_error)?;
+        let mut parser = Parser::new(&tokens);
+        let parsed = parser.parse_or().map_err(parse_error)?;
+        if let Some(token) = parser.peek() {
+            bail!(parse_error(format!("unexpected {token:?}")))
+        }
+        Ok(parsed)
+    }
+}
+
+impl FilterExpression {
+    /// Returns the names of all columns referenced by the expression.
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        match self {
+            FilterExpression::And(left, right) | FilterExpression::Or(left, right) => {
+                let mut columns = left.columns();
+                columns.extend(right.columns());
+                columns
+            }
+            FilterExpression::Not(inner) => inner.columns(),
+            FilterExpression::Comparison { left, right, .. } => [left, right]
+                .into_iter()
+                .filter_map(|operand| match operand {
+                    Operand::Column(column) => Some(column.as_str()),
+                    Operand::Literal(_) => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Resolves the referenced columns against the given headers.
+    pub(crate) fn bind(&self, headers: &[String]) -> Result<RowFilter> {
+        let mut indices = HashMap::new();
+        for column in self.columns() {
+            match headers.iter().position(|header| header == column) {
+                Some(index) => indices.insert(column.to_string(), index),
+                None => bail!(FilterError::UnknownColumn {
+                    column: column.to_string()
+                }),
+            };
+        }
+        Ok(RowFilter {
+            expression: self.clone(),
+            indices,
+        })
+    }
+
+    fn evaluate(&self, record: &[String], indices: &HashMap<String, usize>) -> bool {
+        match self {
+            FilterExpression::And(left, right) => {
+                left.evaluate(record, indices) && right.evaluate(record, indices)
+            }
+            FilterExpression::Or(left, right) => {
+                left.evaluate(record, indices) || right.evaluate(record, indices)
+            }
+            FilterExpression::Not(inner) => !inner.evaluate(record, indices),
+            FilterExpression::Comparison {
+                left,
+                operator,
+                right,
+            } => {
+                let value = |operand: &'_ Operand| match operand {
+                    Operand::Column(column) => record
+                        .get(indices[column])
+                        .map(String::as_str)
+                        .unwrap_or_default()
+                        .to_owned(),
+                    Operand::Literal(literal) => literal.to_owned(),
+                };
+                operator.holds(compare(&value(left), &value(right)))
+            }
+        }
+    }
+}
+
+/// Compares the given values numerically if both are numbers and lexicographically otherwise.
+fn compare(left: &str, right: &str) -> Option<Ordering> {
+    match (f64::from_str(left), f64::from_str(right)) {
+        (Ok(left), Ok(right)) => left.partial_cmp(&right),
+        _ => Some(left.cmp(right)),
+    }
+}
+
+/// Filter expression whose columns are resolved to the indices of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RowFilter {
+    expression: FilterExpression,
+    indices: HashMap<String, usize>,
+}
+
+impl RowFilter {
+    pub(crate) fn matches(&self, record: &[String]) -> bool {
+        self.expression.evaluate(record, &self.indices)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Literal(String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    LeftParenthesis,
+    RightParenthesis,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Eq),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Ne),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Le),
+            '<' => Token::Operator(Operator::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Ge),
+            '>' => Token::Operator(Operator::Gt),
+            '"' | '\'' => Token::Literal(quoted(&mut chars, c)?),
+            '`' => Token::Identifier(quoted(&mut chars, c)?),
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let number = scan_number(&mut chars, c);
+                if f64::from_str(&number).is_err() {
+                    return Err(format!("invalid number {number}"));
+                }
+                Token::Literal(number)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                Token::Identifier(scan_identifier(&mut chars, c, "_.-"))
+            }
+            c => return Err(format!("unexpected character {c:?}")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+impl Parser<'_, Token> {
+    fn parse_or(&mut self) -> Result<FilterExpression, String> {
+        let mut expression = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expression = FilterExpression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpression, String> {
+        let mut expression = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expression = FilterExpression::And(Box::new(expression), Box::new(self.parse_unary()?));
+        }
+        Ok(expression)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpression, String> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next();
+                Ok(FilterExpression::Not(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::LeftParenthesis) => {
+                self.next();
+                let expression = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RightParenthesis) => Ok(expression),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            _ => self.parse_comparison(),
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<FilterExpression, String> {
+        let left = self.parse_operand()?;
+        let operator = match self.next() {
+            Some(Token::Operator(operator)) => *operator,
+            Some(token) => return Err(format!("expected comparison operator, found {token:?}")),
+            None => return Err("expected comparison operator".to_string()),
+        };
+        let right = self.parse_operand()?;
+        Ok(FilterExpression::Comparison {
+            left,
+            operator,
+            right,
+        })
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Identifier(column)) => Ok(Operand::Column(column.to_owned())),
+            Some(Token::Literal(literal)) => Ok(Operand::Literal(literal.to_owned())),
+            Some(token) => Err(format!("expected column or value, found {token:?}")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FilterError {
+    #[error("Could not parse filter expression {expression:?}: {reason}.")]
+    Parse { expression: String, reason: String },
+    #[error("Filter expression refers to unknown column {column:?}.")]
+    UnknownColumn { column: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::filter::FilterExpression;
+    use std::str::FromStr;
+
+    fn matches(expression: &str, record: &[&str]) -> bool {
+        let headers = ["qual", "filter", "gene name"].map(String::from);
+        FilterExpression::from_str(expression)
+            .unwrap()
+            .bind(&headers)
+            .unwrap()
+            .matches(&record.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_filter_expression() {
+        let expression = r#"qual >= 30 && filter == "PASS""#;
+        assert!(matches(expression, &["30", "PASS", "BRCA1"]));
+        assert!(matches(expression, &["100.5", "PASS", "BRCA1"]));
+        // Numbers are compared numerically, not lexicographically.
+        assert!(!matches(expression, &["4", "PASS", "BRCA1"]));
+        assert!(!matches(expression, &["30", "LowQual", "BRCA1"]));
+        assert!(matches(
+            "!(qual < 1e1) || `gene name` != 'BRCA1'",
+            &["5", "PASS", "TP53"]
+        ));
+        assert!(!matches(
+            "!(qual < 1e1) || `gene name` != 'BRCA1'",
+            &["5", "PASS", "BRCA1"]
+        ));
+        assert!(matches("filter > qual", &["30", "PASS", ""]));
+    }
+
+    #[test]
+    fn test_invalid_filter_expression() {
+        for expression in [
+            "qual >",
+            "qual = 30",
+            "(qual > 3",
+            "qual > 3 filter",
+            "'PASS",
+        ] {
+            let error = FilterExpression::from_str(expression).unwrap_err();
+            assert!(error
+                .to_string()
+                .starts_with("Could not parse filter expression"));
+        }
+        let error = FilterExpression::from_str("depth > 3")
+            .unwrap()
+            .bind(&["qual".to_string()])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Filter expression refers to unknown column "depth"."#
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::f64::consts::PI;
+
+/// Estimates the density of the given values with a Gaussian kernel of the given bandwidth at
+/// `n_points` evenly spaced points spanning the range of the values.
+pub(crate) fn compute_kde(values: &[f64], bandwidth: f64, n_points: usize) -> Vec<(f64, f64)> {
+    if values.is_empty() || bandwidth <= 0.0 || n_points == 0 {
+        return Vec::new();
+    }
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let step = if n_points > 1 {
+        (max - min) / (n_points - 1) as f64
+    } else {
+        0.0
+    };
+    let normalization = values.len() as f64 * bandwidth * (2.0 * PI).sqrt();
+    (0..n_points)
+        .map(|i| {
+            let x = min + i as f64 * step;
+            let density = values
+                .iter()
+                .map(|value| (-0.5 * ((x - value) / bandwidth).powi(2)).exp())
+                .sum::<f64>()
+                / normalization;
+            (x, density)
+        })
+        .collect()
+}
+
+/// Estimates a suitable bandwidth for [`compute_kde`] with Silverman's rule of thumb. The given
+/// values have to be sorted.
+pub(crate) fn silverman_bandwidth(sorted: &[f64]) -> f64 {
+    let n = sorted.len() as f64;
+    if sorted.len() < 2 {
+        return 0.0;
+    }
+    let mean = sorted.iter().sum::<f64>() / n;
+    let stddev = (sorted
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.0))
+        .sqrt();
+    let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+    // The interquartile range is zero if most values are equal, so fall back to the deviation.
+    let spread = if iqr > 0.0 {
+        stddev.min(iqr / 1.34)
+    } else {
+        stddev
+    };
+    0.9 * spread * n.powf(-0.2)
+}
+
+/// Linearly interpolated quantile of the given sorted values.
+pub(crate) fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::kde::{compute_kde, quantile, silverman_bandwidth};
+
+    #[test]
+    fn test_kde_of_uniform_distribution() {
+        let values = (0..=1000).map(|i| i as f64 / 1000.0).collect::<Vec<_>>();
+        let density = compute_kde(&values, 0.05, 101);
+        assert_eq!(density.len(), 101);
+        assert_eq!(density[0].0, 0.0);
+        assert!((density[100].0 - 1.0).abs() < 1e-12);
+        // Away from the boundaries the density of the uniform distribution on [0, 1] is 1.
+        for (x, density) in &density[20..=80] {
+            assert!((density - 1.0).abs() < 0.01, "density {density} at {x}");
+        }
+        // Half of the kernel mass at the boundaries lies outside the range of the values.
+        assert!((density[0].1 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_kde_of_single_value() {
+        let density = compute_kde(&[2.0], 1.0, 1);
+        assert_eq!(density.len(), 1);
+        assert!((density[0].1 - 1.0 / (2.0 * std::f64::consts::PI).sqrt()).abs() < 1e-12);
+        assert!(compute_kde(&[2.0], 0.0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_silverman_bandwidth() {
+        let values = (1..=11).map(f64::from).collect::<Vec<_>>();
+        assert_eq!(quantile(&values, 0.5), 6.0);
+        // The deviation of 1 to 11 is sqrt(11) and smaller than the scaled interquartile range.
+        let expected = 0.9 * 11_f64.sqrt() * 11_f64.powf(-0.2);
+        assert!((silverman_bandwidth(&values) - expected).abs() < 1e-12);
+        assert_eq!(silverman_bandwidth(&[1.0]), 0.0);
+    }
+}
@@ -0,0 +1,99 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Reads the remainder of a quoted string, allowing the quote to be escaped with a backslash.
+pub(crate) fn quoted(chars: &mut Peekable<Chars>, quote: char) -> Result<String, String> {
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&quote) => value.push(chars.next().unwrap()),
+            c if c == quote => return Ok(value),
+            c => value.push(c),
+        }
+    }
+    Err(format!("unterminated quote {quote}"))
+}
+
+/// Reads the remainder of a number starting with `first`. The result is not validated.
+pub(crate) fn scan_number(chars: &mut Peekable<Chars>, first: char) -> String {
+    let mut number = first.to_string();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.') {
+        number.push(c);
+        // Exponents may be signed, e.g. 1e-5.
+        if c == 'e' || c == 'E' {
+            if let Some(sign) = chars.next_if(|c| *c == '-' || *c == '+') {
+                number.push(sign);
+            }
+        }
+    }
+    number
+}
+
+/// Reads the remainder of an identifier starting with `first`, made of alphanumeric characters
+/// and the given punctuation.
+pub(crate) fn scan_identifier(
+    chars: &mut Peekable<Chars>,
+    first: char,
+    punctuation: &str,
+) -> String {
+    let mut identifier = first.to_string();
+    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || punctuation.contains(*c)) {
+        identifier.push(c);
+    }
+    identifier
+}
+
+/// Cursor over the tokens of an expression, extended with the grammar of each expression type.
+pub(crate) struct Parser<'a, T> {
+    tokens: &'a [T],
+    position: usize,
+}
+
+impl<'a, T> Parser<'a, T> {
+    pub(crate) fn new(tokens: &'a [T]) -> Self {
+        Parser {
+            tokens,
+            position: 0,
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<&'a T> {
+        self.tokens.get(self.position)
+    }
+
+    pub(crate) fn next(&mut self) -> Option<&'a T> {
+        self.position += 1;
+        self.tokens.get(self.position - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quoted() {
+        let mut chars = r#"a \"b\" c" rest"#.chars().peekable();
+        assert_eq!(quoted(&mut chars, '"').unwrap(), r#"a "b" c"#);
+        assert_eq!(chars.collect::<String>(), " rest");
+        let mut chars = "unterminated".chars().peekable();
+        assert!(quoted(&mut chars, '\'').is_err());
+    }
+
+    #[test]
+    fn test_scan_number() {
+        let mut chars = ".5e-3 + 1".chars().peekable();
+        let first = chars.next().unwrap();
+        assert_eq!(scan_number(&mut chars, first), ".5e-3");
+    }
+
+    #[test]
+    fn test_scan_identifier() {
+        let mut chars = "gene-name.x y".chars().peekable();
+        let first = chars.next().unwrap();
+        assert_eq!(scan_identifier(&mut chars, first, "_."), "gene");
+        let mut chars = "gene-name.x y".chars().peekable();
+        let first = chars.next().unwrap();
+        assert_eq!(scan_identifier(&mut chars, first, "_.-"), "gene-name.x");
+    }
+}
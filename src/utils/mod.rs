@@ -1,10 +1,23 @@
 use crate::spec::DatasetSpecs;
 use anyhow::Result;
 
+#[cfg(feature = "arrow")]
+pub(crate) mod arrow;
 pub(crate) mod column_index;
+pub(crate) mod column_stats;
 pub(crate) mod column_type;
 pub(crate) mod compress;
+pub(crate) mod computed;
+pub(crate) mod filter;
+pub(crate) mod kde;
+pub(crate) mod lexer;
+pub(crate) mod natural_sort;
+pub(crate) mod number_format;
+pub(crate) mod reader;
+pub(crate) mod remote;
+pub(crate) mod render_cache;
 pub(crate) mod row_address;
+pub(crate) mod watch;
 
 /// Returns the index of the given column of a csv header
 pub(crate) fn column_position(column: &str, dataset: &DatasetSpecs) -> Result<usize> {
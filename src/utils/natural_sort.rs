@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+
+/// Compares the given strings by their runs of digits and other characters, where runs of
+/// digits are compared by their numeric value and other runs case-insensitively, e.g. `sample2`
+/// comes before `sample10` and `v1.2.9` before `v1.2.10`. Strings that are equal in this sense,
+/// e.g. `a01` and `A1`, are ordered by their characters.
+pub(crate) fn natural_compare(a: &str, b: &str) -> Ordering {
+    let (mut runs_a, mut runs_b) = (runs(a), runs(b));
+    loop {
+        match (runs_a.next(), runs_b.next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(run_a), Some(run_b)) => match compare_runs(run_a, run_b) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            },
+        }
+    }
+}
+
+/// Splits the given string into alternating runs of ASCII digits and other characters.
+fn runs(value: &str) -> impl Iterator<Item = &str> {
+    let mut rest = value;
+    std::iter::from_fn(move || {
+        let is_digit = rest.chars().next()?.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(end);
+        rest = tail;
+        Some(run)
+    })
+}
+
+fn compare_runs(a: &str, b: &str) -> Ordering {
+    let is_number = |run: &str| run.starts_with(|c: char| c.is_ascii_digit());
+    if is_number(a) && is_number(b) {
+        // Numbers of any length are compared without parsing them, ignoring leading zeros.
+        let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+        a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+    } else {
+        a.chars()
+            .flat_map(char::to_lowercase)
+            .cmp(b.chars().flat_map(char::to_lowercase))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::natural_sort::natural_compare;
+    use itertools::Itertools;
+    use std::cmp::Ordering;
+
+    fn sorted(values: &[&str]) -> Vec<String> {
+        values
+            .iter()
+            .sorted_by(|a, b| natural_compare(a, b))
+            .map(|value| value.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(
+            sorted(&["sample1", "sample10", "sample2"]),
+            vec!["sample1", "sample2", "sample10"]
+        );
+        assert_eq!(sorted(&["3", "b", "1", "a"]), vec!["1", "3", "a", "b"]);
+        assert_eq!(natural_compare("sample", "sample1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_multi_digit_numbers() {
+        assert_eq!(
+            sorted(&["chr10", "chr9", "chr100", "chr21", "chr1"]),
+            vec!["chr1", "chr9", "chr10", "chr21", "chr100"]
+        );
+        // Numbers beyond the range of integers are compared as well.
+        assert_eq!(
+            natural_compare("id123456789012345678901234567890", "id99"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        assert_eq!(
+            sorted(&["file010", "file9", "file001"]),
+            vec!["file001", "file9", "file010"]
+        );
+        // Equal numbers are ordered by their characters.
+        assert_eq!(natural_compare("a01", "a1"), Ordering::Less);
+        assert_eq!(natural_compare("a1", "a1"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_version_strings() {
+        assert_eq!(natural_compare("v1.2.10", "v1.2.9"), Ordering::Greater);
+        assert_eq!(
+            sorted(&["v1.10.0", "v1.2.10", "v1.2.9", "v2.0"]),
+            vec!["v1.2.9", "v1.2.10", "v1.10.0", "v2.0"]
+        );
+    }
+
+    #[test]
+    fn test_mixed_case_identifiers() {
+        assert_eq!(
+            sorted(&["Sample10", "sample2", "SAMPLE1", "sampleB", "Samplea"]),
+            vec!["SAMPLE1", "sample2", "Sample10", "Samplea", "sampleB"]
+        );
+        assert_eq!(natural_compare("ABC", "abc"), Ordering::Less);
+    }
+}
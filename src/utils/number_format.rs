@@ -0,0 +1,399 @@
+use anyhow::{bail, Result};
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+use std::str::FromStr;
+use thiserror::Error;
+
+lazy_static! {
+    /// Format specifier of d3-format: [[fill]align][sign][symbol][0][width][,][.precision][~][type]
+    static ref SPECIFIER_RE: Regex = Regex::new(
+        r"^(?:(.)?([<>=^]))?([+\-( ])?([$#])?(0)?(\d+)?(,)?(\.\d+)?(~)?([a-zA-Z%])?$"
+    )
+    .unwrap();
+}
+
+/// Minus sign used by d3-format for negative numbers.
+const MINUS: char = '\u{2212}';
+
+/// SI prefixes from yocto to yotta as used by the `s` type.
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// Formats the given number according to the given d3-format specifier, e.g. `,.2f` for two
+/// decimals with thousands separators. Falls back to the plain number if the specifier is
+/// invalid.
+pub(crate) fn format_number(value: f64, format: &str) -> String {
+    match FormatSpecifier::from_str(format) {
+        Ok(specifier) => specifier.format(value),
+        Err(_) => value.to_string(),
+    }
+}
+
+/// Parsed d3-format specifier, see https://d3js.org/d3-format#locale_format.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FormatSpecifier {
+    fill: char,
+    align: char,
+    sign: char,
+    symbol: Option<char>,
+    zero: bool,
+    width: usize,
+    comma: bool,
+    precision: Option<usize>,
+    trim: bool,
+    format_type: Option<char>,
+}
+
+impl FromStr for FormatSpecifier {
+    type Err = anyhow::Error;
+
+    fn from_str(specifier: &str) -> Result<Self> {
+        let invalid = || NumberFormatError::InvalidSpecifier {
+            specifier: specifier.to_string(),
+        };
+        let Some(captures) = SPECIFIER_RE.captures(specifier)? else {
+            bail!(invalid())
+        };
+        let group = |index| captures.get(index).map(|group| group.as_str());
+        let single = |index| group(index).and_then(|value| value.chars().next());
+        let format_type = single(10);
+        if format_type.is_some_and(|format_type| !"efgrs%pbodxXn".contains(format_type)) {
+            bail!(invalid())
+        }
+        Ok(FormatSpecifier {
+            fill: single(1).unwrap_or(' '),
+            align: single(2).unwrap_or('>'),
+            sign: single(3).unwrap_or('-'),
+            symbol: single(4),
+            zero: group(5).is_some(),
+            width: group(6).map_or(Ok(0), usize::from_str)?,
+            comma: group(7).is_some(),
+            precision: group(8)
+                .map(|precision| usize::from_str(&precision[1..]))
+                .transpose()?,
+            trim: group(9).is_some(),
+            format_type,
+        })
+    }
+}
+
+impl FormatSpecifier {
+    /// Formats the given number, following the implementation of d3-format.
+    pub(crate) fn format(&self, value: f64) -> String {
+        let mut fill = self.fill;
+        let mut align = self.align;
+        let mut comma = self.comma;
+        let mut trim = self.trim;
+        let mut precision = self.precision;
+        let mut format_type = self.format_type.unwrap_or('g');
+        match self.format_type {
+            Some('n') => {
+                comma = true;
+                format_type = 'g';
+            }
+            None => {
+                precision = precision.or(Some(12));
+                trim = true;
+            }
+            _ => {}
+        }
+        let zero = self.zero || (fill == '0' && align == '=');
+        if zero {
+            fill = '0';
+            align = '=';
+        }
+        let precision = match (precision, format_type) {
+            (None, _) => 6,
+            (Some(precision), 'g' | 'p' | 'r' | 's') => precision.clamp(1, 21),
+            (Some(precision), _) => precision.min(20),
+        };
+
+        let mut prefix = match self.symbol {
+            Some('$') => "$".to_string(),
+            Some('#') if "boxX".contains(format_type) => {
+                format!("0{}", format_type.to_ascii_lowercase())
+            }
+            _ => String::new(),
+        };
+        let mut suffix = if "%p".contains(format_type) {
+            "%".to_string()
+        } else {
+            String::new()
+        };
+
+        let mut negative = value < 0.0 || (1.0 / value) < 0.0;
+        let (mut formatted, si_prefix) = if value.is_nan() {
+            ("NaN".to_string(), None)
+        } else {
+            format_type_value(value.abs(), precision, format_type)
+        };
+        if trim {
+            formatted = trim_insignificant_zeros(&formatted);
+        }
+        // Values that are rounded to zero are not signed.
+        if negative && f64::from_str(&formatted) == Ok(0.0) && self.sign != '+' {
+            negative = false;
+        }
+        prefix = match (negative, self.sign) {
+            (true, '(') => format!("({prefix}"),
+            (true, _) => format!("{MINUS}{prefix}"),
+            (false, '-' | '(') => prefix,
+            (false, sign) => format!("{sign}{prefix}"),
+        };
+        suffix = format!(
+            "{}{suffix}{}",
+            si_prefix.unwrap_or_default(),
+            if negative && self.sign == '(' {
+                ")"
+            } else {
+                ""
+            }
+        );
+
+        // Only the leading digits are grouped and padded with zeros.
+        if "defgprs%".contains(format_type) {
+            if let Some(position) = formatted.find(|c: char| !c.is_ascii_digit()) {
+                suffix = format!("{}{suffix}", &formatted[position..]);
+                formatted.truncate(position);
+            }
+        }
+        if comma && !zero {
+            formatted = group(&formatted, usize::MAX);
+        }
+        let length = prefix.chars().count() + formatted.chars().count() + suffix.chars().count();
+        let mut padding = fill.to_string().repeat(self.width.saturating_sub(length));
+        if comma && zero {
+            let width = if padding.is_empty() {
+                usize::MAX
+            } else {
+                self.width - suffix.chars().count()
+            };
+            formatted = group(&format!("{padding}{formatted}"), width);
+            padding.clear();
+        }
+        match align {
+            '<' => format!("{prefix}{formatted}{suffix}{padding}"),
+            '=' => format!("{prefix}{padding}{formatted}{suffix}"),
+            '^' => {
+                let (left, right) = padding.split_at(padding.chars().count() / 2 * fill.len_utf8());
+                format!("{left}{prefix}{formatted}{suffix}{right}")
+            }
+            _ => format!("{padding}{prefix}{formatted}{suffix}"),
+        }
+    }
+}
+
+/// Formats the given non-negative number according to the given type. Returns the SI prefix
+/// for the `s` type.
+fn format_type_value(
+    value: f64,
+    precision: usize,
+    format_type: char,
+) -> (String, Option<&'static str>) {
+    if !value.is_finite() {
+        return (non_finite(value), None);
+    }
+    let formatted = match format_type {
+        'e' => exponential(value, precision),
+        'f' => format!("{value:.precision$}"),
+        'g' => to_precision(value, precision),
+        'r' => rounded(value, precision),
+        '%' => format!("{:.precision$}", value * 100.0),
+        'p' => rounded(value * 100.0, precision),
+        's' => return si(value, precision),
+        'b' => format!("{:b}", value.round() as u128),
+        'o' => format!("{:o}", value.round() as u128),
+        'x' => format!("{:x}", value.round() as u128),
+        'X' => format!("{:X}", value.round() as u128),
+        // Integers
+        _ => format!("{}", value.round()),
+    };
+    (formatted, None)
+}
+
+/// Returns the significant digits of the given number rounded to the given precision and its
+/// decimal exponent, e.g. ("123", 4) for 12345 and precision 3.
+fn decimal_parts(value: f64, precision: usize) -> Option<(String, i32)> {
+    if !value.is_finite() {
+        return None;
+    }
+    let formatted = format!("{:.*e}", precision.max(1) - 1, value);
+    let (coefficient, exponent) = formatted.split_once('e')?;
+    Some((coefficient.replace('.', ""), i32::from_str(exponent).ok()?))
+}
+
+/// Formats the given number in exponent notation like `Number.toExponential`.
+fn exponential(value: f64, precision: usize) -> String {
+    if !value.is_finite() {
+        return non_finite(value);
+    }
+    let formatted = format!("{value:.precision$e}");
+    let (coefficient, exponent) = formatted.split_once('e').unwrap();
+    match exponent.strip_prefix('-') {
+        Some(exponent) => format!("{coefficient}e-{exponent}"),
+        None => format!("{coefficient}e+{exponent}"),
+    }
+}
+
+/// Formats the given number with the given number of significant digits like
+/// `Number.toPrecision`, i.e. in exponent notation if the exponent is small or large.
+fn to_precision(value: f64, precision: usize) -> String {
+    let Some((_, exponent)) = decimal_parts(value, precision) else {
+        return non_finite(value);
+    };
+    if exponent < -6 || exponent >= precision as i32 {
+        exponential(value, precision - 1)
+    } else {
+        let decimals = (precision as i32 - 1 - exponent) as usize;
+        format!("{value:.decimals$}")
+    }
+}
+
+/// Formats the given number rounded to the given number of significant digits in decimal
+/// notation.
+fn rounded(value: f64, precision: usize) -> String {
+    let Some((digits, exponent)) = decimal_parts(value, precision) else {
+        return non_finite(value);
+    };
+    if exponent < 0 {
+        format!("0.{}{digits}", "0".repeat((-exponent - 1) as usize))
+    } else if digits.len() > exponent as usize + 1 {
+        let (integer, fraction) = digits.split_at(exponent as usize + 1);
+        format!("{integer}.{fraction}")
+    } else {
+        format!(
+            "{digits}{}",
+            "0".repeat(exponent as usize + 1 - digits.len())
+        )
+    }
+}
+
+/// Formats the given number with the given number of significant digits and an SI prefix.
+fn si(value: f64, precision: usize) -> (String, Option<&'static str>) {
+    let Some((digits, exponent)) = decimal_parts(value, precision) else {
+        return (non_finite(value), None);
+    };
+    let prefix_exponent = (exponent as f64 / 3.0).floor().clamp(-8.0, 8.0) as i32 * 3;
+    let i = exponent - prefix_exponent + 1;
+    let n = digits.len() as i32;
+    let formatted = if i == n {
+        digits
+    } else if i > n {
+        format!("{digits}{}", "0".repeat((i - n) as usize))
+    } else if i > 0 {
+        let (integer, fraction) = digits.split_at(i as usize);
+        format!("{integer}.{fraction}")
+    } else {
+        let digits = decimal_parts(value, (precision as i32 + i - 1).max(1) as usize)
+            .map(|(digits, _)| digits)
+            .unwrap_or_default();
+        format!("0.{}{digits}", "0".repeat(-i as usize))
+    };
+    (
+        formatted,
+        Some(SI_PREFIXES[(8 + prefix_exponent / 3) as usize]),
+    )
+}
+
+/// Formats infinite values like JavaScript.
+fn non_finite(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else {
+        "Infinity".to_string()
+    }
+}
+
+/// Removes insignificant trailing zeros of the decimal part, e.g. 1.50e+3 becomes 1.5e+3.
+fn trim_insignificant_zeros(formatted: &str) -> String {
+    let Some(point) = formatted.find('.') else {
+        return formatted.to_string();
+    };
+    let end = formatted[point + 1..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(formatted.len(), |end| point + 1 + end);
+    let decimals = formatted[point..end]
+        .trim_end_matches('0')
+        .trim_end_matches('.');
+    format!("{}{decimals}{}", &formatted[..point], &formatted[end..])
+}
+
+/// Groups the given digits by thousands, stopping once the given width is reached.
+fn group(digits: &str, width: usize) -> String {
+    let chars = digits.chars().collect::<Vec<_>>();
+    let mut groups = Vec::new();
+    let mut end = chars.len();
+    let mut length = 0;
+    while end > 0 {
+        let mut size = 3;
+        if length + size + 1 > width {
+            size = width.saturating_sub(length).max(1);
+        }
+        let start = end.saturating_sub(size);
+        groups.push(chars[start..end].iter().collect::<String>());
+        end = start;
+        length += size + 1;
+        if length > width {
+            break;
+        }
+    }
+    groups.reverse();
+    groups.join(",")
+}
+
+#[derive(Error, Debug)]
+pub enum NumberFormatError {
+    #[error("Invalid number format {specifier:?}.")]
+    InvalidSpecifier { specifier: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::number_format::format_number;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1234567.891234, ",.2f"), "1,234,567.89");
+        assert_eq!(format_number(-1234.5, ",.2f"), "−1,234.50");
+        assert_eq!(format_number(123456.0, ".3e"), "1.235e+5");
+        assert_eq!(format_number(0.000012, ".2e"), "1.20e-5");
+        assert_eq!(format_number(0.123, ".1%"), "12.3%");
+        assert_eq!(format_number(1500.0, ".2s"), "1.5k");
+        assert_eq!(format_number(0.00042, ".3s"), "420µ");
+        assert_eq!(format_number(1.23456, ".3r"), "1.23");
+        assert_eq!(format_number(42.0, "08.2f"), "00042.00");
+        assert_eq!(format_number(1234.0, "010,d"), "00,001,234");
+        assert_eq!(format_number(-3.0, "(.1f"), "(3.0)");
+        assert_eq!(format_number(3.0, "+d"), "+3");
+        assert_eq!(format_number(255.0, "#x"), "0xff");
+        assert_eq!(format_number(42.0, "^7d"), "  42   ");
+        assert_eq!(format_number(1.5, "$.2f"), "$1.50");
+        assert_eq!(format_number(1.50, ".3~f"), "1.5");
+        assert_eq!(format_number(0.1 + 0.2, ""), "0.3");
+        // Values that are rounded to zero are not signed.
+        assert_eq!(format_number(-0.0001, ".2f"), "0.00");
+    }
+
+    #[test]
+    fn test_format_special_numbers() {
+        assert_eq!(format_number(f64::NAN, ",.2f"), "NaN");
+        assert_eq!(format_number(f64::INFINITY, ",.2f"), "Infinity");
+        assert_eq!(format_number(f64::NEG_INFINITY, ".3e"), "−Infinity");
+        assert_eq!(
+            format_number(12345678901234567890.0, ",d"),
+            "12,345,678,901,234,567,000"
+        );
+        assert_eq!(format_number(1e21, "d"), "1000000000000000000000");
+        // Numbers of very different magnitudes switch to exponent notation.
+        assert_eq!(format_number(1.5e-10, ".3g"), "1.50e-10");
+        assert_eq!(format_number(6.02e23, ".3g"), "6.02e+23");
+        assert_eq!(format_number(6.02e23, ""), "6.02e+23");
+    }
+
+    #[test]
+    fn test_invalid_format_falls_back() {
+        assert_eq!(format_number(1.5, "invalid"), "1.5");
+        assert_eq!(format_number(1.5, ".2q"), "1.5");
+    }
+}
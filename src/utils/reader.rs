@@ -0,0 +1,2251 @@
+use crate::spec::{DatasetSpecs, InlineData, InlineValue, Sheet};
+#[cfg(feature = "arrow")]
+use crate::utils::arrow::read_arrow;
+use crate::utils::column_type::ColumnType;
+use crate::utils::computed::{BoundComputedColumns, ComputedColumn};
+use crate::utils::filter::RowFilter;
+use anyhow::{anyhow, bail, Context, Result};
+use bzip2::read::MultiBzDecoder;
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::{DateTime, Timelike};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use flate2::read::MultiGzDecoder;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use parquet::basic::{ConvertedType, LogicalType, Type as PhysicalType};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tempfile::TempPath;
+use thiserror::Error;
+use xz2::read::XzDecoder;
+
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC_BYTES: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const BZIP2_MAGIC_BYTES: [u8; 3] = *b"BZh";
+const XZ_MAGIC_BYTES: [u8; 6] = [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+/// Extensions of compression formats that cannot be read.
+const UNSUPPORTED_COMPRESSIONS: [&str; 6] = ["lz4", "lzma", "lz", "br", "zip", "7z"];
+const UTF8_BOM: [u8; 3] = [0xef, 0xbb, 0xbf];
+const UTF16LE_BOM: [u8; 2] = [0xff, 0xfe];
+const UTF16BE_BOM: [u8; 2] = [0xfe, 0xff];
+const SEPARATOR_CANDIDATES: [char; 4] = [',', '\t', ';', '|'];
+const SEPARATOR_SNIFF_BYTES: u64 = 8192;
+
+/// Path of datasets that are read from stdin.
+pub(crate) const STDIN_PATH: &str = "-";
+/// Name of the column holding the originating file of records of concatenated tables.
+pub(crate) const SOURCE_COLUMN: &str = "__source__";
+
+lazy_static! {
+    /// Temporary files holding buffered or downloaded datasets, removed at the end of the run.
+    static ref TEMPORARY_FILES: Mutex<Vec<TempPath>> = Mutex::new(Vec::new());
+    /// Path of the temporary file holding the data read from stdin.
+    static ref STDIN_BUFFER: Mutex<Option<PathBuf>> = Mutex::new(None);
+}
+
+/// Reader for the records of a dataset regardless of the underlying file format.
+pub(crate) enum TableReader {
+    File(readervzrd::FileReader),
+    Csv {
+        reader: csv::Reader<Box<dyn Read>>,
+        path: PathBuf,
+        headers: Option<Vec<String>>,
+        flexible: bool,
+    },
+    Records {
+        headers: Vec<String>,
+        records: Vec<Vec<String>>,
+        column_types: HashMap<String, ColumnType>,
+    },
+    /// Reader restricted to the columns at the given indices.
+    Selected {
+        reader: Box<TableReader>,
+        indices: Vec<usize>,
+    },
+    /// Reader restricted to the records matching the given filter, except for the given number
+    /// of leading records.
+    Filtered {
+        reader: Box<TableReader>,
+        filter: RowFilter,
+        keep: usize,
+    },
+    /// Reader restricted to a uniform random sample of the given size, drawn in a single pass
+    /// with reservoir sampling, except for the given number of leading records, which are
+    /// always kept. The sampled records retain their original order.
+    Sampled {
+        reader: Box<TableReader>,
+        size: usize,
+        seed: u64,
+        keep: usize,
+    },
+    /// Reader renaming the columns to the given headers, e.g. to number duplicate names.
+    Renamed {
+        reader: Box<TableReader>,
+        headers: Vec<String>,
+    },
+    /// Reader appending computed columns to all records, except for the given number of
+    /// leading records, which get empty values.
+    Computed {
+        reader: Box<TableReader>,
+        names: Vec<String>,
+        columns: BoundComputedColumns,
+        keep: usize,
+    },
+}
+
+impl TableReader {
+    /// Returns the headers of the table, normalized with [`normalize_header`], by which its
+    /// columns are referred to everywhere else.
+    pub(crate) fn headers(&mut self) -> Result<Vec<String>> {
+        Ok(self
+            .original_headers()?
+            .iter()
+            .map(|header| normalize_header(header))
+            .collect())
+    }
+
+    /// Returns the headers of the table as they are written in the file.
+    pub(crate) fn original_headers(&mut self) -> Result<Vec<String>> {
+        match self {
+            TableReader::File(reader) => Ok(reader.headers()?),
+            TableReader::Csv {
+                headers: Some(headers),
+                ..
+            } => Ok(headers.clone()),
+            TableReader::Csv { reader, path, .. } => Ok(reader
+                .headers()
+                .context(format!("Failed to read headers of table {path:?}."))?
+                .iter()
+                .map(|s| s.to_owned())
+                .collect_vec()),
+            TableReader::Records { headers, .. } => Ok(headers.clone()),
+            TableReader::Selected { reader, indices } => {
+                let headers = reader.original_headers()?;
+                Ok(indices
+                    .iter()
+                    .map(|&index| headers[index].clone())
+                    .collect())
+            }
+            TableReader::Filtered { reader, .. } | TableReader::Sampled { reader, .. } => {
+                reader.original_headers()
+            }
+            TableReader::Renamed { headers, .. } => Ok(headers.clone()),
+            TableReader::Computed { reader, names, .. } => {
+                let mut headers = reader.original_headers()?;
+                headers.extend(names.iter().cloned());
+                Ok(headers)
+            }
+        }
+    }
+
+    /// Returns the records of the table, which are read lazily. Records that cannot be read
+    /// yield an error naming the file.
+    pub(crate) fn records(&mut self) -> Result<Box<dyn Iterator<Item = Result<Vec<String>>> + '_>> {
+        match self {
+            TableReader::File(reader) => Ok(Box::new(reader.records()?.map(Ok))),
+            TableReader::Csv {
+                reader,
+                path,
+                headers,
+                flexible,
+            } => {
+                // Missing trailing fields of flexible tables are read as empty strings.
+                let width = match (headers, *flexible) {
+                    (_, false) => 0,
+                    (Some(headers), true) => headers.len(),
+                    (None, true) => reader
+                        .headers()
+                        .context(format!("Failed to read headers of table {path:?}."))?
+                        .len(),
+                };
+                let path = &*path;
+                Ok(Box::new(reader.records().map(move |record| {
+                    let record =
+                        record.context(format!("Failed to read records of table {path:?}."))?;
+                    let mut record = record.iter().map(|s| s.to_owned()).collect_vec();
+                    if record.len() < width {
+                        record.resize(width, String::new());
+                    }
+                    Ok(record)
+                })))
+            }
+            TableReader::Records { records, .. } => Ok(Box::new(records.iter().cloned().map(Ok))),
+            TableReader::Renamed { reader, .. } => reader.records(),
+            TableReader::Selected { reader, indices } => {
+                let indices = &*indices;
+                Ok(Box::new(reader.records()?.map_ok(move |record| {
+                    indices
+                        .iter()
+                        .map(|&index| record.get(index).cloned().unwrap_or_default())
+                        .collect()
+                })))
+            }
+            TableReader::Filtered {
+                reader,
+                filter,
+                keep,
+            } => {
+                let (filter, keep) = (&*filter, *keep);
+                Ok(Box::new(
+                    reader
+                        .records()?
+                        .enumerate()
+                        .filter(move |(index, record)| {
+                            *index < keep
+                                || record
+                                    .as_ref()
+                                    .map_or(true, |record| filter.matches(record))
+                        })
+                        .map(|(_, record)| record),
+                ))
+            }
+            TableReader::Sampled {
+                reader,
+                size,
+                seed,
+                keep,
+            } => {
+                let (size, keep) = (*size, *keep);
+                let mut rng = fastrand::Rng::with_seed(*seed);
+                let mut leading = Vec::new();
+                let mut reservoir = Vec::with_capacity(size);
+                for (index, record) in reader.records()?.enumerate() {
+                    let record = record?;
+                    if index < keep {
+                        leading.push(record);
+                        continue;
+                    }
+                    let index = index - keep;
+                    if index < size {
+                        reservoir.push((index, record));
+                    } else {
+                        let replaced = rng.usize(..=index);
+                        if replaced < size {
+                            reservoir[replaced] = (index, record);
+                        }
+                    }
+                }
+                reservoir.sort_unstable_by_key(|(index, _)| *index);
+                Ok(Box::new(
+                    leading
+                        .into_iter()
+                        .chain(reservoir.into_iter().map(|(_, record)| record))
+                        .map(Ok),
+                ))
+            }
+            TableReader::Computed {
+                reader,
+                columns,
+                keep,
+                ..
+            } => {
+                let (columns, keep) = (&*columns, *keep);
+                Ok(Box::new(reader.records()?.enumerate().map(
+                    move |(index, record)| {
+                        let mut record = record?;
+                        if index < keep {
+                            record.extend(vec![String::new(); columns.len()]);
+                        } else {
+                            columns.append(&mut record);
+                        }
+                        Ok(record)
+                    },
+                )))
+            }
+        }
+    }
+
+    /// Restricts the reader to a uniform random sample of the given size, always keeping the
+    /// given number of leading records.
+    pub(crate) fn sample(self, size: usize, seed: u64, keep: usize) -> TableReader {
+        TableReader::Sampled {
+            reader: Box::new(self),
+            size,
+            seed,
+            keep,
+        }
+    }
+
+    /// Appends the given computed columns to all records except for the given number of
+    /// leading records.
+    pub(crate) fn compute(
+        mut self,
+        columns: &[ComputedColumn],
+        keep: usize,
+    ) -> Result<TableReader> {
+        let bound = BoundComputedColumns::bind(columns, &self.headers()?)?;
+        Ok(TableReader::Computed {
+            reader: Box::new(self),
+            names: columns.iter().map(|column| column.name.clone()).collect(),
+            columns: bound,
+            keep,
+        })
+    }
+
+    /// Restricts the reader to the records matching the given filter, always keeping the given
+    /// number of leading records.
+    pub(crate) fn filter(self, filter: RowFilter, keep: usize) -> TableReader {
+        TableReader::Filtered {
+            reader: Box::new(self),
+            filter,
+            keep,
+        }
+    }
+
+    /// Renames the columns to the given headers, which have to be as many as the columns.
+    pub(crate) fn rename(self, headers: Vec<String>) -> TableReader {
+        TableReader::Renamed {
+            reader: Box::new(self),
+            headers,
+        }
+    }
+
+    /// Restricts the reader to the given columns, skipping columns that do not exist.
+    pub(crate) fn select(mut self, columns: &[String]) -> Result<TableReader> {
+        let headers = self.headers()?;
+        let indices = columns
+            .iter()
+            .filter_map(|column| headers.iter().position(|header| header == column))
+            .collect();
+        Ok(TableReader::Selected {
+            reader: Box::new(self),
+            indices,
+        })
+    }
+
+    /// Returns the column types reported by the underlying file format, if any.
+    pub(crate) fn column_types(&self) -> HashMap<String, ColumnType> {
+        match self {
+            TableReader::Records { column_types, .. } => column_types
+                .iter()
+                .map(|(column, column_type)| (normalize_header(column), *column_type))
+                .collect(),
+            // Types of renamed columns are inferred since they are ambiguous.
+            TableReader::Renamed { reader, headers } => reader
+                .column_types()
+                .into_iter()
+                .filter(|(column, _)| headers.contains(column))
+                .collect(),
+            TableReader::Selected { reader, .. }
+            | TableReader::Filtered { reader, .. }
+            | TableReader::Sampled { reader, .. }
+            | TableReader::Computed { reader, .. } => reader.column_types(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// Returns the given header with surrounding whitespace removed and line breaks, along with
+/// the whitespace around them, collapsed into single spaces, e.g. `depth` for ` depth `.
+pub(crate) fn normalize_header(header: &str) -> String {
+    header
+        .split(['\n', '\r'])
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .join(" ")
+}
+
+/// Returns the names occurring multiple times in the given headers along with the indices of
+/// their columns.
+pub(crate) fn duplicate_headers(headers: &[String]) -> Vec<(String, Vec<usize>)> {
+    headers
+        .iter()
+        .duplicates()
+        .map(|duplicate| {
+            let indices = headers
+                .iter()
+                .positions(|header| header == duplicate)
+                .collect();
+            (duplicate.to_owned(), indices)
+        })
+        .collect()
+}
+
+/// Returns the given headers with repeated names numbered from their second occurrence on,
+/// e.g. `score` and `score_2`, skipping numbers that are taken by other columns.
+pub(crate) fn dedup_headers(headers: &[String]) -> Vec<String> {
+    let mut taken: HashSet<String> = headers.iter().cloned().collect();
+    let mut seen = HashSet::new();
+    headers
+        .iter()
+        .map(|header| {
+            if seen.insert(header.to_owned()) {
+                return header.to_owned();
+            }
+            let renamed = (2..)
+                .map(|number| format!("{header}_{number}"))
+                .find(|renamed| !taken.contains(renamed))
+                .unwrap();
+            taken.insert(renamed.clone());
+            renamed
+        })
+        .collect()
+}
+
+/// Returns the headers of the given dataset that are changed by normalizing them, along with
+/// their normalized names.
+pub(crate) fn normalized_headers(dataset: &DatasetSpecs) -> Result<Vec<(String, String)>> {
+    Ok(dataset
+        .reader()?
+        .original_headers()?
+        .into_iter()
+        .filter_map(|header| {
+            let normalized = normalize_header(&header);
+            (normalized != header).then_some((header, normalized))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of table readers opened by the current thread, to test how often tables are read.
+    pub(crate) static OPENED_READERS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Opens a reader for the given dataset, transparently decompressing compressed files
+/// and reading the first worksheet of spreadsheet files.
+pub(crate) fn open_table_reader(dataset: &DatasetSpecs) -> Result<TableReader> {
+    #[cfg(test)]
+    OPENED_READERS.with(|opened| opened.set(opened.get() + 1));
+    if is_spreadsheet(&dataset.path) {
+        read_spreadsheet(dataset)
+    } else if is_parquet(&dataset.path) {
+        read_parquet(&dataset.path)
+    } else if is_arrow(&dataset.path) {
+        read_arrow(&dataset.path)
+    } else if is_sqlite(&dataset.path) {
+        read_sqlite(dataset)
+    } else if is_json(&dataset.path) {
+        read_json(&dataset.path)
+    } else if Compression::detect(&dataset.path)?.is_some()
+        || has_bom(&dataset.path)
+        || has_crlf_line_endings(&dataset.path)
+        || dataset.column_names.is_some()
+        || skips_lines(dataset)
+        || customizes_quoting(dataset)
+        || dataset.flexible
+        || dataset.encoding.is_some()
+    {
+        let mut reader = csv_reader(dataset)?;
+        // Read the headers right away so that corrupt streams are reported early.
+        let headers = reader
+            .headers()
+            .context(format!("Failed to read table {:?}.", &dataset.path))?;
+        if headers.is_empty() && skips_lines(dataset) {
+            bail!(TableReaderError::EmptyAfterSkipping {
+                path: dataset.path.clone()
+            })
+        }
+        Ok(TableReader::Csv {
+            reader,
+            path: dataset.path.clone(),
+            headers: dataset.column_names.clone(),
+            flexible: dataset.flexible,
+        })
+    } else {
+        let path = dataset
+            .path
+            .to_str()
+            .ok_or(anyhow!("Failed to create dataset reader."))?;
+        Ok(TableReader::File(readervzrd::FileReader::new(
+            path,
+            Some(dataset.separator()?),
+        )?))
+    }
+}
+
+/// Creates a csv reader for the given delimited dataset honoring all of its reading options.
+fn csv_reader(dataset: &DatasetSpecs) -> Result<csv::Reader<Box<dyn Read>>> {
+    Ok(csv::ReaderBuilder::new()
+        .delimiter(dataset.separator()? as u8)
+        .has_headers(dataset.column_names.is_none())
+        .comment(dataset.comment_char.map(|comment_char| comment_char as u8))
+        .quote(quote_char(dataset) as u8)
+        .escape(dataset.escape.as_ref().map(|escape| escape.as_bytes()[0]))
+        .double_quote(dataset.double_quote)
+        .flexible(dataset.flexible)
+        .from_reader(open_data(dataset)?))
+}
+
+/// Checks that all records of the given delimited dataset have as many fields as its header.
+/// Returns the number of records with missing trailing fields, which are only tolerated if
+/// the dataset is flexible.
+pub(crate) fn check_record_lengths(dataset: &DatasetSpecs) -> Result<usize> {
+    let mut reader = csv_reader(dataset)?;
+    let expected = match &dataset.column_names {
+        Some(column_names) => column_names.len(),
+        None => reader
+            .headers()
+            .map_err(|error| describe_csv_error(error, dataset))?
+            .len(),
+    };
+    let mut short_records = 0;
+    for record in reader.records() {
+        let record = record.map_err(|error| describe_csv_error(error, dataset))?;
+        if record.len() < expected {
+            short_records += 1;
+        }
+    }
+    Ok(short_records)
+}
+
+/// Converts errors of the csv reader into descriptive errors pointing to the offending line.
+fn describe_csv_error(error: csv::Error, dataset: &DatasetSpecs) -> anyhow::Error {
+    let line = |pos: &Option<csv::Position>| {
+        pos.as_ref().map_or(0, |pos| pos.line()) as usize + dataset.skip_rows
+    };
+    match error.kind() {
+        csv::ErrorKind::UnequalLengths {
+            pos,
+            expected_len,
+            len,
+        } => TableReaderError::UnequalRecordLength {
+            path: dataset.path.clone(),
+            line: line(pos),
+            expected: *expected_len as usize,
+            found: *len as usize,
+        }
+        .into(),
+        csv::ErrorKind::Utf8 { pos, err } => TableReaderError::InvalidUtf8 {
+            path: dataset.path.clone(),
+            line: line(pos),
+            byte: pos.as_ref().map_or(0, |pos| pos.byte()) as usize + err.valid_up_to(),
+        }
+        .into(),
+        _ => {
+            anyhow::Error::new(error).context(format!("Failed to read table {:?}.", &dataset.path))
+        }
+    }
+}
+
+/// Buffers stdin into a temporary file once, so that it can be read multiple times
+/// without keeping large inputs in memory.
+pub(crate) fn buffer_stdin() -> Result<PathBuf> {
+    let mut buffer = STDIN_BUFFER.lock().unwrap();
+    if buffer.is_none() {
+        let file = spill_to_temp_file(&mut std::io::stdin().lock())?;
+        *buffer = Some(keep_until_exit(file));
+    }
+    Ok(buffer.as_ref().unwrap().to_path_buf())
+}
+
+/// Returns the sorted paths matched by the given path if it is a glob pattern instead of an
+/// existing file.
+pub(crate) fn glob_paths(path: &Path) -> Result<Option<Vec<PathBuf>>> {
+    let pattern = path.to_string_lossy();
+    if path.exists() || !pattern.contains(['*', '?', '[']) {
+        return Ok(None);
+    }
+    let paths: Vec<PathBuf> = glob::glob(&pattern)
+        .context(format!("Invalid glob pattern {pattern:?}."))?
+        .collect::<Result<_, _>>()
+        .context(format!("Failed to expand glob pattern {pattern:?}."))?;
+    if paths.is_empty() {
+        bail!(TableReaderError::NoGlobMatches {
+            pattern: pattern.to_string()
+        })
+    }
+    Ok(Some(paths.into_iter().sorted().collect()))
+}
+
+/// Concatenates the records of the given datasets, which must share the same header, into a
+/// temporary csv file. Additional header rows are only taken from the first dataset.
+pub(crate) fn concatenate_tables(
+    datasets: &[DatasetSpecs],
+    source_column: bool,
+) -> Result<PathBuf> {
+    let file = tempfile::Builder::new()
+        .prefix("datavzrd-concatenated-")
+        .suffix(".csv")
+        .tempfile()
+        .context("Failed to create temporary file for concatenating tables.")?;
+    let mut writer = csv::Writer::from_writer(file.as_file());
+    let mut first: Option<(&Path, Vec<String>)> = None;
+    for dataset in datasets {
+        let mut reader = dataset.reader()?;
+        let headers = reader.headers()?;
+        let skipped_header_rows = match &first {
+            None => {
+                let mut header = headers.clone();
+                if source_column {
+                    header.push(SOURCE_COLUMN.to_string());
+                }
+                writer.write_record(&header)?;
+                first = Some((&dataset.path, headers));
+                0
+            }
+            Some((first_path, first_headers)) if first_headers != &headers => {
+                bail!(TableReaderError::HeaderMismatch {
+                    path: dataset.path.clone(),
+                    first_path: first_path.to_path_buf(),
+                    diff: header_diff(first_headers, &headers),
+                })
+            }
+            Some(_) => dataset.header_rows - 1,
+        };
+        let source = dataset.path.display().to_string();
+        for record in reader.records()?.skip(skipped_header_rows) {
+            let mut record = record?;
+            if source_column {
+                record.push(source.clone());
+            }
+            writer.write_record(&record)?;
+        }
+    }
+    writer
+        .flush()
+        .context("Failed to write concatenated tables.")?;
+    drop(writer);
+    Ok(keep_until_exit(file.into_temp_path()))
+}
+
+/// Writes the records of a table given in the config to a temporary csv file. Rows are written
+/// as they are, so that lines of differing lengths are reported like for any delimited file.
+pub(crate) fn write_inline_table(data: &InlineData) -> Result<PathBuf> {
+    let file = tempfile::Builder::new()
+        .prefix("datavzrd-inline-")
+        .suffix(".csv")
+        .tempfile()
+        .context("Failed to create temporary file for inline data.")?;
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(file.as_file());
+    match data {
+        InlineData::Records(records) => {
+            let header = records
+                .iter()
+                .flat_map(|record| record.keys())
+                .unique()
+                .collect_vec();
+            writer.write_record(&header)?;
+            for record in records {
+                writer.write_record(header.iter().map(|column| {
+                    record
+                        .get(*column)
+                        .map(InlineValue::to_field)
+                        .unwrap_or_default()
+                }))?;
+            }
+        }
+        InlineData::Rows(rows) => {
+            for row in rows {
+                writer.write_record(row.iter().map(InlineValue::to_field))?;
+            }
+        }
+    }
+    writer.flush().context("Failed to write inline data.")?;
+    drop(writer);
+    Ok(keep_until_exit(file.into_temp_path()))
+}
+
+/// Describes the differences between the given headers.
+fn header_diff(expected: &[String], found: &[String]) -> String {
+    let missing = expected.iter().filter(|c| !found.contains(c)).collect_vec();
+    let unexpected = found.iter().filter(|c| !expected.contains(c)).collect_vec();
+    if missing.is_empty() && unexpected.is_empty() {
+        format!("expected column order {expected:?} but found {found:?}")
+    } else {
+        format!("missing columns {missing:?}, unexpected columns {unexpected:?}")
+    }
+}
+
+/// Keeps the given temporary file until [`remove_temporary_files`] is called.
+pub(crate) fn keep_until_exit(file: TempPath) -> PathBuf {
+    let path = file.to_path_buf();
+    TEMPORARY_FILES.lock().unwrap().push(file);
+    path
+}
+
+/// Removes all temporary files holding buffered or downloaded datasets.
+pub(crate) fn remove_temporary_files() -> Result<()> {
+    for file in TEMPORARY_FILES.lock().unwrap().drain(..) {
+        file.close()?;
+    }
+    Ok(())
+}
+
+/// Copies the given input into a temporary file. The file gets a `.csv` extension, since the
+/// table format is derived from it and stdin is read as a delimited file.
+fn spill_to_temp_file(input: &mut impl Read) -> Result<TempPath> {
+    let mut file = tempfile::Builder::new()
+        .prefix("datavzrd-stdin-")
+        .suffix(".csv")
+        .tempfile()
+        .context("Failed to create temporary file for buffering stdin.")?;
+    std::io::copy(input, &mut file).context("Failed to read table from stdin.")?;
+    Ok(file.into_temp_path())
+}
+
+/// Opens the given file, transparently decompressing it if it is compressed
+/// and stripping a leading byte order mark.
+fn open_decompressed(path: &Path) -> Result<Box<dyn Read>> {
+    let file = File::open(path).context(format!("Could not open table {path:?}."))?;
+    let reader = match Compression::detect(path)? {
+        Some(compression) => compression.decoder(file, path)?,
+        None => Box::new(file),
+    };
+    Ok(Box::new(BomStrippingReader::new(reader)))
+}
+
+/// Decompresses the given file once into a temporary file that is kept until exit, so that
+/// the table can be read multiple times without decompressing it again. Returns `None` if the
+/// file is not compressed.
+pub(crate) fn decompress_to_temp_file(path: &Path) -> Result<Option<PathBuf>> {
+    let Some(compression) = Compression::detect(path)? else {
+        return Ok(None);
+    };
+    // Keep the inner extension (e.g. .csv or .jsonl) so that the format is still recognized.
+    let file_name = if path
+        .extension()
+        .is_some_and(|extension| extension == compression.extension())
+    {
+        path.file_stem()
+    } else {
+        path.file_name()
+    }
+    .unwrap_or_default();
+    let mut decoder = compression.decoder(
+        File::open(path).context(format!("Could not open table {path:?}."))?,
+        path,
+    )?;
+    let mut file = tempfile::Builder::new()
+        .prefix("datavzrd-decompressed-")
+        .suffix(&format!("-{}", file_name.to_string_lossy()))
+        .tempfile()
+        .context("Failed to create temporary file for decompressing table.")?;
+    std::io::copy(&mut decoder, &mut file)
+        .context(format!("Failed to decompress table {path:?}."))?;
+    Ok(Some(keep_until_exit(file.into_temp_path())))
+}
+
+/// Converts tables that are not delimited files, i.e. spreadsheets, parquet, arrow, JSON and
+/// SQLite tables, once into a temporary csv file that is kept until exit, so that they are not
+/// parsed again every time the table is read. Returns the path along with the column types
+/// reported by the original format, or `None` for delimited files.
+pub(crate) fn convert_to_temp_file(
+    dataset: &DatasetSpecs,
+) -> Result<Option<(PathBuf, HashMap<String, ColumnType>)>> {
+    if is_delimited(&dataset.path) {
+        return Ok(None);
+    }
+    let path = &dataset.path;
+    let mut reader = open_table_reader(dataset)?;
+    let converted = write_temp_csv(
+        "datavzrd-converted-",
+        &reader.original_headers()?,
+        reader.records()?,
+    )
+    .context(format!("Failed to convert table {path:?}."))?;
+    Ok(Some((converted, reader.column_types())))
+}
+
+/// Writes the given headers and records into a temporary csv file with the given prefix that
+/// is kept until exit.
+pub(crate) fn write_temp_csv(
+    prefix: &str,
+    headers: &[String],
+    records: impl IntoIterator<Item = Result<Vec<String>>>,
+) -> Result<PathBuf> {
+    let file = tempfile::Builder::new()
+        .prefix(prefix)
+        .suffix(".csv")
+        .tempfile()
+        .context("Failed to create temporary csv file.")?;
+    let mut writer = csv::Writer::from_writer(file.as_file());
+    writer.write_record(headers)?;
+    for record in records {
+        writer.write_record(record?)?;
+    }
+    writer.flush()?;
+    drop(writer);
+    Ok(keep_until_exit(file.into_temp_path()))
+}
+
+/// Returns true if leading rows or comment lines of the given dataset have to be skipped.
+fn skips_lines(dataset: &DatasetSpecs) -> bool {
+    dataset.skip_rows > 0 || dataset.comment_char.is_some()
+}
+
+/// Returns true if the given dataset deviates from the default quoting of delimited files.
+fn customizes_quoting(dataset: &DatasetSpecs) -> bool {
+    dataset.quote.is_some() || dataset.escape.is_some() || !dataset.double_quote
+}
+
+/// Returns the quote character of the given delimited dataset.
+fn quote_char(dataset: &DatasetSpecs) -> char {
+    dataset
+        .quote
+        .as_ref()
+        .and_then(|quote| quote.chars().next())
+        .unwrap_or('"')
+}
+
+/// Opens the given delimited dataset, transcoding it to UTF-8 if an encoding is given.
+fn open_decoded(dataset: &DatasetSpecs) -> Result<Box<dyn Read>> {
+    let reader = open_decompressed(&dataset.path)?;
+    match dataset.encoding() {
+        Some(encoding) => Ok(Box::new(
+            DecodeReaderBytesBuilder::new()
+                .encoding(Some(encoding))
+                .build(reader),
+        )),
+        None => Ok(reader),
+    }
+}
+
+/// Opens the given delimited dataset without the leading rows to skip.
+fn open_data(dataset: &DatasetSpecs) -> Result<Box<dyn Read>> {
+    let mut reader = BufReader::new(open_decoded(dataset)?);
+    let mut line = Vec::new();
+    for _ in 0..dataset.skip_rows {
+        line.clear();
+        if reader
+            .read_until(b'\n', &mut line)
+            .context(format!("Failed to read table {:?}.", &dataset.path))?
+            == 0
+        {
+            break;
+        }
+    }
+    Ok(Box::new(reader))
+}
+
+/// Returns the leading rows and comment lines that are skipped when reading the given dataset.
+pub(crate) fn skipped_lines(dataset: &DatasetSpecs) -> Result<Vec<String>> {
+    let mut skipped = Vec::new();
+    for (index, line) in BufReader::new(open_decoded(dataset)?).lines().enumerate() {
+        let line = line.context(format!("Failed to read table {:?}.", &dataset.path))?;
+        if index < dataset.skip_rows
+            || dataset
+                .comment_char
+                .is_some_and(|comment_char| line.starts_with(comment_char))
+        {
+            skipped.push(line);
+        }
+    }
+    Ok(skipped)
+}
+
+/// Checks whether the given file starts with a UTF-8 byte order mark.
+fn has_bom(path: &Path) -> bool {
+    let mut prefix = [0_u8; 3];
+    File::open(path)
+        .is_ok_and(|mut file| file.read_exact(&mut prefix).is_ok() && prefix == UTF8_BOM)
+}
+
+/// Checks whether the first line of the given file is terminated by a Windows line ending.
+/// Such files are read with the csv crate, which strips the carriage return from the last field.
+fn has_crlf_line_endings(path: &Path) -> bool {
+    let mut line = Vec::new();
+    File::open(path).is_ok_and(|file| {
+        BufReader::new(file)
+            .take(SEPARATOR_SNIFF_BYTES)
+            .read_until(b'\n', &mut line)
+            .is_ok()
+            && line.ends_with(b"\r\n")
+    })
+}
+
+/// Returns the encoding of the given file if it starts with a UTF-16 byte order mark.
+pub(crate) fn utf16_encoding(path: &Path) -> Option<&'static str> {
+    let mut prefix = [0_u8; 2];
+    File::open(path).ok()?.read_exact(&mut prefix).ok()?;
+    match prefix {
+        UTF16LE_BOM => Some("utf-16le"),
+        UTF16BE_BOM => Some("utf-16be"),
+        _ => None,
+    }
+}
+
+/// Reader that strips a leading UTF-8 byte order mark as written by e.g. Microsoft Excel.
+pub(crate) struct BomStrippingReader<R> {
+    inner: R,
+    prefix: Option<Vec<u8>>,
+}
+
+impl<R: Read> BomStrippingReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        BomStrippingReader {
+            inner,
+            prefix: None,
+        }
+    }
+}
+
+impl<R: Read> Read for BomStrippingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.prefix.is_none() {
+            // Peek at the first bytes and only hand them out again if they are not a BOM.
+            let mut prefix = Vec::with_capacity(UTF8_BOM.len());
+            (&mut self.inner)
+                .take(UTF8_BOM.len() as u64)
+                .read_to_end(&mut prefix)?;
+            if prefix == UTF8_BOM {
+                prefix.clear();
+            }
+            self.prefix = Some(prefix);
+        }
+        let prefix = self.prefix.as_mut().unwrap();
+        if prefix.is_empty() {
+            self.inner.read(buf)
+        } else {
+            let length = prefix.len().min(buf.len());
+            buf[..length].copy_from_slice(&prefix[..length]);
+            prefix.drain(..length);
+            Ok(length)
+        }
+    }
+}
+
+/// Returns true if the given file contains delimiter-separated values.
+pub(crate) fn is_delimited(path: &Path) -> bool {
+    !is_spreadsheet(path)
+        && !is_json(path)
+        && !is_parquet(path)
+        && !is_arrow(path)
+        && !is_sqlite(path)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "json" || extension == "jsonl")
+}
+
+/// Reads a JSON array of objects or a JSON-lines file, using the union of all keys as headers.
+/// Nested objects of JSON arrays are flattened into columns named by their dotted key paths.
+fn read_json(path: &Path) -> Result<TableReader> {
+    if let Some(encoding) = utf16_encoding(path) {
+        bail!(TableReaderError::UnsupportedEncoding {
+            path: path.to_owned(),
+            encoding: encoding.to_string()
+        })
+    }
+    let reader = BufReader::new(open_decompressed(path)?);
+    let json_lines = path
+        .extension()
+        .is_some_and(|extension| extension == "jsonl");
+    let values = if json_lines {
+        reader
+            .lines()
+            .enumerate()
+            .map(|(index, line)| Ok((index + 1, line?)))
+            .filter_ok(|(_, line)| !line.trim().is_empty())
+            .map(|line: Result<(usize, String)>| {
+                let (number, line) = line?;
+                Ok((
+                    number,
+                    serde_json::from_str(&line)
+                        .context(format!("Failed to parse line {number} of table {path:?}."))?,
+                ))
+            })
+            .collect::<Result<Vec<(usize, Value)>>>()?
+    } else {
+        serde_json::from_reader::<_, Vec<Value>>(reader)
+            .context(format!("Failed to parse table {path:?}."))?
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (index + 1, value))
+            .collect()
+    };
+    let objects = values
+        .into_iter()
+        .map(|(record, value)| match value {
+            Value::Object(object) if json_lines => Ok(object),
+            Value::Object(object) => {
+                let mut flattened = Map::new();
+                flatten_json_object(object, "", &mut flattened);
+                Ok(flattened)
+            }
+            _ => Err(anyhow!(TableReaderError::InvalidJsonRecord {
+                path: path.to_owned(),
+                record
+            })),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let headers = objects
+        .iter()
+        .flat_map(|object| object.keys())
+        .unique()
+        .cloned()
+        .collect_vec();
+    let records = objects
+        .iter()
+        .map(|object| {
+            headers
+                .iter()
+                .map(|header| {
+                    object
+                        .get(header)
+                        .map(format_json_value)
+                        .unwrap_or_default()
+                })
+                .collect_vec()
+        })
+        .collect();
+    Ok(TableReader::Records {
+        headers,
+        records,
+        column_types: HashMap::new(),
+    })
+}
+
+/// Moves the leaves of the given object into `flattened`, naming nested values by their dotted
+/// key paths like `a.b`. Arrays are kept as leaves.
+fn flatten_json_object(
+    object: Map<String, Value>,
+    prefix: &str,
+    flattened: &mut Map<String, Value>,
+) {
+    for (key, value) in object {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Object(inner) => flatten_json_object(inner, &key, flattened),
+            value => {
+                flattened.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Converts a JSON value into a cell, serializing nested objects and arrays as JSON strings.
+fn format_json_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.to_owned(),
+        _ => value.to_string(),
+    }
+}
+
+/// Detects the separator of the given dataset by sniffing its first lines for the candidate
+/// that splits all of them into the same number of columns.
+pub(crate) fn detect_separator(dataset: &DatasetSpecs) -> Result<char> {
+    let path = &dataset.path;
+    let mut sample = Vec::new();
+    open_data(dataset)?
+        .take(SEPARATOR_SNIFF_BYTES)
+        .read_to_end(&mut sample)
+        .context(format!("Failed to read table {path:?}."))?;
+    let sample = String::from_utf8_lossy(&sample);
+    let mut lines = sample
+        .lines()
+        .filter(|line| {
+            !line.is_empty()
+                && !dataset
+                    .comment_char
+                    .is_some_and(|comment_char| line.starts_with(comment_char))
+        })
+        .collect_vec();
+    if sample.len() as u64 == SEPARATOR_SNIFF_BYTES && lines.len() > 1 {
+        // The last line is most likely truncated.
+        lines.pop();
+    }
+    let consistent = SEPARATOR_CANDIDATES
+        .iter()
+        .filter(|separator| {
+            let counts = lines
+                .iter()
+                .map(|line| count_unquoted(line, **separator, dataset))
+                .unique()
+                .collect_vec();
+            counts.len() == 1 && counts[0] > 0
+        })
+        .collect_vec();
+    match consistent.as_slice() {
+        [separator] => Ok(**separator),
+        // Files with a single column do not contain any separator.
+        [] if SEPARATOR_CANDIDATES
+            .iter()
+            .all(|separator| lines.iter().all(|line| !line.contains(*separator))) =>
+        {
+            Ok(',')
+        }
+        _ => bail!(TableReaderError::AmbiguousSeparator {
+            path: path.to_owned()
+        }),
+    }
+}
+
+/// Counts the occurrences of the given character outside of quoted fields, honoring the
+/// quote and escape characters of the given dataset.
+fn count_unquoted(line: &str, character: char, dataset: &DatasetSpecs) -> usize {
+    let quote = quote_char(dataset);
+    let escape = dataset
+        .escape
+        .as_ref()
+        .and_then(|escape| escape.chars().next());
+    let mut quoted = false;
+    let mut count = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if quoted && Some(c) == escape {
+            chars.next();
+        } else if c == quote {
+            quoted = !quoted;
+        } else if c == character && !quoted {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn is_spreadsheet(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            matches!(
+                extension.to_lowercase().as_str(),
+                "xlsx" | "xlsm" | "xls" | "ods"
+            )
+        })
+}
+
+/// Reads the given or else the first worksheet of the given spreadsheet, using its first row
+/// as headers unless column names are given.
+fn read_spreadsheet(dataset: &DatasetSpecs) -> Result<TableReader> {
+    let path = &dataset.path;
+    let mut workbook =
+        open_workbook_auto(path).context(format!("Could not open spreadsheet {path:?}."))?;
+    // Sheet names include hidden worksheets, so that these can be selected as well.
+    let sheet_names = workbook.sheet_names();
+    let sheet = match &dataset.sheet {
+        Some(Sheet::Name(sheet)) if !sheet_names.contains(sheet) => {
+            bail!(TableReaderError::MissingNamedWorksheet {
+                path: path.to_owned(),
+                sheet: sheet.to_owned(),
+                available: sheet_names
+            })
+        }
+        Some(Sheet::Name(sheet)) => sheet.to_owned(),
+        Some(Sheet::Index(index)) => match sheet_names.get(*index) {
+            Some(sheet) => sheet.to_owned(),
+            None => bail!(TableReaderError::WorksheetIndexOutOfRange {
+                path: path.to_owned(),
+                index: *index,
+                available: sheet_names
+            }),
+        },
+        None => match sheet_names.first() {
+            Some(sheet) => sheet.to_owned(),
+            None => bail!(TableReaderError::MissingWorksheet {
+                path: path.to_owned()
+            }),
+        },
+    };
+    let range = workbook.worksheet_range(&sheet).context(format!(
+        "Failed to read worksheet {sheet} of spreadsheet {path:?}."
+    ))?;
+    let mut rows = range
+        .rows()
+        .map(|row| row.iter().map(format_cell).collect_vec());
+    let headers = match (&dataset.column_names, rows.next()) {
+        (Some(column_names), Some(first_row)) => {
+            return Ok(TableReader::Records {
+                headers: column_names.clone(),
+                records: std::iter::once(first_row).chain(rows).collect(),
+                column_types: HashMap::new(),
+            })
+        }
+        (None, Some(headers)) => headers,
+        (_, None) => bail!(TableReaderError::EmptyWorksheet {
+            path: path.to_owned()
+        }),
+    };
+    Ok(TableReader::Records {
+        headers,
+        records: rows.collect(),
+        column_types: HashMap::new(),
+    })
+}
+
+/// Converts a spreadsheet cell into the string representation used for csv files.
+fn format_cell(cell: &Data) -> String {
+    match cell {
+        Data::DateTime(datetime) if datetime.is_datetime() => match datetime.as_datetime() {
+            Some(datetime) if datetime.num_seconds_from_midnight() == 0 => {
+                datetime.format("%Y-%m-%d").to_string()
+            }
+            Some(datetime) => datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+            None => datetime.to_string(),
+        },
+        _ => cell.to_string(),
+    }
+}
+
+fn is_parquet(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "parquet")
+}
+
+/// Opens the given parquet file, rejecting schemas with nested columns.
+fn open_parquet(path: &Path) -> Result<SerializedFileReader<File>> {
+    let file = File::open(path).context(format!("Could not open table {path:?}."))?;
+    let reader = SerializedFileReader::new(file)
+        .context(format!("Failed to read parquet file {path:?}."))?;
+    let schema = reader.metadata().file_metadata().schema_descr_ptr();
+    if let Some(field) = schema
+        .root_schema()
+        .get_fields()
+        .iter()
+        .find(|field| field.is_group())
+    {
+        bail!(TableReaderError::NestedParquetColumn {
+            path: path.to_owned(),
+            column: field.name().to_owned()
+        });
+    }
+    Ok(reader)
+}
+
+/// Reads all row groups of the given parquet file.
+fn read_parquet(path: &Path) -> Result<TableReader> {
+    let reader = open_parquet(path)?;
+    let headers = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| column.name().to_owned())
+        .collect_vec();
+    let mut records = Vec::new();
+    for index in 0..reader.num_row_groups() {
+        for row in reader.get_row_group(index)?.get_row_iter(None)? {
+            records.push(
+                row?.get_column_iter()
+                    .map(|(_, field)| format_field(field))
+                    .collect_vec(),
+            );
+        }
+    }
+    Ok(TableReader::Records {
+        headers,
+        records,
+        column_types: parquet_column_types(&reader),
+    })
+}
+
+/// Converts a parquet field into the string representation used for csv files.
+fn format_field(field: &Field) -> String {
+    let timestamp = match field {
+        Field::Null => return String::new(),
+        Field::Str(value) => return value.to_owned(),
+        Field::TimestampMillis(millis) => DateTime::from_timestamp_millis(*millis),
+        Field::TimestampMicros(micros) => DateTime::from_timestamp_micros(*micros),
+        _ => return field.to_string(),
+    };
+    timestamp.map_or_else(
+        || field.to_string(),
+        |timestamp| timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )
+}
+
+/// Derives the column types of the given parquet file from its schema.
+fn parquet_column_types(reader: &SerializedFileReader<File>) -> HashMap<String, ColumnType> {
+    reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| {
+            let column_type = match (
+                column.logical_type(),
+                column.converted_type(),
+                column.physical_type(),
+            ) {
+                (Some(LogicalType::Date | LogicalType::Timestamp { .. }), _, _)
+                | (_, ConvertedType::DATE, _)
+                | (_, ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS, _)
+                | (_, _, PhysicalType::INT96) => ColumnType::Date,
+                (Some(LogicalType::Decimal { .. }), _, _)
+                | (_, ConvertedType::DECIMAL, _)
+                | (_, _, PhysicalType::FLOAT | PhysicalType::DOUBLE) => ColumnType::Float,
+                (_, _, PhysicalType::INT32 | PhysicalType::INT64) => ColumnType::Integer,
+                (_, _, PhysicalType::BOOLEAN) => ColumnType::Boolean,
+                _ => ColumnType::String,
+            };
+            (column.name().to_owned(), column_type)
+        })
+        .collect()
+}
+
+fn is_arrow(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "arrow" || extension == "feather")
+}
+
+#[cfg(not(feature = "arrow"))]
+fn read_arrow(path: &Path) -> Result<TableReader> {
+    bail!(TableReaderError::ArrowSupportDisabled {
+        path: path.to_owned()
+    })
+}
+
+fn is_sqlite(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "db" | "sqlite" | "sqlite3"))
+}
+
+/// Reads the given table of an SQLite database or the result of the given query.
+fn read_sqlite(dataset: &DatasetSpecs) -> Result<TableReader> {
+    let path = &dataset.path;
+    let query = match (&dataset.table, &dataset.query) {
+        (Some(table), None) => format!("SELECT * FROM \"{}\"", table.replace('"', "\"\"")),
+        (None, Some(query)) => query.to_owned(),
+        _ => bail!(TableReaderError::InvalidSqliteSource {
+            path: path.to_owned()
+        }),
+    };
+    let context = || {
+        format!(
+            "Failed to execute query {query:?} on table {:?} of SQLite database {path:?}.",
+            dataset.table.as_deref().unwrap_or_default()
+        )
+    };
+    let connection =
+        rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .context(format!("Could not open SQLite database {path:?}."))?;
+    let mut statement = connection.prepare(&query).with_context(context)?;
+    let headers = statement
+        .column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect_vec();
+    let column_types = statement
+        .columns()
+        .iter()
+        .filter_map(|column| {
+            let declared = column.decl_type()?.to_uppercase();
+            // Follows the type affinity rules of SQLite.
+            let column_type = if declared.contains("INT") {
+                ColumnType::Integer
+            } else if ["REAL", "FLOA", "DOUB", "NUMERIC", "DECIMAL"]
+                .iter()
+                .any(|affinity| declared.contains(affinity))
+            {
+                ColumnType::Float
+            } else if declared.contains("DATE") || declared.contains("TIME") {
+                ColumnType::Date
+            } else if declared.contains("BOOL") {
+                ColumnType::Boolean
+            } else {
+                ColumnType::String
+            };
+            Some((column.name().to_owned(), column_type))
+        })
+        .collect();
+    let records = statement
+        .query_map([], |row| {
+            (0..headers.len())
+                .map(|index| Ok(format_sqlite_value(row.get_ref(index)?)))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .with_context(context)?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .with_context(context)?;
+    Ok(TableReader::Records {
+        headers,
+        records,
+        column_types,
+    })
+}
+
+/// Converts an SQLite value into the string representation used for csv files.
+fn format_sqlite_value(value: rusqlite::types::ValueRef) -> String {
+    match value {
+        rusqlite::types::ValueRef::Null => String::new(),
+        rusqlite::types::ValueRef::Integer(value) => value.to_string(),
+        rusqlite::types::ValueRef::Real(value) => value.to_string(),
+        rusqlite::types::ValueRef::Text(value) | rusqlite::types::ValueRef::Blob(value) => {
+            String::from_utf8_lossy(value).to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compression {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    const ALL: [Compression; 4] = [
+        Compression::Gzip,
+        Compression::Zstd,
+        Compression::Bzip2,
+        Compression::Xz,
+    ];
+
+    /// Detects the compression of the given file by its extension or magic bytes.
+    fn detect(path: &Path) -> Result<Option<Self>> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        if let Some(compression) = Compression::ALL
+            .into_iter()
+            .find(|compression| compression.extension() == extension)
+        {
+            return Ok(Some(compression));
+        }
+        if UNSUPPORTED_COMPRESSIONS.contains(&extension) {
+            bail!(TableReaderError::UnsupportedCompression {
+                path: path.to_owned(),
+                format: extension.to_string(),
+                supported: Compression::ALL
+                    .iter()
+                    .map(|compression| compression.extension())
+                    .join(", ")
+            })
+        }
+        let mut magic_bytes = [0_u8; 6];
+        // Missing or unreadable files are left to the actual reader to report.
+        let Ok(mut file) = File::open(path) else {
+            return Ok(None);
+        };
+        if file.read_exact(&mut magic_bytes).is_err() {
+            return Ok(None);
+        }
+        Ok(Compression::ALL
+            .into_iter()
+            .find(|compression| magic_bytes.starts_with(compression.magic_bytes())))
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+            Compression::Bzip2 => "bz2",
+            Compression::Xz => "xz",
+        }
+    }
+
+    fn magic_bytes(&self) -> &'static [u8] {
+        match self {
+            Compression::Gzip => &GZIP_MAGIC_BYTES,
+            Compression::Zstd => &ZSTD_MAGIC_BYTES,
+            Compression::Bzip2 => &BZIP2_MAGIC_BYTES,
+            Compression::Xz => &XZ_MAGIC_BYTES,
+        }
+    }
+
+    /// Wraps the given file with a decoder for this compression format.
+    fn decoder(&self, file: File, path: &Path) -> Result<Box<dyn Read>> {
+        Ok(match self {
+            Compression::Gzip => Box::new(MultiGzDecoder::new(file)),
+            Compression::Zstd => Box::new(
+                zstd::stream::read::Decoder::new(file)
+                    .context(format!("Failed to decompress table {path:?}."))?,
+            ),
+            Compression::Bzip2 => Box::new(MultiBzDecoder::new(file)),
+            Compression::Xz => Box::new(XzDecoder::new_multi_decoder(file)),
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TableReaderError {
+    #[error("Table {path:?} is compressed with unsupported format {format:?}. Supported compression formats are: {supported}.")]
+    UnsupportedCompression {
+        path: PathBuf,
+        format: String,
+        supported: String,
+    },
+    #[error("Spreadsheet {path:?} does not contain any worksheet.")]
+    MissingWorksheet { path: PathBuf },
+    #[error("Spreadsheet {path:?} does not contain a worksheet named {sheet:?}. Available worksheets are {available:?}.")]
+    MissingNamedWorksheet {
+        path: PathBuf,
+        sheet: String,
+        available: Vec<String>,
+    },
+    #[error("Spreadsheet {path:?} does not contain a worksheet with index {index}. Available worksheets are {available:?}.")]
+    WorksheetIndexOutOfRange {
+        path: PathBuf,
+        index: usize,
+        available: Vec<String>,
+    },
+    #[error("First worksheet of spreadsheet {path:?} is empty.")]
+    EmptyWorksheet { path: PathBuf },
+    #[error("Could not unambiguously detect the separator of table {path:?}. Please specify it via the separator property of the dataset.")]
+    AmbiguousSeparator { path: PathBuf },
+    #[error("Please specify either a table or a query for SQLite database {path:?}.")]
+    InvalidSqliteSource { path: PathBuf },
+    #[error("Record {record} of table {path:?} is not a JSON object.")]
+    InvalidJsonRecord { path: PathBuf, record: usize },
+    #[error(
+        "Column {column} of parquet file {path:?} is nested. Nested columns are not supported yet."
+    )]
+    NestedParquetColumn { path: PathBuf, column: String },
+    #[cfg(feature = "arrow")]
+    #[error("Column {column:?} of Arrow file {path:?} has the nested type {data_type}, which is not supported.")]
+    NestedArrowColumn {
+        path: PathBuf,
+        column: String,
+        data_type: String,
+    },
+    #[cfg(not(feature = "arrow"))]
+    #[error("Cannot read Arrow file {path:?} as datavzrd was built without the arrow feature.")]
+    ArrowSupportDisabled { path: PathBuf },
+    #[error(
+        "Table {path:?} does not contain any data after skipping leading rows and comment lines."
+    )]
+    EmptyAfterSkipping { path: PathBuf },
+    #[error("Glob pattern {pattern:?} does not match any table.")]
+    NoGlobMatches { pattern: String },
+    #[error("Header of table {path:?} differs from the header of table {first_path:?}: {diff}. All tables matched by a glob pattern must share the same header.")]
+    HeaderMismatch {
+        path: PathBuf,
+        first_path: PathBuf,
+        diff: String,
+    },
+    #[error("Table {path:?} contains invalid UTF-8 at byte {byte} (line {line}). Please specify the encoding of the table via the encoding property of the dataset, e.g. encoding: latin1.")]
+    InvalidUtf8 {
+        path: PathBuf,
+        line: usize,
+        byte: usize,
+    },
+    #[error("Table {path:?} is encoded in {encoding}, which is only supported for delimited files. Please convert it to UTF-8.")]
+    UnsupportedEncoding { path: PathBuf, encoding: String },
+    #[error("Line {line} of table {path:?} has {found} fields but {expected} were expected. Please fix the table or set the flexible property of the dataset to pad missing trailing fields.")]
+    UnequalRecordLength {
+        path: PathBuf,
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spec::{DatasetSpecs, Sheet};
+    use crate::utils::column_type::{classify_table, ColumnType};
+    use crate::utils::reader::{
+        check_record_lengths, convert_to_temp_file, decompress_to_temp_file, dedup_headers,
+        detect_separator, duplicate_headers, has_crlf_line_endings, is_delimited, normalize_header,
+        normalized_headers, skipped_lines, spill_to_temp_file, BomStrippingReader,
+    };
+    use crate::utils::remote::DownloadSettings;
+    use itertools::Itertools;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_gzip_compressed_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/uniform_datatypes.csv.gz"),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        assert_eq!(dataset.size().unwrap(), 4);
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("price").unwrap(), &ColumnType::Float);
+    }
+
+    #[test]
+    fn test_corrupt_gzip_compressed_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/corrupt.csv.gz"),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let error = dataset.reader().err().unwrap();
+        assert!(error.to_string().contains("corrupt.csv.gz"));
+    }
+
+    #[test]
+    fn test_bzip2_and_xz_compressed_datasets() {
+        for path in [
+            "tests/data/uniform_datatypes.csv.bz2",
+            "tests/data/uniform_datatypes.csv.xz",
+        ] {
+            let dataset = delimited_dataset(path);
+            assert_eq!(detect_separator(&dataset).unwrap(), ',');
+            assert_eq!(dataset.size().unwrap(), 4);
+            let classification = classify_table(&dataset).unwrap();
+            assert_eq!(classification.get("price").unwrap(), &ColumnType::Float);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_compression() {
+        let error = delimited_dataset("tests/data/uniform_datatypes.csv.lz4")
+            .reader()
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Table \"tests/data/uniform_datatypes.csv.lz4\" is compressed with unsupported format \"lz4\". Supported compression formats are: gz, zst, bz2, xz."
+        );
+    }
+
+    #[test]
+    fn test_decompress_to_temp_file() {
+        let path = decompress_to_temp_file(Path::new("tests/data/uniform_datatypes.csv.xz"))
+            .unwrap()
+            .unwrap();
+        assert!(path.to_string_lossy().ends_with("-uniform_datatypes.csv"));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            std::fs::read_to_string("tests/data/uniform_datatypes.csv").unwrap()
+        );
+        std::fs::remove_file(path).unwrap();
+        assert!(
+            decompress_to_temp_file(Path::new("tests/data/uniform_datatypes.csv"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_spreadsheet_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/spreadsheet.xlsx"),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["first", "last", "count", "price", "date"]
+        );
+        assert_eq!(
+            reader.records().unwrap().next().unwrap().unwrap(),
+            vec!["Delia", "Arnold", "3", "213.5", "2024-01-01"]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+        assert_eq!(classification.get("price").unwrap(), &ColumnType::Float);
+    }
+
+    #[test]
+    fn test_spreadsheet_dataset_with_sheet() {
+        let mut dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/workbook.xlsx"),
+            header_rows: 1,
+            sheet: Some(Sheet::Name(String::from("samples"))),
+            double_quote: true,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["name", "score", "passed", "sampled"]
+        );
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![
+                vec!["A1", "2.5", "true", "2024-01-01"],
+                vec!["B2", "3", "false", ""],
+                vec!["C3", "", "true", "2024-02-01"],
+            ]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("score").unwrap(), &ColumnType::Float);
+        assert_eq!(classification.get("sampled").unwrap(), &ColumnType::Date);
+
+        dataset.sheet = Some(Sheet::Index(1));
+        assert_eq!(
+            dataset.reader().unwrap().headers().unwrap(),
+            vec!["name", "score", "passed", "sampled"]
+        );
+
+        dataset.sheet = Some(Sheet::Name(String::from("missing")));
+        let error = dataset.reader().err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"Spreadsheet "tests/data/workbook.xlsx" does not contain a worksheet named "missing". Available worksheets are ["summary", "samples"]."#
+        );
+        dataset.sheet = Some(Sheet::Index(2));
+        let error = dataset.reader().err().unwrap();
+        assert!(error.to_string().contains("index 2"));
+    }
+
+    #[test]
+    fn test_hidden_worksheet() {
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("path: tests/data/hidden_sheet.xlsx\nsheet: raw").unwrap();
+        assert_eq!(dataset.sheet, Some(Sheet::Name(String::from("raw"))));
+        assert_eq!(dataset.reader().unwrap().headers().unwrap(), vec!["read"]);
+        dataset.sheet = serde_yaml::from_str("2").unwrap();
+        assert_eq!(dataset.sheet, Some(Sheet::Index(2)));
+        assert_eq!(
+            dataset
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect_vec(),
+            vec![vec!["ACGT"]]
+        );
+    }
+
+    #[test]
+    fn test_empty_spreadsheet_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/empty_sheet.xlsx"),
+            separator: Some(','),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let error = dataset.reader().err().unwrap();
+        assert!(error.to_string().contains("empty_sheet.xlsx"));
+    }
+
+    #[test]
+    fn test_parquet_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/uniform_datatypes.parquet"),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["first", "last", "count", "price", "joined"]
+        );
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![
+                vec!["Delia", "Arnold", "3", "213.5", "2024-01-01"],
+                vec!["George", "Winnie", "1", "", "2024-01-28"],
+                vec!["Winnie", "George", "7", "12.25", "2024-03-18"],
+            ]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("first").unwrap(), &ColumnType::String);
+        assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+        assert_eq!(classification.get("price").unwrap(), &ColumnType::Float);
+        assert_eq!(classification.get("joined").unwrap(), &ColumnType::Date);
+    }
+
+    #[test]
+    fn test_converted_parquet_dataset() {
+        let dataset = DatasetSpecs::from_path(
+            Path::new("tests/data/uniform_datatypes.parquet"),
+            None,
+            &DownloadSettings::default(),
+        )
+        .unwrap();
+        // The table is parsed once during preprocessing and read as csv afterwards.
+        assert!(is_delimited(&dataset.path));
+        assert_eq!(
+            dataset
+                .reader()
+                .unwrap()
+                .records()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect_vec(),
+            vec![
+                vec!["Delia", "Arnold", "3", "213.5", "2024-01-01"],
+                vec!["George", "Winnie", "1", "", "2024-01-28"],
+                vec!["Winnie", "George", "7", "12.25", "2024-03-18"],
+            ]
+        );
+        // The column types of the schema are kept.
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+        assert_eq!(classification.get("joined").unwrap(), &ColumnType::Date);
+        assert!(
+            convert_to_temp_file(&delimited_dataset("tests/data/uniform_datatypes.csv"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_nested_parquet_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/nested.parquet"),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let error = dataset.reader().err().unwrap();
+        assert!(error.to_string().contains("point"));
+    }
+
+    #[test]
+    fn test_json_lines_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/records.jsonl"),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["first", "last", "price", "tags", "active"]
+        );
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![
+                vec!["Delia", "Arnold", "213.5", "", ""],
+                vec!["George", "", "-350.9", r#"["a","b"]"#, ""],
+                vec!["Winnie", "Cunningham", "", "", "true"],
+            ]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("price").unwrap(), &ColumnType::Float);
+    }
+
+    #[test]
+    fn test_sqlite_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/results.db"),
+            header_rows: 1,
+            table: Some(String::from("scores")),
+            double_quote: true,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec!["name", "score", "rank", "measured"]
+        );
+        assert_eq!(
+            reader.records().unwrap().next().unwrap().unwrap(),
+            vec!["Delia", "3", "1", "2024-01-01"]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("name").unwrap(), &ColumnType::String);
+        assert_eq!(classification.get("score").unwrap(), &ColumnType::Float);
+        assert_eq!(classification.get("rank").unwrap(), &ColumnType::Integer);
+        assert_eq!(classification.get("measured").unwrap(), &ColumnType::Date);
+    }
+
+    #[test]
+    fn test_sqlite_query_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/results.db"),
+            header_rows: 1,
+            query: Some(String::from(
+                "SELECT name, score * 2 AS doubled FROM scores WHERE score IS NOT NULL",
+            )),
+            double_quote: true,
+            ..Default::default()
+        };
+        assert_eq!(dataset.size().unwrap(), 2);
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("doubled").unwrap(), &ColumnType::Integer);
+    }
+
+    #[test]
+    fn test_invalid_sqlite_query() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/results.db"),
+            header_rows: 1,
+            query: Some(String::from("SELECT missing FROM scores")),
+            double_quote: true,
+            ..Default::default()
+        };
+        let error = format!("{:?}", dataset.reader().err().unwrap());
+        assert!(error.contains("SELECT missing FROM scores"));
+    }
+
+    #[test]
+    fn test_buffered_stdin_dataset() {
+        let mut input = std::io::Cursor::new("first;count\nDelia;3\nGeorge;1\n");
+        let buffer = spill_to_temp_file(&mut input).unwrap();
+        let mut dataset = DatasetSpecs {
+            path: buffer.to_path_buf(),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        dataset.separator = Some(dataset.separator().unwrap());
+        assert_eq!(dataset.separator, Some(';'));
+        assert_eq!(dataset.size().unwrap(), 2);
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+    }
+
+    #[test]
+    fn test_bom_stripping_reader() {
+        let mut stripped = String::new();
+        BomStrippingReader::new("\u{feff}first,last".as_bytes())
+            .read_to_string(&mut stripped)
+            .unwrap();
+        assert_eq!(stripped, "first,last");
+        let mut unchanged = String::new();
+        BomStrippingReader::new("ab".as_bytes())
+            .read_to_string(&mut unchanged)
+            .unwrap();
+        assert_eq!(unchanged, "ab");
+    }
+
+    #[test]
+    fn test_dataset_with_bom() {
+        let dataset = |path: &str| DatasetSpecs {
+            path: PathBuf::from(path),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        };
+        let with_bom = dataset("tests/data/uniform_datatypes_bom.csv");
+        assert_eq!(with_bom.separator().unwrap(), ',');
+        assert_eq!(
+            with_bom.reader().unwrap().headers().unwrap(),
+            vec!["first", "last", "ccnumber", "price"]
+        );
+        assert_eq!(
+            classify_table(&with_bom).unwrap(),
+            classify_table(&dataset("tests/data/uniform_datatypes.csv")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_crlf_dataset() {
+        let dataset: DatasetSpecs = serde_yaml::from_str("path: tests/data/crlf.csv").unwrap();
+        assert!(has_crlf_line_endings(&dataset.path));
+        assert!(!has_crlf_line_endings(Path::new("tests/data/booleans.csv")));
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["sample", "depth", "status"]);
+        assert_eq!(
+            reader
+                .records()
+                .unwrap()
+                .map(|record| record.unwrap()[2].clone())
+                .collect_vec(),
+            vec!["ok", "failed", "ok", "ok"]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("depth"), Some(&ColumnType::Integer));
+        assert_eq!(classification.get("status"), Some(&ColumnType::String));
+    }
+
+    #[test]
+    fn test_crlf_dataset_streams_records() {
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/crlf_short_record.csv, separator: ','}")
+                .unwrap();
+        let mut reader = dataset.reader().unwrap();
+        let mut records = reader.records().unwrap();
+        // Records before a malformed line are read before the error is reported.
+        assert_eq!(records.next().unwrap().unwrap(), vec!["A", "12", "ok"]);
+        let error = records.next().unwrap().unwrap_err();
+        assert!(error.to_string().contains("crlf_short_record.csv"));
+    }
+
+    #[test]
+    fn test_nested_json_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/nested.json"),
+            header_rows: 1,
+            ..Default::default()
+        };
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec![
+                "name",
+                "address.city",
+                "address.geo.lat",
+                "tags",
+                "address.zip",
+                "score"
+            ]
+        );
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![
+                vec!["Delia", "Berlin", "52.5", r#"["a","b"]"#, "", ""],
+                vec!["George", "Paris", "", "", "75001", "3"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_utf16_json_dataset() {
+        let dataset = DatasetSpecs {
+            path: PathBuf::from("tests/data/utf16.json"),
+            header_rows: 1,
+            ..Default::default()
+        };
+        let error = dataset.reader().err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("is encoded in utf-16le, which is only supported for delimited files"));
+    }
+
+    #[test]
+    fn test_headerless_dataset() {
+        for path in ["tests/data/headerless.csv", "tests/data/headerless.tsv"] {
+            let dataset = DatasetSpecs {
+                path: PathBuf::from(path),
+                header_rows: 1,
+                column_names: Some(vec![
+                    String::from("first"),
+                    String::from("last"),
+                    String::from("count"),
+                ]),
+                double_quote: true,
+                ..Default::default()
+            };
+            let mut reader = dataset.reader().unwrap();
+            assert_eq!(reader.headers().unwrap(), vec!["first", "last", "count"]);
+            assert_eq!(
+                reader.records().unwrap().next().unwrap().unwrap(),
+                vec!["Delia", "Arnold", "3"]
+            );
+            assert_eq!(dataset.size().unwrap(), 3);
+            let classification = classify_table(&dataset).unwrap();
+            assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+        }
+    }
+
+    /// Returns the spec of a delimited dataset under the given path with default options.
+    fn delimited_dataset(path: &str) -> DatasetSpecs {
+        DatasetSpecs {
+            path: PathBuf::from(path),
+            header_rows: 1,
+            double_quote: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detect_separator() {
+        assert_eq!(
+            detect_separator(&delimited_dataset("tests/data/uniform_datatypes.csv")).unwrap(),
+            ','
+        );
+        assert_eq!(
+            detect_separator(&delimited_dataset("tests/data/uniform_datatypes.csv.gz")).unwrap(),
+            ','
+        );
+        assert_eq!(
+            detect_separator(&delimited_dataset("tests/data/uniform_datatypes.tsv")).unwrap(),
+            '\t'
+        );
+    }
+
+    #[test]
+    fn test_detect_ambiguous_separator() {
+        assert!(
+            detect_separator(&delimited_dataset("tests/data/ambiguous_separator.csv")).is_err()
+        );
+    }
+
+    #[test]
+    fn test_dataset_with_skipped_lines() {
+        let dataset = DatasetSpecs {
+            skip_rows: 2,
+            comment_char: Some('#'),
+            ..delimited_dataset("tests/data/skipped_lines.tsv")
+        };
+        assert_eq!(detect_separator(&dataset).unwrap(), '\t');
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["gene", "sample", "count"]);
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![vec!["BRCA1", "a", "5"], vec!["TP53", "b", "12"]]
+        );
+        assert_eq!(
+            skipped_lines(&dataset).unwrap(),
+            vec![
+                "exported by LIMS v2.1",
+                "",
+                "#reference=GRCh38",
+                "#filter: PASS only",
+                "# end of metadata",
+                "#TP53 recalled below",
+            ]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+    }
+
+    #[test]
+    fn test_dataset_empty_after_skipping() {
+        let dataset = DatasetSpecs {
+            skip_rows: 10,
+            separator: Some('\t'),
+            ..delimited_dataset("tests/data/skipped_lines.tsv")
+        };
+        assert!(dataset.reader().is_err());
+    }
+
+    #[test]
+    fn test_dataset_with_escaped_quotes() {
+        let dataset = DatasetSpecs {
+            quote: Some(String::from("'")),
+            escape: Some(String::from("\\")),
+            double_quote: false,
+            flexible: false,
+            encoding: None,
+            source_column: false,
+            classify_rows: Default::default(),
+            padded_rows: 0,
+            selected_columns: None,
+            row_filter: None,
+            computed_columns: vec![],
+            sample: None,
+            number_formats: HashMap::new(),
+            default_number_format: None,
+            stripped_columns: HashMap::new(),
+            column_types: HashMap::new(),
+            na_values: HashMap::new(),
+            default_na_values: None,
+            min_numeric_fractions: HashMap::new(),
+            default_min_numeric_fraction: None,
+            dedup_headers: false,
+            ..delimited_dataset("tests/data/escaped_quotes.csv")
+        };
+        assert_eq!(detect_separator(&dataset).unwrap(), ',');
+        let mut reader = dataset.reader().unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["title", "quote", "year"]);
+        assert_eq!(
+            reader.records().unwrap().map(Result::unwrap).collect_vec(),
+            vec![
+                vec!["Casablanca", "Here's looking at you, kid.", "1942"],
+                vec!["Jaws", "You're gonna need a bigger boat.", "1975"],
+            ]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("year").unwrap(), &ColumnType::Integer);
+    }
+
+    #[test]
+    fn test_strict_dataset_with_ragged_rows() {
+        let dataset = DatasetSpecs {
+            separator: Some(','),
+            ..delimited_dataset("tests/data/ragged.csv")
+        };
+        let error = check_record_lengths(&dataset).err().unwrap().to_string();
+        assert!(error.contains("Line 3"));
+        assert!(error.contains("has 2 fields but 3 were expected"));
+    }
+
+    #[test]
+    fn test_flexible_dataset_with_ragged_rows() {
+        let dataset = DatasetSpecs {
+            separator: Some(','),
+            flexible: true,
+            encoding: None,
+            source_column: false,
+            classify_rows: Default::default(),
+            ..delimited_dataset("tests/data/ragged.csv")
+        };
+        assert_eq!(check_record_lengths(&dataset).unwrap(), 1);
+        let records = dataset
+            .reader()
+            .unwrap()
+            .records()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect_vec();
+        assert_eq!(records[1], vec!["TP53", "2", ""]);
+        assert_eq!(dataset.size().unwrap(), 3);
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("count").unwrap(), &ColumnType::Integer);
+    }
+
+    #[test]
+    fn test_dataset_with_encoding() {
+        for (path, encoding) in [
+            ("tests/data/latin1.csv", "latin1"),
+            ("tests/data/utf16.csv", "utf-16le"),
+        ] {
+            let dataset = DatasetSpecs {
+                encoding: Some(String::from(encoding)),
+                ..delimited_dataset(path)
+            };
+            assert_eq!(detect_separator(&dataset).unwrap(), ',');
+            let mut reader = dataset.reader().unwrap();
+            assert_eq!(reader.headers().unwrap(), vec!["name", "city", "count"]);
+            assert_eq!(
+                reader.records().unwrap().map(Result::unwrap).collect_vec(),
+                vec![vec!["Müller", "Zürich", "3"], vec!["Zoë", "Besançon", "5"]]
+            );
+        }
+    }
+
+    #[test]
+    fn test_dataset_with_invalid_utf8() {
+        let dataset = DatasetSpecs {
+            separator: Some(','),
+            ..delimited_dataset("tests/data/latin1.csv")
+        };
+        let error = check_record_lengths(&dataset).err().unwrap().to_string();
+        assert!(error.contains("byte 17 (line 2)"));
+        assert!(error.contains("encoding"));
+    }
+
+    #[test]
+    fn test_normalize_headers() {
+        assert_eq!(normalize_header(" depth "), "depth");
+        assert_eq!(normalize_header("read\r\n count"), "read count");
+        assert_eq!(normalize_header("allele frequency"), "allele frequency");
+
+        let dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/messy_headers.csv, separator: ','}").unwrap();
+        assert_eq!(
+            dataset.reader().unwrap().headers().unwrap(),
+            vec!["sample", "depth", "read count"]
+        );
+        assert_eq!(
+            normalized_headers(&dataset).unwrap(),
+            vec![
+                (" sample".to_string(), "sample".to_string()),
+                ("depth ".to_string(), "depth".to_string()),
+                ("read\n count".to_string(), "read count".to_string()),
+            ]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.get("depth"), Some(&ColumnType::Integer));
+        assert_eq!(classification.get("read count"), Some(&ColumnType::Integer));
+    }
+
+    #[test]
+    fn test_duplicate_headers() {
+        let headers = ["sample", "score", "score_2", "score", "score"].map(String::from);
+        assert_eq!(
+            duplicate_headers(&headers),
+            vec![("score".to_string(), vec![1, 3, 4])]
+        );
+        assert_eq!(
+            dedup_headers(&headers),
+            vec!["sample", "score", "score_2", "score_3", "score_4"]
+        );
+
+        let mut dataset: DatasetSpecs =
+            serde_yaml::from_str("{path: tests/data/duplicate_header.csv, separator: ','}")
+                .unwrap();
+        let error = dataset.reader().err().unwrap().to_string();
+        assert!(error.contains("\"sample\" at indices 0 and 2"));
+        dataset.dedup_headers = true;
+        assert_eq!(
+            dataset.reader().unwrap().headers().unwrap(),
+            vec!["sample", "score", "sample_2"]
+        );
+        let classification = classify_table(&dataset).unwrap();
+        assert_eq!(classification.len(), 3);
+        assert_eq!(classification.get("sample_2"), Some(&ColumnType::String));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::utils::reader::keep_until_exit;
+use anyhow::{bail, Context, Result};
+use reqwest::blocking::get;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Settings for datasets that are downloaded from HTTP(S) URLs.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DownloadSettings {
+    pub(crate) cache_dir: Option<PathBuf>,
+    pub(crate) offline: bool,
+}
+
+/// Returns the URL of the given dataset path if it points to a remote file.
+pub(crate) fn remote_url(path: &Path) -> Option<String> {
+    let path = path.to_str()?;
+    (path.starts_with("http://") || path.starts_with("https://")).then(|| path.to_owned())
+}
+
+/// Downloads the given dataset once and returns the path of the local copy.
+/// Copies are stored in the cache directory if given and reused by later runs.
+pub(crate) fn fetch_dataset(name: &str, url: &str, settings: &DownloadSettings) -> Result<PathBuf> {
+    // Keep the original file name so that the file format can still be derived from it.
+    let file_name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|file_name| !file_name.is_empty())
+        .unwrap_or("dataset");
+    let cached = settings
+        .cache_dir
+        .as_ref()
+        .map(|cache_dir| cache_dir.join(format!("{:x}-{file_name}", md5::compute(url))));
+    if let Some(cached) = cached.as_ref().filter(|cached| cached.exists()) {
+        return Ok(cached.to_owned());
+    }
+    if settings.offline {
+        bail!(RemoteError::Offline {
+            dataset: name.to_owned(),
+            url: url.to_owned()
+        })
+    }
+    let directory = settings
+        .cache_dir
+        .clone()
+        .unwrap_or_else(std::env::temp_dir);
+    fs::create_dir_all(&directory).context(format!(
+        "Failed to create cache directory {directory:?} for dataset {name}."
+    ))?;
+    let mut file = tempfile::Builder::new()
+        .prefix("datavzrd-")
+        .suffix(&format!("-{file_name}"))
+        .tempfile_in(&directory)
+        .context(format!("Failed to create local copy of dataset {name}."))?;
+    let mut response =
+        get(url).context(format!("Failed to download dataset {name} from {url}."))?;
+    if !response.status().is_success() {
+        bail!(RemoteError::UnexpectedStatus {
+            dataset: name.to_owned(),
+            url: url.to_owned(),
+            status: response.status().as_u16()
+        })
+    }
+    response
+        .copy_to(&mut file)
+        .context(format!("Failed to download dataset {name} from {url}."))?;
+    match cached {
+        Some(cached) => {
+            file.persist(&cached)
+                .context(format!("Failed to cache dataset {name} in {cached:?}."))?;
+            Ok(cached)
+        }
+        None => Ok(keep_until_exit(file.into_temp_path())),
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    #[error("Refusing to download dataset {dataset} from {url} in offline mode. Please run without --offline or provide a cache directory containing the dataset.")]
+    Offline { dataset: String, url: String },
+    #[error("Failed to download dataset {dataset} from {url}. The server responded with status {status}.")]
+    UnexpectedStatus {
+        dataset: String,
+        url: String,
+        status: u16,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::remote::{fetch_dataset, remote_url, DownloadSettings};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_remote_url() {
+        assert_eq!(
+            remote_url(Path::new("https://example.com/calls.csv")),
+            Some(String::from("https://example.com/calls.csv"))
+        );
+        assert_eq!(
+            remote_url(Path::new("tests/data/uniform_datatypes.csv")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fetch_dataset_offline() {
+        let settings = DownloadSettings {
+            cache_dir: None,
+            offline: true,
+        };
+        let error = fetch_dataset("calls", "https://example.com/calls.csv", &settings)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("calls"));
+        assert!(error.to_string().contains("https://example.com/calls.csv"));
+    }
+
+    #[test]
+    fn test_fetch_cached_dataset_offline() {
+        let url = "https://example.com/cached/calls.csv";
+        let cache_dir = std::env::temp_dir().join("datavzrd-test-cache");
+        fs::create_dir_all(&cache_dir).unwrap();
+        let cached = cache_dir.join(format!("{:x}-calls.csv", md5::compute(url)));
+        fs::copy("tests/data/uniform_datatypes.csv", &cached).unwrap();
+        let settings = DownloadSettings {
+            cache_dir: Some(cache_dir.clone()),
+            offline: true,
+        };
+        assert_eq!(fetch_dataset("calls", url, &settings).unwrap(), cached);
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
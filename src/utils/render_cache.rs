@@ -0,0 +1,277 @@
+use crate::spec::{read_config, ItemSpecs, ItemsSpec};
+use crate::utils::reader::glob_paths;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Name of the file in the output directory that records the inputs of the rendered views.
+pub(crate) const CACHE_FILE: &str = ".datavzrd_cache.json";
+
+/// Inputs a view has been rendered from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CacheEntry {
+    /// Hash of the config of the view, its datasets and linked views and the report settings.
+    pub(crate) config_hash: String,
+    /// Hash of the paths, modification times and sizes of the files the view is rendered from.
+    pub(crate) csv_hash: String,
+    pub(crate) datavzrd_version: String,
+}
+
+/// Cache entries by view name.
+pub(crate) type RenderCache = HashMap<String, CacheEntry>;
+
+/// Everything that is hashed into the config hash of a view.
+#[derive(Serialize)]
+struct ViewInputs<'a, C: Serialize> {
+    views: BTreeMap<&'a str, Option<&'a Value>>,
+    datasets: BTreeMap<&'a str, Option<&'a Value>>,
+    report: &'a Value,
+    menu: &'a BTreeMap<&'a String, (bool, &'a Option<String>)>,
+    context: &'a C,
+}
+
+/// Reads the render cache of the report in the given output directory. A missing or invalid
+/// cache is empty, so that all views are rendered again.
+pub(crate) fn read_cache(output: &Path) -> RenderCache {
+    fs::read_to_string(output.join(CACHE_FILE))
+        .ok()
+        .and_then(|cache| serde_json::from_str(&cache).ok())
+        .unwrap_or_default()
+}
+
+/// Writes the given render cache to the given output directory.
+pub(crate) fn write_cache(output: &Path, cache: &RenderCache) -> Result<()> {
+    fs::write(
+        output.join(CACHE_FILE),
+        serde_json::to_string_pretty(cache)?,
+    )?;
+    Ok(())
+}
+
+/// Returns the views whose entries in the current cache equal those in the previous cache and
+/// whose directories still exist in the given output directory.
+pub(crate) fn unchanged_views(
+    previous: &RenderCache,
+    current: &RenderCache,
+    output: &Path,
+) -> HashSet<String> {
+    current
+        .iter()
+        .filter(|(name, entry)| previous.get(*name) == Some(*entry) && output.join(name).is_dir())
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+/// Returns the cache entries of all views of the given specs read from the given config file.
+/// The given context has to contain everything else that is rendered into the pages of every
+/// view, e.g. the view sizes shown in the menu, and must serialize deterministically.
+pub(crate) fn cache_entries(
+    config: &Path,
+    specs: &ItemsSpec,
+    context: &impl Serialize,
+) -> Result<RenderCache> {
+    // The raw config is hashed since it serializes in a stable order unlike the parsed one.
+    let raw: Value = serde_yaml::from_str(&read_config(config)?)?;
+    let section = |key: &str, name: &str| raw.get(key).and_then(|section| section.get(name));
+    let mut report = raw.as_mapping().cloned().unwrap_or_default();
+    report.remove(&Value::from("views"));
+    report.remove(&Value::from("datasets"));
+    let report = Value::Mapping(report);
+    let menu = specs
+        .views
+        .iter()
+        .map(|(name, view)| (name, (view.hidden, &view.description)))
+        .collect();
+
+    let mut cache = RenderCache::new();
+    for (name, view) in &specs.views {
+        let views: BTreeSet<&str> = std::iter::once(name.as_str())
+            .chain(linked_views(specs, view))
+            .collect();
+        let datasets: BTreeSet<&str> = views
+            .iter()
+            .filter_map(|view| specs.views.get(*view))
+            .flat_map(view_datasets)
+            .collect();
+        let inputs = ViewInputs {
+            views: views
+                .iter()
+                .map(|view| (*view, section("views", view)))
+                .collect(),
+            datasets: datasets
+                .iter()
+                .map(|dataset| (*dataset, section("datasets", dataset)))
+                .collect(),
+            report: &report,
+            menu: &menu,
+            context,
+        };
+
+        let mut files = BTreeSet::new();
+        for dataset in datasets.iter().filter_map(|name| specs.datasets.get(*name)) {
+            let path = &dataset.path;
+            if path.as_os_str().is_empty() {
+                continue;
+            }
+            match glob_paths(path) {
+                Ok(Some(paths)) => files.extend(paths),
+                _ => {
+                    files.insert(path.to_path_buf());
+                }
+            }
+        }
+        for view in views.iter().filter_map(|view| section("views", view)) {
+            referenced_files(view, &mut files);
+        }
+
+        cache.insert(
+            name.to_owned(),
+            CacheEntry {
+                config_hash: hash(serde_yaml::to_string(&inputs)?),
+                csv_hash: fingerprint(&files)?,
+                datavzrd_version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+        );
+    }
+    Ok(cache)
+}
+
+/// Returns the views whose datasets are read when rendering links of the given view.
+fn linked_views<'a>(specs: &'a ItemsSpec, view: &'a ItemSpecs) -> impl Iterator<Item = &'a str> {
+    let links = view
+        .dataset
+        .as_ref()
+        .and_then(|dataset| specs.datasets.get(dataset))
+        .and_then(|dataset| dataset.links.as_ref())
+        .into_iter()
+        .flat_map(|links| links.values())
+        .flat_map(|link| {
+            link.view.as_deref().into_iter().chain(
+                link.table_row
+                    .as_deref()
+                    .and_then(|table_row| table_row.split_once('/'))
+                    .map(|(view, _)| view),
+            )
+        });
+    let linked_tables = view
+        .render_table
+        .iter()
+        .flat_map(|render_table| render_table.columns.values())
+        .filter_map(|column| column.link_to_table.as_deref());
+    links.chain(linked_tables)
+}
+
+/// Returns the names of the datasets rendered by the given view.
+fn view_datasets(view: &ItemSpecs) -> Vec<&str> {
+    view.dataset
+        .as_deref()
+        .into_iter()
+        .chain(
+            view.datasets
+                .iter()
+                .flat_map(|datasets| datasets.values().map(String::as_str)),
+        )
+        .collect()
+}
+
+/// Collects all values of the given config that are paths of existing files, e.g. scripts,
+/// images, plot specs or local spells.
+fn referenced_files(value: &Value, files: &mut BTreeSet<PathBuf>) {
+    match value {
+        Value::String(value) if Path::new(value).is_file() => {
+            files.insert(PathBuf::from(value));
+        }
+        Value::Sequence(values) => {
+            for value in values {
+                referenced_files(value, files);
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (_, value) in mapping.iter() {
+                referenced_files(value, files);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Hashes the paths, modification times and sizes of the given files as a fast proxy for
+/// their contents.
+fn fingerprint(files: &BTreeSet<PathBuf>) -> Result<String> {
+    let mut fingerprint = String::new();
+    for file in files {
+        let metadata = fs::metadata(file)?;
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_nanos();
+        fingerprint.push_str(&format!(
+            "{}\t{modified}\t{}\n",
+            file.display(),
+            metadata.len()
+        ));
+    }
+    Ok(hash(fingerprint))
+}
+
+/// Returns the hex encoded SHA-256 hash of the given string.
+fn hash(value: String) -> String {
+    format!("{:x}", Sha256::digest(value.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spec::ItemsSpec;
+    use crate::utils::render_cache::{cache_entries, linked_views, unchanged_views};
+    use std::fs;
+    use std::path::Path;
+
+    fn specs(config: &str) -> ItemsSpec {
+        serde_yaml::from_str(&fs::read_to_string(config).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_cache_entries() {
+        let config = Path::new("tests/cross-links.yaml");
+        let specs = specs("tests/cross-links.yaml");
+        assert_eq!(
+            linked_views(&specs, &specs.views["variants"]).collect::<Vec<_>>(),
+            vec!["genes"]
+        );
+        assert_eq!(linked_views(&specs, &specs.views["genes"]).count(), 0);
+
+        let cache = cache_entries(config, &specs, &"context").unwrap();
+        assert_eq!(cache, cache_entries(config, &specs, &"context").unwrap());
+        // The linked dataset is read when rendering the variants.
+        assert_ne!(cache["variants"].csv_hash, cache["genes"].csv_hash);
+        assert_eq!(
+            cache["variants"].datavzrd_version,
+            env!("CARGO_PKG_VERSION")
+        );
+
+        let other_context = cache_entries(config, &specs, &"other context").unwrap();
+        assert_ne!(
+            cache["genes"].config_hash,
+            other_context["genes"].config_hash
+        );
+        assert_eq!(cache["genes"].csv_hash, other_context["genes"].csv_hash);
+    }
+
+    #[test]
+    fn test_unchanged_views() {
+        let config = Path::new("tests/cross-links.yaml");
+        let specs = specs("tests/cross-links.yaml");
+        let output = tempfile::tempdir().unwrap();
+        let previous = cache_entries(config, &specs, &"context").unwrap();
+        let mut current = previous.clone();
+        fs::create_dir(output.path().join("variants")).unwrap();
+        fs::create_dir(output.path().join("genes")).unwrap();
+        assert_eq!(unchanged_views(&previous, &current, output.path()).len(), 2);
+
+        current.get_mut("genes").unwrap().datavzrd_version = "0.0.0".to_string();
+        fs::remove_dir(output.path().join("variants")).unwrap();
+        assert!(unchanged_views(&previous, &current, output.path()).is_empty());
+    }
+}
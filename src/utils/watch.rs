@@ -0,0 +1,201 @@
+use crate::spec::{extended_configs, read_config, ItemsSpec};
+use crate::utils::reader::{glob_paths, STDIN_PATH};
+use crate::utils::remote::remote_url;
+use anyhow::Result;
+use log::error;
+use notify::{recommended_watcher, Event, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Time without further filesystem events after which a change triggers a re-render. Editors
+/// often write files in multiple chunks or via temporary files.
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(150);
+
+/// Coalesces bursts of filesystem events so that they trigger a single re-render once no
+/// further event occurred for the given delay.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Debouncer {
+    delay: Duration,
+    last_event: Option<Instant>,
+}
+
+impl Debouncer {
+    pub(crate) fn new(delay: Duration) -> Self {
+        Debouncer {
+            delay,
+            last_event: None,
+        }
+    }
+
+    /// Registers an event that occurred at the given time.
+    pub(crate) fn event(&mut self, now: Instant) {
+        self.last_event = Some(now);
+    }
+
+    /// Returns true if the delay has passed since the last event at the given time. Only
+    /// returns true once per burst of events.
+    pub(crate) fn is_due(&mut self, now: Instant) -> bool {
+        match self.last_event {
+            Some(last_event) if now.duration_since(last_event) >= self.delay => {
+                self.last_event = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns how long to wait for further events at the given time, which is unbounded if no
+    /// event is pending.
+    pub(crate) fn timeout(&self, now: Instant) -> Duration {
+        match self.last_event {
+            Some(last_event) => self.delay.saturating_sub(now.duration_since(last_event)),
+            None => Duration::MAX,
+        }
+    }
+}
+
+/// Calls the given render function whenever the given config file or any local file it
+/// references (datasets and custom stylesheets) is modified, created or replaced. Bursts of
+/// events are coalesced with a delay of 150 ms. Files referenced by a changed config are
+/// watched from the next re-render on. Errors of the render function are logged without
+/// stopping to watch, so that the user can fix the config and save again. Runs until the
+/// watcher stops.
+pub(crate) fn watch(config: &Path, mut render: impl FnMut() -> Result<()>) -> Result<()> {
+    let (sender, receiver) = channel();
+    let mut watcher = recommended_watcher(sender)?;
+    // Parent directories are watched since editors often replace files instead of writing to
+    // them, which would end watches of the files themselves.
+    let mut directories = HashSet::new();
+    let mut files = HashSet::new();
+    update_watched_files(&mut watcher, config, &mut files, &mut directories)?;
+    let mut debouncer = Debouncer::new(DEBOUNCE_DELAY);
+    loop {
+        match receiver.recv_timeout(debouncer.timeout(Instant::now())) {
+            Ok(Ok(event)) => {
+                if affects_files(&event, &files) {
+                    debouncer.event(Instant::now());
+                }
+            }
+            Ok(Err(err)) => error!("Failed to watch files: {err}"),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        if debouncer.is_due(Instant::now()) {
+            if let Err(err) = render() {
+                error!("Failed to render report: {err:#}");
+            }
+            update_watched_files(&mut watcher, config, &mut files, &mut directories)?;
+        }
+    }
+}
+
+/// Returns true if the given event modifies, creates or removes any of the given files.
+fn affects_files(event: &Event, files: &HashSet<PathBuf>) -> bool {
+    !event.kind.is_access() && event.paths.iter().any(|path| files.contains(path))
+}
+
+/// Watches the directories of the config file and all local files referenced by it. The
+/// previously watched files are kept if the config cannot be parsed.
+fn update_watched_files(
+    watcher: &mut impl Watcher,
+    config: &Path,
+    files: &mut HashSet<PathBuf>,
+    directories: &mut HashSet<PathBuf>,
+) -> Result<()> {
+    let Ok(referenced) = referenced_files(config) else {
+        return Ok(());
+    };
+    *files = referenced
+        .iter()
+        .filter_map(|path| absolute_path(path))
+        .collect();
+    for directory in files.iter().filter_map(|path| path.parent()) {
+        if directories.insert(directory.to_path_buf()) {
+            watcher.watch(directory, RecursiveMode::NonRecursive)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the given config file, the configs it extends and the local datasets and
+/// stylesheets referenced by them.
+fn referenced_files(config: &Path) -> Result<Vec<PathBuf>> {
+    let specs: ItemsSpec = serde_yaml::from_str(&read_config(config)?)?;
+    let mut files = vec![config.to_path_buf()];
+    files.extend(extended_configs(config)?);
+    for dataset in specs.datasets.values() {
+        let path = &dataset.path;
+        if path.as_os_str().is_empty()
+            || path == Path::new(STDIN_PATH)
+            || remote_url(path).is_some()
+        {
+            continue;
+        }
+        match glob_paths(path) {
+            Ok(Some(paths)) => files.extend(paths),
+            _ => files.push(path.to_path_buf()),
+        }
+    }
+    files.extend(specs.custom_css);
+    Ok(files)
+}
+
+/// Returns the absolute path of the given file, whose directory has to exist.
+fn absolute_path(path: &Path) -> Option<PathBuf> {
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Some(fs::canonicalize(directory).ok()?.join(path.file_name()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::watch::{referenced_files, Debouncer};
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_debounce_events() {
+        let delay = Duration::from_millis(150);
+        let mut debouncer = Debouncer::new(delay);
+        let start = Instant::now();
+        assert!(!debouncer.is_due(start));
+        assert_eq!(debouncer.timeout(start), Duration::MAX);
+
+        // Events in quick succession are coalesced.
+        debouncer.event(start);
+        debouncer.event(start + Duration::from_millis(100));
+        assert!(!debouncer.is_due(start + Duration::from_millis(200)));
+        assert_eq!(
+            debouncer.timeout(start + Duration::from_millis(200)),
+            Duration::from_millis(50)
+        );
+        assert!(debouncer.is_due(start + Duration::from_millis(250)));
+        // A burst of events only triggers once.
+        assert!(!debouncer.is_due(start + Duration::from_millis(300)));
+
+        debouncer.event(start + Duration::from_millis(400));
+        assert_eq!(
+            debouncer.timeout(start + Duration::from_millis(600)),
+            Duration::ZERO
+        );
+        assert!(debouncer.is_due(start + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn test_referenced_files() {
+        let files = referenced_files(&PathBuf::from("tests/custom_css.yaml")).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("tests/custom_css.yaml"),
+                PathBuf::from("tests/data/stats.csv"),
+                PathBuf::from("tests/style/branding.css"),
+            ]
+        );
+    }
+}
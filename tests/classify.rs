@@ -0,0 +1,42 @@
+use std::process::{Command, Output};
+
+fn classify(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_datavzrd"))
+        .arg("classify")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_classify_json() {
+    let output = classify(&["tests/data/mostly_numbers.csv", "--format", "json"]);
+    assert!(output.status.success());
+    let summaries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        summaries[1],
+        serde_json::json!({
+            "column": "score",
+            "type": "string",
+            "empty": 0,
+            "unparseable": 2,
+            "distinct": 10
+        })
+    );
+}
+
+#[test]
+fn test_classify_duplicate_header() {
+    let output = classify(&["tests/data/duplicate_header.csv", "--separator", ","]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("\"sample\" at indices 0 and 2"));
+}
+
+#[test]
+fn test_classify_missing_file() {
+    let output = classify(&["tests/data/does-not-exist.csv"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
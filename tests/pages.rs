@@ -0,0 +1,219 @@
+use jsonm::unpacker::Unpacker;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn render(config: &str, output: &Path) -> Output {
+    render_with_args(config, output, &[])
+}
+
+/// Renders the given config with additional arguments, e.g. `--debug` to keep the rendered
+/// pages and scripts unminified.
+fn render_with_args(config: &str, output: &Path, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_datavzrd"))
+        .args([config, "--no-progress", "--overwrite-output"])
+        .args(args)
+        .arg("--output")
+        .arg(output)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    output
+}
+
+/// Returns the rows of the given page of a view, decompressing them like the browser does.
+fn page_rows(view: &Path, page: usize) -> Vec<Vec<String>> {
+    let data = fs::read_to_string(view.join("data").join(format!("data_{page}.js"))).unwrap();
+    let compressed: String = serde_json::from_str(
+        data.lines()
+            .next()
+            .unwrap()
+            .strip_prefix("const data = ")
+            .unwrap()
+            .trim_end_matches(';'),
+    )
+    .unwrap();
+    let packed = String::from_utf16(&lz_str::decompress_from_utf16(&compressed).unwrap()).unwrap();
+    Unpacker::new()
+        .unpack(&serde_json::from_str(&packed).unwrap())
+        .unwrap()
+}
+
+/// Returns the given rows as owned strings.
+fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| row.iter().map(|value| value.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_render_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render("tests/pages.yaml", &output);
+
+    // The seven rows are split into pages of three rows each.
+    let view = output.join("scores");
+    for page in 1..=3 {
+        assert!(view.join(format!("index_{page}.html")).exists());
+        assert!(view.join("data").join(format!("data_{page}.js")).exists());
+    }
+    assert!(!view.join("index_4.html").exists());
+
+    assert_eq!(
+        page_rows(&view, 1),
+        rows(&[&["A", "a", "4"], &["B", "b", "2"], &["C", "b", ""]])
+    );
+    assert_eq!(page_rows(&view, 3), rows(&[&["G", "b", "NA"]]));
+    let data = fs::read_to_string(view.join("data").join("data_3.js")).unwrap();
+    assert!(data.contains("const CURRENT_PAGE = 3;"));
+}
+
+#[test]
+fn test_render_single_page() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render("tests/display_name.yaml", &output);
+
+    // Tables below the in-memory threshold are rendered to a single page with all rows.
+    let view = output.join("scores");
+    assert!(view.join("index_1.html").exists());
+    assert!(!view.join("index_2.html").exists());
+    let records = page_rows(&view, 1);
+    assert_eq!(records.len(), 7);
+    assert_eq!(records[6], vec!["G", "b", "NA"]);
+}
+
+#[test]
+fn test_render_messy_headers() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    let rendered = render("tests/messy_headers.yaml", &output);
+    assert!(String::from_utf8(rendered.stderr)
+        .unwrap()
+        .contains("Column \"depth \" of dataset \"samples\" is referred to as \"depth\""));
+
+    // Columns are configured, plotted and rendered under their normalized names.
+    let view = output.join("samples");
+    assert!(view.join("index_1.html").exists());
+    let plots = fs::read_to_string(view.join("plots").join("plots.js")).unwrap();
+    assert!(plots.contains("Sequencing depth"));
+    assert!(plots.contains("read count"));
+    assert!(!plots.contains("read\\n count"));
+    let config = fs::read_to_string(view.join("config.js")).unwrap();
+    assert!(config.contains(r#""read count""#));
+}
+
+#[test]
+fn test_render_sorted_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render("tests/sorted_pages.yaml", &output);
+
+    // The rows are sorted before they are split into pages.
+    let view = output.join("scores");
+    let mut scores = Vec::new();
+    for page in 1..=4 {
+        let records = page_rows(&view, page);
+        assert_eq!(records.len(), 5);
+        scores.extend(records.iter().map(|row| row[1].parse::<f64>().unwrap()));
+    }
+    assert_eq!(scores.len(), 20);
+    assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(!view.join("index_5.html").exists());
+}
+
+#[test]
+fn test_render_column_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render_with_args("tests/filterable.yaml", &output, &["--debug"]);
+
+    // Only the column configured with filterable: false gets no filter in its header, and the
+    // numeric column configured with range-filter: false is filtered by text.
+    let config = fs::read_to_string(output.join("scores").join("config.js")).unwrap();
+    assert!(config.contains(r#""unfilterable_columns":["group"]"#));
+    let page = fs::read_to_string(output.join("scores").join("index_1.html")).unwrap();
+    assert!(page.contains(r#"data-filter-control="input" data-title="score""#));
+    assert!(!page.contains(r#"data-filter-control="range""#));
+}
+
+#[test]
+fn test_render_range_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render_with_args("tests/display_name.yaml", &output, &["--debug"]);
+
+    // Numeric columns are filtered by a range slider between their minimum and maximum.
+    let page = fs::read_to_string(output.join("scores").join("index_1.html")).unwrap();
+    assert!(page.contains(
+        r#"<div class="filter-slider" data-filter-control="range" data-title="score" data-min="2" data-max="9"></div>"#
+    ));
+    assert!(page.contains(r#"<script src="../static/nouislider.min.js"></script>"#));
+}
+
+#[test]
+fn test_render_range_filters_of_plot_domains() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render_with_args("tests/range_domains.yaml", &output, &["--debug"]);
+
+    // Sliders span the domain of tick plots and the range of heatmaps together with their
+    // auxiliary domain columns, and the range of the values of other columns.
+    let page = fs::read_to_string(output.join("values").join("index_1.html")).unwrap();
+    let slider = |column: &str, min: &str, max: &str| {
+        format!(
+            r#"data-filter-control="range" data-title="{column}" data-min="{min}" data-max="{max}""#
+        )
+    };
+    assert!(page.contains(&slider("x", "0", "10")));
+    assert!(page.contains(&slider("y", "-1", "11")));
+    assert!(page.contains(&slider("z", "-1", "2")));
+}
+
+#[test]
+fn test_render_filter_inputs() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render_with_args("tests/filterable.yaml", &output, &["--debug"]);
+
+    // The text input of a filterable column is rendered into the page, while a column
+    // configured with filterable: false gets none.
+    let page = fs::read_to_string(output.join("scores").join("index_1.html")).unwrap();
+    assert!(page.contains(
+        r#"<input type="text" class="form-control form-control-sm" placeholder="Filter score" data-filter-control="input" data-title="score">"#
+    ));
+    assert!(!page.contains(r#"data-title="group""#));
+}
+
+#[test]
+fn test_render_category_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render_with_args("tests/category_filters.yaml", &output, &["--debug"]);
+
+    // Only the column with at most five distinct values offers its sorted distinct values
+    // for selection.
+    let mut reader = csv::Reader::from_path("tests/data/stats.csv").unwrap();
+    let mut groups: Vec<String> = reader
+        .records()
+        .map(|record| record.unwrap()[1].to_string())
+        .collect();
+    groups.sort();
+    groups.dedup();
+    assert_eq!(groups, ["", "a", "b", "c"]);
+
+    let page = fs::read_to_string(output.join("scores").join("index_1.html")).unwrap();
+    let select = page
+        .split(r#"<select multiple data-filter-control="select" data-title="group">"#)
+        .nth(1)
+        .and_then(|rest| rest.split("</select>").next())
+        .unwrap();
+    let options: Vec<String> = select
+        .split(r#"<option value=""#)
+        .skip(1)
+        .map(|option| option.split('"').next().unwrap().to_string())
+        .collect();
+    assert_eq!(options, groups);
+    assert!(!page.contains(r#"data-filter-control="select" data-title="name""#));
+}
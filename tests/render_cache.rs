@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+fn render(output: &Path, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_datavzrd"))
+        .args([
+            "tests/display_name.yaml",
+            "--no-progress",
+            "--overwrite-output",
+            "-v",
+        ])
+        .args(args)
+        .arg("--output")
+        .arg(output)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    output
+}
+
+/// Returns the modification times of all files in the given directory.
+fn modification_times(path: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let mut times = BTreeMap::new();
+    for entry in fs::read_dir(path).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            times.extend(modification_times(&path));
+        } else {
+            let modified = fs::metadata(&path).unwrap().modified().unwrap();
+            times.insert(path, modified);
+        }
+    }
+    times
+}
+
+#[test]
+fn test_skip_unchanged_views() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report");
+    render(&output, &[]);
+    assert!(output.join(".datavzrd_cache.json").exists());
+    let pages = modification_times(&output.join("scores"));
+    assert!(!pages.is_empty());
+
+    sleep(Duration::from_millis(20));
+    let rerender = render(&output, &[]);
+    assert!(String::from_utf8(rerender.stderr)
+        .unwrap()
+        .contains("Skipping unchanged table: scores"));
+    assert_eq!(modification_times(&output.join("scores")), pages);
+    assert!(output.join("index.html").exists());
+
+    render(&output, &["--no-cache"]);
+    let rerendered = modification_times(&output.join("scores"));
+    assert_eq!(
+        rerendered.keys().collect::<Vec<_>>(),
+        pages.keys().collect::<Vec<_>>()
+    );
+    assert!(rerendered
+        .iter()
+        .all(|(path, modified)| modified > &pages[path]));
+}
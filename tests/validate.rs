@@ -0,0 +1,38 @@
+use std::process::{Command, Output};
+
+fn validate(config: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_datavzrd"))
+        .args(["--validate", config])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_validate_valid_config() {
+    let output = validate("tests/display_name.yaml");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "OK: scores — 3 columns, path exists\n"
+    );
+}
+
+#[test]
+fn test_validate_missing_path() {
+    let output = validate("tests/validate/missing_path.yaml");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr).unwrap().contains(
+        "Could not find dataset \"scores\" under given path \"tests/data/does-not-exist.csv\"."
+    ));
+}
+
+#[test]
+fn test_validate_missing_column() {
+    let output = validate("tests/validate/missing_column.yaml");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Could not find column named 'p-value'"));
+}
@@ -23,6 +23,20 @@ let LINE_NUMBERS = false;
 
 let VEGA_EMBED_OPTIONS = { 'renderer': 'svg', 'actions': false };
 
+const DARK_VEGA_CONFIG = {
+    "background": "#212529",
+    "axis": {"gridColor": "#495057", "labelColor": "#adb5bd"},
+    "title": {"color": "#dee2e6"}
+};
+
+// Adds the colors of the dark theme to the given vega-embed options if the page uses it.
+function themed(options = {}) {
+    if (document.documentElement.getAttribute("data-bs-theme") === "dark") {
+        return {...options, "config": DARK_VEGA_CONFIG};
+    }
+    return options;
+}
+
 function renderMarkdownDescription() {
     var innerDescription = document.getElementById('innerDescription');
     const converter = new showdown.Converter({
@@ -88,9 +102,211 @@ function precision_formatter(precision, value) {
     }
 }
 
+function percentage_sorter(a, b) {
+    const parse = (value) => parseFloat(String(value).replace("%", ""));
+    const x = parse(a);
+    const y = parse(b);
+    if (isNaN(x)) {
+        return isNaN(y) ? 0 : 1;
+    }
+    if (isNaN(y)) {
+        return -1;
+    }
+    return x - y;
+}
+
+// Units of durations like "1h 32m" with their length in seconds, ordered by length like in
+// Rust.
+const DURATION_UNITS = [["d", 86400], ["h", 3600], ["min", 60], ["m", 60], ["sec", 1], ["s", 1]];
+
+// Parses the given value as a duration like "00:05:12" or "1h 32m", returning its length in
+// seconds or NaN.
+function parse_duration(value) {
+    value = String(value).trim();
+    const clock = value.match(/^(\d+):([0-5]\d):([0-5]\d(?:\.\d+)?)$/);
+    if (clock) {
+        return parseInt(clock[1]) * 3600 + parseInt(clock[2]) * 60 + parseFloat(clock[3]);
+    }
+    let seconds = 0;
+    let previous = Infinity;
+    let rest = value;
+    while (rest.length > 0) {
+        const [part, amount, unit] = rest.match(/^(\d*\.?\d*)\s*([a-z]*)\s*/i);
+        const length = (DURATION_UNITS.find(([name]) => name == unit) || [])[1];
+        if (amount === "" || isNaN(Number(amount)) || length === undefined || length >= previous) {
+            return NaN;
+        }
+        previous = length;
+        seconds += Number(amount) * length;
+        rest = rest.slice(part.length);
+    }
+    if (previous == Infinity) {
+        return NaN;
+    }
+    return seconds;
+}
+
+function duration_sorter(a, b) {
+    const x = parse_duration(a);
+    const y = parse_duration(b);
+    if (isNaN(x)) {
+        return isNaN(y) ? 0 : 1;
+    }
+    if (isNaN(y)) {
+        return -1;
+    }
+    return x - y;
+}
+
+// Compares values by their runs of digits and other characters, e.g. "sample2" before
+// "sample10", like in Rust.
+const NATURAL_COLLATOR = new Intl.Collator(undefined, {numeric: true, sensitivity: "base"});
+
+function natural_sorter(a, b) {
+    const ordering = NATURAL_COLLATOR.compare(String(a), String(b));
+    if (ordering != 0) {
+        return ordering;
+    }
+    return a < b ? -1 : a > b ? 1 : 0;
+}
+
+// Returns a copy of the filter control rendered into the page for the given column, or null
+// if the column has no filter.
+function filter_control(title) {
+    let template = document.getElementById("column-filters");
+    if (template === null) {
+        return null;
+    }
+    for (const control of template.content.children) {
+        if (control.dataset.title === title) {
+            return control.cloneNode(true);
+        }
+    }
+    return null;
+}
+
+// Escapes the given text for use in HTML content and attributes.
+function escape_html(text) {
+    return String(text)
+        .replace(/&/g, "&amp;")
+        .replace(/</g, "&lt;")
+        .replace(/>/g, "&gt;")
+        .replace(/"/g, "&quot;")
+        .replace(/'/g, "&#39;");
+}
+
+// Returns a sorter comparing values by their position in the given order. Other values follow
+// in alphabetical order and empty ones come last, like in Rust.
+function custom_order_sorter(order) {
+    const rank = (value) => {
+        const index = order.indexOf(String(value));
+        return index < 0 ? order.length : index;
+    };
+    return function(a, b) {
+        if (a === "" || a == null) {
+            return b === "" || b == null ? 0 : 1;
+        }
+        if (b === "" || b == null) {
+            return -1;
+        }
+        const ranks = rank(a) - rank(b);
+        if (ranks != 0) {
+            return ranks;
+        }
+        return a < b ? -1 : a > b ? 1 : 0;
+    };
+}
+
+// Returns the given number with thousands separators removed and "." as decimal separator
+// according to the given format. Without a format, only unambiguous thousands separators are
+// removed, like in Rust.
+function normalize_number(value, format) {
+    const decimal = format ? format.decimal_separator : ".";
+    const thousands = format ? format.thousands_separator : ",";
+    value = String(value);
+    const index = value.indexOf(decimal);
+    let integer = index < 0 ? value : value.slice(0, index);
+    const fraction = index < 0 ? null : value.slice(index + 1);
+    if (thousands && integer.includes(thousands)) {
+        const groups = integer.split(thousands);
+        const leading = groups[0].replace(/^[-+]+/, "");
+        if (!/^\d{1,3}$/.test(leading) || !groups.slice(1).every((group) => /^\d{3}$/.test(group))) {
+            return value;
+        }
+        integer = groups.join("");
+    }
+    return fraction === null ? integer : `${integer}.${fraction}`;
+}
+
+// Removes the configured prefixes and suffixes or regex matches from the given value.
+function strip_value(value, number_config) {
+    value = String(value).trim();
+    for (const affix of number_config.strip_affixes) {
+        if (value.startsWith(affix)) {
+            value = value.slice(affix.length).trim();
+        }
+        if (value.endsWith(affix)) {
+            value = value.slice(0, value.length - affix.length).trim();
+        }
+    }
+    if (number_config.strip_regex) {
+        value = value.replace(new RegExp(number_config.strip_regex, "g"), "").trim();
+    }
+    return value;
+}
+
+function number_sorter(number_config) {
+    const parse = (value) => parseFloat(normalize_number(strip_value(value, number_config), number_config.format));
+    return function(a, b) {
+        const x = parse(a);
+        const y = parse(b);
+        if (isNaN(x)) {
+            return isNaN(y) ? 0 : 1;
+        }
+        if (isNaN(y)) {
+            return -1;
+        }
+        return x - y;
+    };
+}
+
+function date_formatter(format, value, input_format) {
+    if (value == "") {
+        return "";
+    }
+    let date = input_format ? d3.timeParse(input_format)(value) : d3.isoParse(value) || new Date(value);
+    if (date == null) {
+        return value;
+    }
+    if (isNaN(date)) {
+        return value;
+    }
+    return `<span title="${value}">${d3.timeFormat(format)(date)}</span>`;
+}
+
+function renderColumnStats(stats) {
+    let rows = [["missing", stats.nulls]];
+    for (const key of ["min", "max", "mean", "stddev"]) {
+        if (stats[key] != null) {
+            rows.push([key, precision_formatter(2, stats[key])]);
+        }
+    }
+    if (stats.distinct != null) {
+        rows.push(["distinct", stats.distinct]);
+    }
+    if (stats.mode != null) {
+        rows.push(["mode", $("<span>").text(stats.mode).html()]);
+    }
+    return rows.map(([key, value]) => `<div><b>${key}:</b> ${value}</div>`).join("");
+}
+
 function createShareURL(index, webhost_url) {
     var data = $('#table').bootstrapTable('getData')[index];
     delete data["linkouts"];
+    delete data["cell_colors"];
+    delete data["cell_links"];
+    delete data["formatted_numbers"];
+    delete data["row_color"];
     delete data["share"];
     delete data["line_number"];
     var c = JSON.parse(JSON.stringify(config));
@@ -135,7 +351,7 @@ function renderTickPlot(ah, columns, title, slug_title, specs, is_float, precisi
                 var s = specs;
                 s.data = {};
                 s.data.values = data;
-                vegaEmbed(div, JSON.parse(JSON.stringify(s)), VEGA_EMBED_OPTIONS);
+                vegaEmbed(div, JSON.parse(JSON.stringify(s)), themed(VEGA_EMBED_OPTIONS));
             }
             row++;
         }
@@ -165,7 +381,7 @@ function renderBarPlot(ah, columns, title, slug_title, specs, is_float, precisio
                 var s = specs;
                 s.data = {};
                 s.data.values = data;
-                vegaEmbed(div, JSON.parse(JSON.stringify(s)), VEGA_EMBED_OPTIONS);
+                vegaEmbed(div, JSON.parse(JSON.stringify(s)), themed(VEGA_EMBED_OPTIONS));
             }
             row++;
         }
@@ -181,7 +397,7 @@ function renderDetailTickBarPlot(value, div, specs, title) {
         var s = specs;
         s.data = {};
         s.data.values = data;
-        vegaEmbed(div, JSON.parse(JSON.stringify(s)), VEGA_EMBED_OPTIONS);
+        vegaEmbed(div, JSON.parse(JSON.stringify(s)), themed(VEGA_EMBED_OPTIONS));
     }
 }
 
@@ -256,6 +472,119 @@ function shortenColumn(ah, columns, title, ellipsis, detail_mode, header_label_l
     );
 }
 
+function renderUrlColumn(ah, columns, title, truncate, detail_mode, header_label_length) {
+    let index = get_index(title, columns, detail_mode, header_label_length);
+    let table_rows = $('#table').bootstrapTable('getData');
+    $(`table > tbody > tr td:nth-child(${index})`).each(
+        function () {
+            let row = this.parentElement.dataset.index;
+            let value = table_rows[row][title];
+            // Only HTTP(S) URLs are linked, so that no scripts can be injected via other schemes.
+            if (typeof value !== "string" || !/^https?:\/\//.test(value)) {
+                return;
+            }
+            let link = document.createElement("a");
+            link.href = value;
+            link.target = "_blank";
+            link.rel = "noopener noreferrer";
+            link.title = value;
+            link.textContent = shortenUrl(value, truncate);
+            this.replaceChildren(link);
+        }
+    );
+}
+
+// Shortens the given URL to the given number of characters for display by omitting its scheme
+// and truncating its path, but keeps at least its host.
+function shortenUrl(url, length) {
+    if (url.length <= length) {
+        return url;
+    }
+    let address = url.replace(/^https?:\/\//i, "");
+    if (address.length <= length) {
+        return address;
+    }
+    let host = address.split(/[/?#]/)[0];
+    return `${address.substring(0, Math.max(length, host.length))}...`;
+}
+
+function renderImageColumn(ah, columns, title, height, width, detail_mode, header_label_length) {
+    let index = get_index(title, columns, detail_mode, header_label_length);
+    let table_rows = $('#table').bootstrapTable('getData');
+    $(`table > tbody > tr td:nth-child(${index})`).each(
+        function () {
+            let row = this.parentElement.dataset.index;
+            let value = table_rows[row][title];
+            // Besides HTTP(S) URLs, only relative paths without any scheme are shown.
+            if (typeof value !== "string" || value === "" || (/^[a-z][a-z0-9+.-]*:/i.test(value) && !/^https?:\/\//i.test(value))) {
+                return;
+            }
+            let image = document.createElement("img");
+            image.src = value;
+            image.loading = "lazy";
+            image.style.maxHeight = `${height}px`;
+            image.style.maxWidth = `${width}px`;
+            image.alt = "";
+            let link = document.createElement("a");
+            // Missing or broken images are replaced by a placeholder naming the path.
+            image.onerror = function () {
+                let placeholder = document.createElement("span");
+                placeholder.className = "text-muted";
+                placeholder.title = value;
+                placeholder.textContent = "missing image";
+                link.replaceWith(placeholder);
+            };
+            link.href = value;
+            link.target = "_blank";
+            link.rel = "noopener noreferrer";
+            link.title = value;
+            link.appendChild(image);
+            this.replaceChildren(link);
+        }
+    );
+}
+
+function colorizeValues(ah, columns, title, colors, detail_mode, header_label_length) {
+    let index = get_index(title, columns, detail_mode, header_label_length);
+    let table_rows = $('#table').bootstrapTable('getData');
+    $(`table > tbody > tr td:nth-child(${index})`).each(
+        function () {
+            let row = this.parentElement.dataset.index;
+            let value = table_rows[row][title];
+            // Only cells with a configured value are colored.
+            if (Object.prototype.hasOwnProperty.call(colors, value)) {
+                this.style.setProperty("background-color", colors[value], "important");
+            }
+        }
+    );
+}
+
+function renderBooleanColumn(ah, columns, title, true_values, false_values, detail_mode, header_label_length) {
+    let index = get_index(title, columns, detail_mode, header_label_length);
+    let table_rows = $('#table').bootstrapTable('getData');
+    $(`table > tbody > tr td:nth-child(${index})`).each(
+        function () {
+            let row = this.parentElement.dataset.index;
+            let value = table_rows[row][title];
+            if (typeof value !== "string") {
+                return;
+            }
+            let badge = document.createElement("span");
+            badge.title = value;
+            if (true_values.includes(value.toLowerCase())) {
+                badge.className = "badge badge-success";
+                badge.textContent = "\u2713";
+            } else if (false_values.includes(value.toLowerCase())) {
+                badge.className = "badge badge-danger";
+                badge.textContent = "\u2717";
+            } else {
+                return;
+            }
+            this.replaceChildren(badge);
+        }
+    );
+}
+
 function shortenHeaderRow(row, ellipsis, skip_label) {
     $(`table > thead > tr:nth-child(${row + 1}) > td`).each(
         function() {
@@ -413,7 +742,7 @@ function renderCustomPlot(ah, dp_columns, plot, dm, header_label_length) {
                 var opt = {"actions": plot.vega_controls, 'renderer': 'svg'};
                 this.innerHTML = "";
                 this.appendChild(div);
-                vegaEmbed(div, JSON.parse(JSON.stringify(s)), opt);
+                vegaEmbed(div, JSON.parse(JSON.stringify(s)), themed(opt));
             }
             row++;
         }
@@ -426,7 +755,7 @@ function renderCustomPlotDetailView(value, row, div, data_function, specs, vega_
     s.data = {};
     s.data.values = data;
     var opt = {"actions": vega_controls, 'renderer': 'svg'};
-    vegaEmbed(div, JSON.parse(JSON.stringify(s)), opt);
+    vegaEmbed(div, JSON.parse(JSON.stringify(s)), themed(opt));
 }
 
 export function embedSearch(index) {
@@ -437,7 +766,7 @@ export function embedSearch(index) {
 export function embedHistogram(show_plot, index, plot) {
     $("#histogram-modal-title").text(config.columns[index]);
     if (show_plot) {
-        vegaEmbed('#histogram-plot', plot);
+        vegaEmbed('#histogram-plot', plot, themed());
     } else {
         document.getElementById('histogram-plot').innerHTML = '<p>No reasonable plot possible.</p>';
     }
@@ -468,7 +797,7 @@ function detailFormatter(index, row) {
     let hidden_columns = config.hidden_columns;
     var html = []
     $.each(row, function (key, value) {
-        if (!hidden_columns.includes(key) && !displayed_columns.includes(key) && key !== "linkouts" && key !== "share" && key !== "line_number") {
+        if (!hidden_columns.includes(key) && !displayed_columns.includes(key) && key !== "linkouts" && key !== "cell_colors" && key !== "cell_links" && key !== "formatted_numbers" && key !== "row_color" && key !== "share" && key !== "line_number") {
             let id;
             let card_title = key;
             if (config.column_config[key].label) {
@@ -556,6 +885,30 @@ function render(additional_headers, displayed_columns, table_rows, columns, conf
         }
     }
 
+    for (const o of config.url_columns) {
+        if (displayed_columns.includes(o.title)) {
+            renderUrlColumn(additional_headers.length, displayed_columns, o.title, o.truncate, config.detail_mode, config.header_label_length);
+        }
+    }
+
+    for (const o of config.image_columns) {
+        if (displayed_columns.includes(o.title)) {
+            renderImageColumn(additional_headers.length, displayed_columns, o.title, o.height, o.width, config.detail_mode, config.header_label_length);
+        }
+    }
+
+    for (const o of config.boolean_columns) {
+        if (displayed_columns.includes(o.title)) {
+            renderBooleanColumn(additional_headers.length, displayed_columns, o.title, o.true_values, o.false_values, config.detail_mode, config.header_label_length);
+        }
+    }
+
+    for (const [title, colors] of Object.entries(config.value_colors)) {
+        if (displayed_columns.includes(title)) {
+            colorizeValues(additional_headers.length, displayed_columns, title, colors, config.detail_mode, config.header_label_length);
+        }
+    }
+
     for (const o of config.heatmaps) {
         if (displayed_columns.includes(o.title)) {
             colorizeColumn(additional_headers.length, displayed_columns, o, config.detail_mode, config.header_label_length);
@@ -664,10 +1017,26 @@ export function load() {
 
                 // Add histogram button
                 let histogram_icon = `<a class="sym" style="margin-left: 2px;" data-toggle="modal" data-target="#histogram_modal" onclick="datavzrd.embedHistogram(show_plot_${config.columns.indexOf(column)}, ${config.columns.indexOf(column)}, plot_${config.columns.indexOf(column)})"><svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-bar-chart-fill" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><rect width="4" height="5" x="1" y="10" rx="1"/><rect width="4" height="9" x="6" y="6" rx="1"/><rect width="4" height="14" x="11" y="1" rx="1"/></svg></a>`;
-                if (!config.additional_colums[column]) {
+                let is_image_column = config.image_columns.some((o) => o.title === column);
+                if (!config.additional_colums[column] && !is_image_column) {
                     title += histogram_icon;
                 }
 
+                // Add box plot button
+                if (config.column_config[column].has_boxplot) {
+                    title += `<a class="sym" style="margin-left: 2px;" data-toggle="modal" data-target="#histogram_modal" onclick="datavzrd.embedHistogram(show_boxplot_${config.columns.indexOf(column)}, ${config.columns.indexOf(column)}, boxplot_${config.columns.indexOf(column)})"><svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-boxplot" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><rect x="1" y="7.5" width="3" height="1"/><rect x="12" y="7.5" width="3" height="1"/><path d="M4 4h8v8H4zm1 1v6h2V5zm3 0v6h3V5z"/></svg></a>`;
+                }
+
+                // Add violin plot button
+                if (config.column_config[column].has_violin_plot) {
+                    title += `<a class="sym" style="margin-left: 2px;" data-toggle="modal" data-target="#histogram_modal" onclick="datavzrd.embedHistogram(show_violin_plot_${config.columns.indexOf(column)}, ${config.columns.indexOf(column)}, violin_plot_${config.columns.indexOf(column)})"><svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-violin" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><path d="M8 1c1 2 3 3 3 5s-2 2-2 4 2 2 2 3-2 2-3 2-3-1-3-2 2-1 2-3-2-2-2-4 2-3 3-5z"/></svg></a>`;
+                }
+
+                // Add scatter plot button
+                if (config.column_config[column].has_scatter_plot) {
+                    title += `<a class="sym" style="margin-left: 2px;" data-toggle="modal" data-target="#histogram_modal" onclick="datavzrd.embedHistogram(show_scatter_plot_${config.columns.indexOf(column)}, ${config.columns.indexOf(column)}, scatter_plot_${config.columns.indexOf(column)})"><svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-scatter" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><circle cx="3" cy="12" r="1.5"/><circle cx="6" cy="7" r="1.5"/><circle cx="10" cy="9" r="1.5"/><circle cx="13" cy="3" r="1.5"/></svg></a>`;
+                }
+
                 // Add static search if not single page mode
                 if (!config.is_single_page && !config.additional_colums[column] && !config.column_config[column].is_float) {
                     title += `<a class="sym" style="margin-left: 2px;" data-toggle="modal" onclick="datavzrd.embedSearch(${config.columns.indexOf(column)})" data-target="#search"><svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-search" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><path fill-rule="evenodd" d="M10.442 10.442a1 1 0 0 1 1.415 0l3.85 3.85a1 1 0 0 1-1.414 1.415l-3.85-3.85a1 1 0 0 1 0-1.415z"/><path fill-rule="evenodd" d="M6.5 12a5.5 5.5 0 1 0 0-11 5.5 5.5 0 0 0 0 11zM13 6.5a6.5 6.5 0 1 1-13 0 6.5 6.5 0 0 1 13 0z"/></svg></a>`;
@@ -692,24 +1061,104 @@ export function load() {
                     `
                 }
 
+                // Show the fraction of missing values
+                if (config.missing_values[column]) {
+                    title += `<span class="badge badge-warning" style="margin-left: 2px;">${config.missing_values[column]}</span>`;
+                }
+
+                // Add collapsible summary statistics
+                if (config.column_stats[column]) {
+                    const index = config.columns.indexOf(column);
+                    title += `<a class="sym" style="margin-left: 2px;" data-toggle="collapse" href="#stats_${index}" onclick="event.stopPropagation()"><svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-info-circle" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><path d="M8 15A7 7 0 1 1 8 1a7 7 0 0 1 0 14m0 1A8 8 0 1 0 8 0a8 8 0 0 0 0 16"/><path d="m8.93 6.588-2.29.287-.082.38.45.083c.294.07.352.176.288.469l-.738 3.468c-.194.897.105 1.319.808 1.319.545 0 1.178-.252 1.465-.598l.088-.416c-.2.176-.492.246-.686.246-.275 0-.375-.193-.304-.533zM9 4.5a1 1 0 1 1-2 0 1 1 0 0 1 2 0"/></svg></a>`;
+                    title += `<div class="collapse" id="stats_${index}"><div class="card card-body p-1 small font-weight-normal text-left">${renderColumnStats(config.column_stats[column])}</div></div>`;
+                }
+
                 let formatter = undefined;
                 if (config.format[column] != undefined) {
                     formatter = config.format[column];
                 } else {
                     if (config.column_config[column].precision !== undefined && config.column_config[column].is_float) {
                         formatter = function(value) { return precision_formatter(config.column_config[column].precision, value); };
+                    } else if (config.column_config[column].date_format) {
+                        formatter = function(value) { return date_formatter(config.column_config[column].date_format, value, config.column_config[column].input_date_format); };
                     } else {
                         formatter = function(value) { return value; };
                     }
                 }
 
+                if (config.na_values[column] && config.na_values[column].length > 0) {
+                    // Tokens denoting missing values are shown uniformly instead of as given.
+                    let value_formatter = typeof formatter === "string" ? window[formatter] : formatter;
+                    formatter = function(value, row) {
+                        if (config.na_values[column].includes(value)) {
+                            return '<span class="text-muted">NA</span>';
+                        }
+                        return value_formatter(value, row);
+                    };
+                }
+
+                if (formatted_numbers != null) {
+                    let number_formatter = typeof formatter === "string" ? window[formatter] : formatter;
+                    formatter = function(value, row) {
+                        if (row.formatted_numbers && row.formatted_numbers[column] !== undefined) {
+                            return row.formatted_numbers[column];
+                        }
+                        return number_formatter(value, row);
+                    };
+                }
+
+                if (cell_links != null) {
+                    let cell_formatter = typeof formatter === "string" ? window[formatter] : formatter;
+                    formatter = function(value, row) {
+                        let content = cell_formatter(value, row);
+                        if (row.cell_links && row.cell_links[column]) {
+                            return `<a href="${row.cell_links[column]}">${content}</a>`;
+                        }
+                        return content;
+                    };
+                }
+
                 let column_config = {
                     field: field,
                     title: title,
                     formatter: formatter,
                 }
 
-                if (config.is_single_page) {
+                if (config.percentage_columns.includes(column)) {
+                    // Percentages are displayed as given but sorted by their numeric value.
+                    column_config["sorter"] = percentage_sorter;
+                }
+
+                if (config.duration_columns.includes(column)) {
+                    // Durations are displayed as given but sorted by their length.
+                    column_config["sorter"] = duration_sorter;
+                }
+
+                if (config.natural_sort_columns.includes(column)) {
+                    // Identifiers like sample2 and sample10 are sorted by their numbers.
+                    column_config["sorter"] = natural_sorter;
+                }
+
+                if (column in config.sort_orders) {
+                    // Ordinal values like severity levels are sorted in their configured order.
+                    column_config["sorter"] = custom_order_sorter(config.sort_orders[column]);
+                }
+
+                if (column in config.number_formats) {
+                    // Numbers may contain thousands separators, a decimal comma or units.
+                    column_config["sorter"] = number_sorter(config.number_formats[column]);
+                }
+
+                if (cell_colors != null) {
+                    column_config["cellStyle"] = function(value, row) {
+                        if (row.cell_colors && row.cell_colors[column]) {
+                            return { css: { "background-color": row.cell_colors[column] } };
+                        }
+                        return {};
+                    };
+                }
+
+                if (config.is_single_page && !config.unfilterable_columns.includes(column)) {
                     column_config["filterControl"] = "input";
                 }
 
@@ -722,6 +1171,22 @@ export function load() {
             var decompressed_linkouts = decompress(linkouts);
         }
 
+        if (cell_colors != null) {
+            var decompressed_cell_colors = decompress(cell_colors);
+        }
+
+        if (cell_links != null) {
+            var decompressed_cell_links = decompress(cell_links);
+        }
+
+        if (formatted_numbers != null) {
+            var decompressed_formatted_numbers = decompress(formatted_numbers);
+        }
+
+        if (row_colors != null) {
+            var decompressed_row_colors = decompress(row_colors);
+        }
+
         if (config.webview_controls) {
             bs_table_cols.push({field: 'share', title: '', formatter: function(value){ return value }});
         }
@@ -742,6 +1207,15 @@ export function load() {
             bs_table_config.detailFormatter = detailFormatter;
         }
 
+        if (row_colors != null) {
+            bs_table_config.rowStyle = function(row) {
+                if (row.row_color) {
+                    return { css: { "background-color": row.row_color } };
+                }
+                return {};
+            };
+        }
+
         $('#table').bootstrapTable(bs_table_config);
 
         let additional_headers = "";
@@ -798,6 +1272,18 @@ export function load() {
             if (linkouts != null) {
                 row["linkouts"] = decompressed_linkouts[j];
             }
+            if (cell_colors != null) {
+                row["cell_colors"] = decompressed_cell_colors[j];
+            }
+            if (cell_links != null) {
+                row["cell_links"] = decompressed_cell_links[j];
+            }
+            if (formatted_numbers != null) {
+                row["formatted_numbers"] = decompressed_formatted_numbers[j];
+            }
+            if (row_colors != null) {
+                row["row_color"] = decompressed_row_colors[j];
+            }
             if (config.webview_controls) {
                 row["share"] = `<span data-toggle="tooltip" data-placement="left" title="Share link via QR code. Note that when using the link the row data can temporarily occur (in base64-encoded form) in the server logs of ${config.webview_host}.">
             <button class="btn btn-outline-secondary btn-table share-btn" data-row="${j}">
@@ -872,11 +1358,11 @@ export function load() {
             var index = config.columns.indexOf(col);
             var plot_id = `plot_${index}`;
             var modal_id = `#modal_${index}`;
-            if (window[plot_id]["layer"].length > 1) {
+            if (window[plot_id] && window[plot_id]["layer"].length > 1) {
                 $(modal_id).modal();
                 var marked_plot = JSON.parse(JSON.stringify(window[plot_id]));
                 marked_plot["layer"][1]["data"]["values"].push(marker);
-                vegaEmbed(`#${plot_id}`, marked_plot);
+                vegaEmbed(`#${plot_id}`, marked_plot, themed());
             }
         });
         addNumClass(config.displayed_numeric_columns, additional_headers.length, config.detail_mode, config);
@@ -897,156 +1383,79 @@ export function load() {
             for (const title of config.displayed_columns) {
                 checkbox_filters[title] = [];
             }
-            let tick_brush_specs = {
-                "width": 50,
-                "height": 12,
-                "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
-                "data": {"values":[]},
-                "mark": "tick",
-                "encoding": {
-                    "tooltip": {"field": "value", "type": "quantitative"},
-                    "x": {
-                        "field": "value",
-                        "type": "quantitative",
-                        "scale": {"type": "linear", "zero": false},
-                        "axis": {
-                            "title": null,
-                            "orient": "top",
-                            "labelFontWeight": "lighter"
-                        }
-                    },
-                    "color": {"condition": {"param": "selection", "value": "#0275d8"}, "value": "grey"}
-                },
-                "params": [{"name": "selection", "select": "interval"}],
-                "config": {"axis": {"grid": false},"background": null, "style": {"cell": {"stroke": "transparent"}}, "tick": {"thickness": 0.5, "bandSize": 10}}
-            };
-
-            let brush_domains = config.brush_domains;
-            let aux_domains = config.aux_domains;
 
-            function render_brush_plots(reset) {
-                let tick_brush = 0;
+            function render_filters(reset) {
+                let position = 0;
                 for (const title of config.displayed_columns) {
-                    let index = tick_brush + 2;
+                    let index = position + 2;
                     if (config.detail_mode || config.header_label_length > 0) {
                         index += 1;
                     }
-                    if (config.displayed_numeric_columns.includes(title)) {
-                        let plot_data = [];
-                        let values = []
-                        for (const row of table_rows) {
-                            if (row[title] != "" && row[title] != "NA") {
-                                plot_data.push({"value": parseFloat(row[title])});
-                                values.push(parseFloat(row[title]));
-                            }
-                        }
-                        let min = Math.min(...values);
-                        let max = Math.max(...values);
-                        if (brush_domains[title] != undefined && config.tick_titles.includes(title)) {
-                            min = Math.min(...brush_domains[title]);
-                            max = Math.max(...brush_domains[title]);
-                        } else if (aux_domains[title] != undefined) {
-                            let aux_values = [min, max];
-                            for (const col of aux_domains[title]) {
-                                for (const row of table_rows) {
-                                    aux_values.push(parseFloat(row[col]));
-                                }
-                            }
-                            min = Math.min(...aux_values);
-                            max = Math.max(...aux_values);
-                        }
-                        if (Number.isInteger(min)) {
-                            min = parseInt(min.toString());
-                        }
-                        if (Number.isInteger(max)) {
-                            max = parseInt(max.toString());
-                        }
-                        let legend_tick_length = min.toString().length + max.toString().length;
-                        var s = tick_brush_specs;
-                        let has_labels = legend_tick_length < 8;
-                        s.encoding.x.axis.labels = has_labels;
-                        s.data.values = plot_data;
-                        s.name = title;
-                        s.encoding.x.axis.values = [min, max];
-                        s.encoding.x.scale.domain = [min, max];
-                        let brush_class = "";
-                        if (!has_labels) {
-                            brush_class = "no-labels";
-                        }
-                        if(!reset && !config.additional_colums[title]) {
+                    if (config.unfilterable_columns.includes(title)) {
+                        // Columns configured with filterable: false get no filter in their header.
+                        position++;
+                        continue;
+                    }
+                    let control = filter_control(title);
+                    if (control !== null && control.dataset.filterControl === "range") {
+                        if(!reset) {
                             let search_icon = '<svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-search" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><path fill-rule="evenodd" d="M10.442 10.442a1 1 0 0 1 1.415 0l3.85 3.85a1 1 0 0 1-1.414 1.415l-3.85-3.85a1 1 0 0 1 0-1.415z"/><path fill-rule="evenodd" d="M6.5 12a5.5 5.5 0 1 0 0-11 5.5 5.5 0 0 0 0 11zM13 6.5a6.5 6.5 0 1 1-13 0 6.5 6.5 0 0 1 13 0z"/></svg>';
-                            $(`table > thead > tr th:nth-child(${index})  > div.th-inner`).append(`<div class="sym" data-s='${JSON.stringify(s)}' data-brush="${tick_brush}" id="filter-${index}-container" data-toggle="popover" data-placement="top" data-trigger="click focus" data-html="true" data-content="<div class='filter-brush-container'><div class='filter-brush ${brush_class}' id='brush-${tick_brush}'></div></div>"> ${search_icon}</div>`);
-                        }
-                        var opt = {"actions": false};
-                        $(`#filter-${index}-container`).on('click', function (e) {
-                            var b_specs = JSON.parse(e.currentTarget.dataset.s);
-                            if (filter_boundaries[b_specs.name] != undefined) {
-                                b_specs.params[0].value = {"x": filter_boundaries[b_specs.name].value};
-                            }
-                            vegaEmbed(`#brush-${e.currentTarget.dataset.brush}`, b_specs, opt).then(({spec, view}) => {
-                                view.addSignalListener('selection', function(name, value) {
-                                    filter_boundaries[spec.name] = value;
+                            // Numeric columns are filtered by a range slider between their minimum and maximum.
+                            control.id = `filter-${index}`;
+                            let data_content = `<div class='filter-slider-container'>${control.outerHTML}</div>`;
+                            $(`table > thead > tr th:nth-child(${index}) > div.th-inner`).append(`<div class="sym" id="filter-${index}-container" data-column-title='${title.replace(/'/g, "&#39;")}' data-toggle="popover" data-placement="top" data-trigger="click" data-html="true" data-content="${escape_html(data_content)}"> ${search_icon}</div>`);
+                            $(`#filter-${index}-container`).on('inserted.bs.popover', function (e) {
+                                let slider = document.getElementById(`filter-${index}`);
+                                let min = parseFloat(slider.dataset.min);
+                                let max = parseFloat(slider.dataset.max);
+                                let start = filter_boundaries[title] !== undefined ? filter_boundaries[title].value : [min, max];
+                                noUiSlider.create(slider, {
+                                    start: start,
+                                    connect: true,
+                                    tooltips: [true, true],
+                                    range: {"min": min, "max": max}
                                 });
-                                document.getElementById(`brush-${e.currentTarget.dataset.brush}`).addEventListener('click', function(event) {
+                                slider.noUiSlider.on('change', function (values) {
+                                    filter_boundaries[title] = {"value": values.map(parseFloat)};
                                     $('#table').bootstrapTable('filterBy', {"":""}, {
                                         'filterAlgorithm': customFilter
                                     })
                                 });
-                                // Add another event listener so the filter is still triggered when the brush is dragged outside the plot.
-                                view.addEventListener('mouseleave', function(event) {
-                                    // Only apply filter when mouseleave events happens while mouse is pressed
-                                    if (event.buttons > 0) {
-                                        $('#table').bootstrapTable('filterBy', {"":""}, {
-                                            'filterAlgorithm': customFilter
-                                        })
-                                    }
-                                });
                             })
-                        });
-                    } else if (config.unique_column_values[title] <= 10) {
+                        }
+                    } else if (control !== null && control.dataset.filterControl === "select") {
                         if(!reset) {
                             let search_icon = '<svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-search" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><path fill-rule="evenodd" d="M10.442 10.442a1 1 0 0 1 1.415 0l3.85 3.85a1 1 0 0 1-1.414 1.415l-3.85-3.85a1 1 0 0 1 0-1.415z"/><path fill-rule="evenodd" d="M6.5 12a5.5 5.5 0 1 0 0-11 5.5 5.5 0 0 0 0 11zM13 6.5a6.5 6.5 0 1 1-13 0 6.5 6.5 0 0 1 13 0z"/></svg>';
-                            let data_content = ``;
-                            let column_values = [];
-                            for (let v of table_rows) {
-                                column_values.push(v[title]);
-                            }
-                            let values = [...new Set(column_values)]
-                            for (let opt of values) {
-                                let checkbox = `<div class='form-check'>
-                                                    <input type='checkbox' class='form-check-input' id='${opt}' checked>
-                                                    <label class='form-check-label' for='${opt}'>${opt}</label>
-                                                </div>`;
-                                data_content = data_content.concat(checkbox);
-                            }
-                            $(`table > thead > tr th:nth-child(${index}) > div.th-inner`).append(`<div class="sym" id="filter-${index}-container" data-column-title='${title.replace(/'/g, "&#39;")}' data-toggle="popover" data-placement="top" data-trigger="hover click focus" data-html="true" data-content="${data_content}"> ${search_icon}</div>`);
-                            $(`#filter-${index}-container`).on('click', function (e) {
-                                $('input:checkbox').change(function (event) {
-                                    if (!event.currentTarget.checked) {
-                                        checkbox_filters[title].push(event.currentTarget.id);
-                                    } else {
-                                        checkbox_filters[title] = checkbox_filters[title].filter(function (v) {
-                                            return v !== event.currentTarget.id
-                                        })
-                                    }
+                            // Columns with few distinct values are filtered by selecting the values to show.
+                            control.id = `filter-${index}`;
+                            let values = Array.from(control.options).map((option) => option.value);
+                            let data_content = control.outerHTML;
+                            $(`table > thead > tr th:nth-child(${index}) > div.th-inner`).append(`<div class="sym" id="filter-${index}-container" data-column-title='${title.replace(/'/g, "&#39;")}' data-toggle="popover" data-placement="top" data-trigger="click" data-html="true" data-content="${escape_html(data_content)}"> ${search_icon}</div>`);
+                            $(`#filter-${index}-container`).on('inserted.bs.popover', function (e) {
+                                let select = document.getElementById(`filter-${index}`);
+                                for (let option of select.options) {
+                                    option.selected = !checkbox_filters[title].includes(option.value);
+                                }
+                                let choices = new Choices(select, {
+                                    removeItemButton: true,
+                                    shouldSort: false,
+                                    searchEnabled: values.length > 10
+                                });
+                                select.addEventListener('change', function () {
+                                    let selected = choices.getValue(true);
+                                    checkbox_filters[title] = values.filter((value) => !selected.includes(value));
                                     $('#table').bootstrapTable('filterBy', {"":""}, {
                                         'filterAlgorithm': customFilter
                                     })
-                                })
-                            });
-                            $(`#filter-${index}-container`).on('inserted.bs.popover', function (e) {
-                                for (let val of checkbox_filters[e.currentTarget.dataset.columnTitle]) {
-                                    if (checkbox_filters[e.currentTarget.dataset.columnTitle].includes(val)) {
-                                        $(`[id='${val}']`).prop('checked', false);
-                                    }
-                                }
-
+                                });
                             })
                         }
-                    } else {
+                    } else if (control !== null) {
                         if(!reset) {
                             let search_icon = '<svg width="1em" height="1em" viewBox="0 0 16 16" class="bi bi-search" fill="currentColor" xmlns="http://www.w3.org/2000/svg"><path fill-rule="evenodd" d="M10.442 10.442a1 1 0 0 1 1.415 0l3.85 3.85a1 1 0 0 1-1.414 1.415l-3.85-3.85a1 1 0 0 1 0-1.415z"/><path fill-rule="evenodd" d="M6.5 12a5.5 5.5 0 1 0 0-11 5.5 5.5 0 0 0 0 11zM13 6.5a6.5 6.5 0 1 1-13 0 6.5 6.5 0 0 1 13 0z"/></svg>';
-                            $(`table > thead > tr th:nth-child(${index}) > div.th-inner`).append(`<div class="sym" id="filter-${index}-container" data-column-title='${title.replace(/'/g, "&#39;")}' data-toggle="popover" data-placement="top" data-trigger="hover click focus" data-html="true" data-content="<input class='form-control form-control-sm' id='filter-${index}' data-title='${title.replace(/'/g, "&#39;")}' placeholder='Filter...'>"> ${search_icon}</div>`);
+                            // The text input is rendered into the page by datavzrd.
+                            control.id = `filter-${index}`;
+                            $(`table > thead > tr th:nth-child(${index}) > div.th-inner`).append(`<div class="sym" id="filter-${index}-container" data-column-title='${title.replace(/'/g, "&#39;")}' data-toggle="popover" data-placement="top" data-trigger="hover click focus" data-html="true" data-content="${escape_html(control.outerHTML)}"> ${search_icon}</div>`);
                             $(`#filter-${index}-container`).on('click', function (e) {
                                 $(`#filter-${index}`).on('input', function(event) {
                                     filters[event.target.dataset.title] = $(`#filter-${index}`).val();
@@ -1062,11 +1471,11 @@ export function load() {
                             })
                         }
                     }
-                    tick_brush++;
+                    position++;
                 }
             }
 
-            render_brush_plots(false);
+            render_filters(false);
 
             $('#clear-filter').click(function clearFilter() {
                 filter_boundaries = {};
@@ -1081,7 +1490,7 @@ export function load() {
                 $('.form-control').each( function() {
                     $(this).val('');
                 });
-                render_brush_plots(true);
+                render_filters(true);
             });
 
             function customFilter(row, filter) {
@@ -1141,6 +1550,10 @@ export function load() {
             $('#screenshotTable').on('click', function() {
                 screenshot_table()
             })
+            $('#correlationMatrix').on('click', function() {
+                $("#histogram-modal-title").text("Correlation matrix");
+                vegaEmbed('#histogram-plot', correlation_matrix, themed());
+            })
             $('#btnExcel').on('click', function() {
                 window.location.href = '../data.xlsx';
             })
@@ -1209,7 +1622,7 @@ export function load_plot(specs, data, multiple_datasets, resize) {
     } else if (!resize && !specs.hconcat && !specs.vconcat) {
         render_plot_size_controls();
     }
-    vegaEmbed('#vis', specs).then(({spec, view}) => {
+    vegaEmbed('#vis', specs, themed()).then(({spec, view}) => {
         if (resize && specs.width !== "container") {
             let width = view.width();
             let height = view.height();
@@ -17,6 +17,24 @@ export function render_html_contents() {
     if (!config.description) {
         description_html = "";
     }
+    let skipped_lines_html = "";
+    if (config.skipped_lines) {
+        skipped_lines_html = `
+        <div class="row">
+            <div class="col-md-12">
+                <pre id="skipped-lines">${config.skipped_lines}</pre>
+            </div>
+        </div>`;
+    }
+    let sample_html = "";
+    if (config.sample) {
+        sample_html = `
+        <div class="row">
+            <div class="col-md-12">
+                <div class="alert alert-info" id="sample-banner">This table shows a random sample of ${config.sample.rows} of ${config.sample.total} rows. Plots are based on all rows.</div>
+            </div>
+        </div>`;
+    }
     let inner_pagination_html = "";
     if (CURRENT_PAGE > 1) {
         if (CURRENT_PAGE !== 2) {
@@ -76,6 +94,12 @@ export function render_html_contents() {
         sidebar_html += '<li class="list-group-item sidebar-btn" id="downloadCSV-btn">Download CSV</li>';
         sidebar_html += '<li class="list-group-item sidebar-btn" id="unhide-btn">Unhide columns</li>';
     }
+    if (typeof csv_download !== "undefined" && csv_download != null) {
+        sidebar_html += `<a class="list-group-item sidebar-btn" id="downloadPageCSV-btn" href="${csv_download}" download="${config.title}.csv">Download CSV</a>`;
+    }
+    if (typeof correlation_matrix !== "undefined") {
+        sidebar_html += '<li class="list-group-item sidebar-btn" id="correlationMatrix" data-toggle="modal" data-target="#histogram_modal">Show correlation matrix</li>';
+    }
     sidebar_html += '<li class="list-group-item sidebar-btn" id="toggleLineNumbers">Show/Hide Line Numbers</li>';
     sidebar_html += '<li class="list-group-item sidebar-btn" id="screenshotTable">Export table page as SVG</li>';
     const content = `
@@ -102,6 +126,8 @@ export function render_html_contents() {
                 </div>
                 <div class="container-fluid">
                     ${description_html}
+                    ${skipped_lines_html}
+                    ${sample_html}
                     <div class="row justify-content-center">
                         <div class="col-md-12 loading text-center">
                             <div class="spinner-border" role="status">
@@ -213,6 +239,7 @@ export function render_html_contents() {
                                 <a class="nav-link" href="https://github.com/datavzrd/datavzrd">github</a>
                             </li>
                         </ul>
+                        ${config.classified_rows ? `<span class="navbar-text mr-3">column types inferred from the first ${config.classified_rows} rows</span>` : ""}
                         <span class="navbar-text">
                             created ${config.time}
                         </span>